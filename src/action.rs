@@ -1,6 +1,8 @@
 use serde::{Deserialize, Serialize};
 use strum::Display;
 
+use crate::redis_client::{export::ExportFormat, pubsub::SubscriptionKind};
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Display, Deserialize)]
 pub enum Action {
     Tick,
@@ -8,6 +10,7 @@ pub enum Action {
     Resize(u16, u16),
     Refresh,
     Error(String),
+    Info(String),
     Help,
     // Commands actions
     Quit,
@@ -23,4 +26,121 @@ pub enum Action {
     DeleteKeyspaceFilter,
     ConfirmKeyspacePopup,
     DiscardKeyspacePopup,
+    OpenConsole,
+    ExecuteConsoleCommand,
+    CompleteConsoleInput,
+    ConsoleHistoryPrevious,
+    ConsoleHistoryNext,
+    LoadConsoleReplies,
+    LoadCommandDocs,
+    DeleteSelectedKey,
+    DeleteKeys(Vec<String>),
+    ConfirmUndo,
+    Undo,
+    OpenUndoHistory,
+    UndoSelected,
+    LoadUndoHistory,
+    OpenScripts,
+    Select,
+    NextFocus,
+    Run,
+    LoadFunctions,
+    LoadScriptResult,
+    BeginTransaction,
+    ConfirmExecTransaction,
+    ExecTransaction,
+    ConfirmDiscardTransaction,
+    DiscardTransaction,
+    RemoveQueuedCommand,
+    ToggleTransactionWatch,
+    LoadTransactionResults,
+    ToggleKeyTree,
+    ApplyKeyFilter(u64),
+    NextColumn,
+    PreviousColumn,
+    SortByColumn,
+    GrowColumn,
+    ShrinkColumn,
+    ToggleSelection,
+    RangeSelect,
+    SelectPage,
+    InvertSelection,
+    ClearSelection,
+    PromptKeysTtl,
+    SetKeysTtl(Vec<String>, String),
+    ExportKeys,
+    CopyKeyNames,
+    Search,
+    NextMatch,
+    PreviousMatch,
+    OpenInspector,
+    ScrollLeft,
+    ScrollRight,
+    ToggleWrap,
+    Yank,
+    EditInEditor,
+    ToggleBookmark,
+    BookmarkPattern,
+    OpenBookmarks,
+    RemoveBookmark,
+    LoadFetchedKey,
+    PromptGoToKey,
+    GoToKey(String),
+    OpenDashboard,
+    OpenClients,
+    LoadClients,
+    ConfirmKillClient,
+    KillClient(u64),
+    OpenSlowlog,
+    LoadSlowlog,
+    ConfirmReset,
+    ResetSlowlog,
+    OpenLatency,
+    LoadLatency,
+    OpenMemory,
+    LoadMemory,
+    StopAnalysis,
+    OpenHotKeys,
+    LoadHotKeys,
+    /// Opens what is under the cursor of the hot keys, the key or the keys of the prefix.
+    DrillDown,
+    /// Writes the table of the current view to a file.
+    ExportTable(ExportFormat),
+    OpenMonitor,
+    LoadMonitor,
+    TogglePause,
+    OpenPubSub,
+    LoadPubSub,
+    LoadChannels,
+    PromptSubscribe(SubscriptionKind),
+    Subscribe(SubscriptionKind, String),
+    Unsubscribe,
+    PromptPublish,
+    /// The channel and the message, separated by the first space.
+    Publish(String),
+    /// The changed keys were fetched again.
+    KeysRefreshed,
+    OfferNotifications(String),
+    EnableNotifications(String),
+    PromptFilter,
+    ApplyFilter(String),
+    WriteBack {
+        key: String,
+        original: String,
+        commands: Vec<Vec<String>>,
+    },
+}
+
+impl Action {
+    /// Fills in what was typed into an input popup.
+    pub fn with_input(self, input: String) -> Self {
+        match self {
+            Self::SetKeysTtl(keys, _) => Self::SetKeysTtl(keys, input),
+            Self::GoToKey(_) => Self::GoToKey(input),
+            Self::ApplyFilter(_) => Self::ApplyFilter(input),
+            Self::Subscribe(kind, _) => Self::Subscribe(kind, input),
+            Self::Publish(_) => Self::Publish(input),
+            action => action,
+        }
+    }
 }
@@ -1,14 +1,28 @@
-use crate::{config, redis_client::event::RedisEvent, state::SharedState};
+use crate::{
+    clipboard,
+    config::{self, Profile},
+    editor,
+    redis_client::{
+        edit,
+        event::RedisEvent,
+        export::{self, ExportFormat},
+        pubsub::SubscriptionKind,
+        reply::Reply,
+        types::{ConsoleEntry, TransactionOutcome},
+    },
+    state::SharedState,
+};
 use color_eyre::eyre::Result;
-use crossterm::event::KeyCode;
+use crossterm::event::{KeyCode, MouseButton, MouseEvent, MouseEventKind};
 use ratatui::{
     buffer::Buffer,
     crossterm::event::KeyEvent,
     layout::{Flex, Layout},
     prelude::Rect,
     style::Stylize,
-    widgets::{Block, StatefulWidget, Widget},
+    widgets::{Block, BorderType, Borders, StatefulWidget, Widget},
 };
+use std::time::Duration;
 use tokio::sync::{
     broadcast,
     mpsc::{self, UnboundedReceiver, UnboundedSender},
@@ -20,15 +34,33 @@ use crate::{
     mode::Mode,
     tui,
     widgets::{
+        bookmarks::{Bookmark, Bookmarks, BookmarksWidget},
+        clients::{Clients, ClientsWidget},
+        console::{Console, ConsoleWidget},
+        dashboard::{Dashboard, DashboardWidget},
+        hotkeys::{DrillDown, HotKeys, HotKeysWidget},
         info::{Info, InfoWidget},
-        keyspace::{KeySpace, KeySpaceWidget},
+        inspector::{self, Inspector, InspectorWidget},
+        keyspace::{KeySpace, KeySpaceWidget, MouseTarget},
+        latency::{Latency, LatencyWidget},
+        memory::{Memory, MemoryWidget},
+        monitor::{Monitor, MonitorWidget},
+        popup::{Popup, PopupWidget},
+        pubsub::{PubSub, PubSubWidget},
+        scripts::{Scripts, ScriptsWidget},
+        slowlog::{Slowlog, SlowlogWidget},
+        transaction::{Transaction, TransactionWidget},
+        undo::{UndoHistory, UndoHistoryWidget},
     },
 };
 
+const FILTER_DEBOUNCE: Duration = Duration::from_millis(300);
+
 pub struct AppWidget;
 
 pub struct App {
     state: SharedState,
+    profile: Profile,
 
     tick_rate: f64,
     frame_rate: f64,
@@ -45,6 +77,25 @@ pub struct App {
     redis_tx: broadcast::Sender<RedisEvent>,
     summary: Info,
     keyspace: KeySpace,
+    console: Console,
+    undo_history: UndoHistory,
+    scripts: Scripts,
+    transaction: Transaction,
+    inspector: Inspector,
+    bookmarks: Bookmarks,
+    dashboard: Dashboard,
+    clients: Clients,
+    slowlog: Slowlog,
+    latency: Latency,
+    memory: Memory,
+    hot_keys: HotKeys,
+    monitor: Monitor,
+    pubsub: PubSub,
+    popup: Option<Popup>,
+    /// Bumped on every filter change, only the latest debounced scan is applied.
+    filter_generation: u64,
+    /// Whether a refresh of the changed keys is on its way, the next one waits for it.
+    refreshing_keys: bool,
 }
 
 impl App {
@@ -53,6 +104,7 @@ impl App {
         tx: UnboundedSender<Action>,
         rx: UnboundedReceiver<Action>,
         redis_tx: broadcast::Sender<RedisEvent>,
+        profile: Profile,
         tick_rate: f64,
         frame_rate: f64,
     ) -> Result<Self> {
@@ -60,13 +112,40 @@ impl App {
 
         let mode = Mode::KeySpace;
 
-        let summary = Info::new(state.info.clone());
+        let summary = Info::new(state.info.clone())
+            .profile(profile.clone())
+            .ping(state.ping.clone());
         let keyspace = KeySpace::new(Vec::new());
+        let console = Console::new();
+        let undo_history = UndoHistory::new();
+        let scripts = Scripts::new();
+        let transaction = Transaction::new();
+        let bookmarks = Bookmarks::load(&profile.name);
+        let dashboard = Dashboard::new(state.info.clone(), state.samples.clone());
+        let latency = Latency::new(state.ping.clone());
 
         Ok(Self {
             state,
+            profile,
             summary,
             keyspace,
+            console,
+            undo_history,
+            scripts,
+            transaction,
+            inspector: Inspector::new(),
+            bookmarks,
+            dashboard,
+            clients: Clients::new(),
+            slowlog: Slowlog::new(),
+            latency,
+            memory: Memory::new(),
+            hot_keys: HotKeys::new(),
+            monitor: Monitor::new(),
+            pubsub: PubSub::new(),
+            popup: None,
+            filter_generation: 0,
+            refreshing_keys: false,
             tick_rate,
             frame_rate,
             should_quit: false,
@@ -83,9 +162,9 @@ impl App {
         let mut tui = tui::Tui::new()?
             .tick_rate(self.tick_rate)
             .frame_rate(self.frame_rate)
-            .cancelation_token(cancellation_token.clone());
+            .cancelation_token(cancellation_token.clone())
+            .mouse(config::get().mouse);
 
-        // tui.mouse(true);
         tui.enter()?;
 
         loop {
@@ -101,6 +180,7 @@ impl App {
             }
             if self.should_quit {
                 tui.stop()?;
+                tui.cancel();
                 break;
             }
         }
@@ -122,6 +202,7 @@ impl App {
             tui::Event::Render => Some(Action::Render),
             tui::Event::Resize(x, y) => Some(Action::Resize(x, y)),
             tui::Event::Key(key) => self.handle_key_event(key)?,
+            tui::Event::Mouse(mouse) => self.handle_mouse_event(mouse),
             _ => None,
         };
 
@@ -129,15 +210,110 @@ impl App {
     }
 
     fn handle_key_event(&mut self, key: KeyEvent) -> Result<Option<Action>> {
+        if self.popup.is_some() {
+            return Ok(self.handle_popup_key(key));
+        }
+
         if self.keyspace.is_popup() && (key.code != KeyCode::Enter && key.code != KeyCode::Esc) {
-            self.keyspace.handle_key(key);
+            if self.keyspace.handle_key(key) {
+                self.schedule_filter_scan();
+            }
+            return Ok(None);
+        }
+
+        if self.mode == Mode::Details && self.keyspace.details_mut().is_searching() {
+            let details = self.keyspace.details_mut();
+            match key.code {
+                KeyCode::Enter => details.confirm_search(),
+                KeyCode::Esc => details.cancel_search(),
+                _ => details.handle_search_key(key),
+            }
             return Ok(None);
         }
 
+        // While typing a script only focus changes and running it are bound
+        if self.mode == Mode::Scripts && self.scripts.is_editing() {
+            return Ok(match self.handle_keybindings(key) {
+                action @ Some(Action::NextFocus | Action::Run | Action::Quit) => action,
+                Some(Action::PreviousMode) => {
+                    self.scripts.focus_library();
+                    None
+                }
+                _ => {
+                    self.scripts.handle_key(key);
+                    None
+                }
+            });
+        }
+
         let action = self.handle_keybindings(key);
+
+        // Everything that is not bound in the console is typed into the input
+        if action.is_none() && self.mode == Mode::Console {
+            self.console.handle_key(key);
+        }
+
         Ok(action.map(Into::into))
     }
 
+    fn handle_mouse_event(&mut self, mouse: MouseEvent) -> Option<Action> {
+        if let Some(ref popup) = self.popup {
+            if mouse.kind != MouseEventKind::Down(MouseButton::Left) {
+                return None;
+            }
+            let accepted = popup.button_at(mouse.column, mouse.row)?;
+            let popup = self.popup.take()?;
+            return if accepted {
+                popup.confirm_action()
+            } else {
+                None
+            };
+        }
+
+        match (self.mode, mouse.kind) {
+            (Mode::KeySpace | Mode::Details, _) if !self.keyspace.is_popup() => {
+                match self.keyspace.handle_mouse(mouse)? {
+                    MouseTarget::List { again } => {
+                        self.switch_mode(Mode::KeySpace);
+                        // A second click on a folder of the tree opens it, like <Enter>
+                        (again && self.keyspace.is_tree()).then_some(Action::ConfirmKeyspacePopup)
+                    }
+                    MouseTarget::Details => {
+                        self.remember_selected_key();
+                        self.switch_mode(Mode::Details);
+                        None
+                    }
+                }
+            }
+            (Mode::KeySpace | Mode::Details, _) => None,
+            (_, MouseEventKind::ScrollDown) => Some(Action::ScrollDown),
+            (_, MouseEventKind::ScrollUp) => Some(Action::ScrollUp),
+            _ => None,
+        }
+    }
+
+    fn handle_popup_key(&mut self, key: KeyEvent) -> Option<Action> {
+        let popup = self.popup.as_mut()?;
+
+        match key.code {
+            KeyCode::Enter => self.popup.take().and_then(Popup::confirm_action),
+            KeyCode::Esc => {
+                self.popup = None;
+                None
+            }
+            _ if popup.is_input() => {
+                popup.handle_key(key);
+                None
+            }
+            KeyCode::Char('y') => self.popup.take().and_then(Popup::confirm_action),
+            KeyCode::Char('n') | KeyCode::Char('q') => {
+                self.popup = None;
+                None
+            }
+            _ => None,
+        }
+    }
+
     fn handle_keybindings(&mut self, key: KeyEvent) -> Option<Action> {
         self.last_tick_key_events.push(key);
 
@@ -159,13 +335,22 @@ impl App {
         match action {
             Action::Tick => {
                 self.last_tick_key_events.drain(..);
+                if self.mode == Mode::Slowlog && self.slowlog.request() {
+                    self.send_redis_event(RedisEvent::FetchSlowlog);
+                }
+                if self.mode == Mode::Latency && self.latency.request() {
+                    self.send_redis_event(RedisEvent::FetchLatency);
+                }
+                if !self.refreshing_keys {
+                    self.refresh_changed_keys();
+                }
             }
             Action::Quit => self.should_quit = true,
             Action::Resize(w, h) => self.resize(tui, (w, h))?,
             Action::Render => self.draw(tui)?,
             Action::PreviousMode => self.switch_to_previous_mode(),
             Action::LoadKeySpace => self.load_keyspace(),
-            Action::RefreshSpace => self.refresh_space(),
+            Action::RefreshSpace => self.reload_space(),
             Action::LoadKeysIntoKeySpace => self.load_new_keys(),
             Action::ScrollDown => self.scroll_down(),
             Action::ScrollUp => self.scroll_up(),
@@ -173,8 +358,186 @@ impl App {
             Action::LoadPreviousPage => self.load_previous_page(),
             Action::SetKeyspaceFilter => self.enter_filter_popup(),
             Action::DiscardKeyspacePopup => self.close_popup(),
-            Action::ConfirmKeyspacePopup => self.set_keyspace_filter(),
+            Action::ConfirmKeyspacePopup => self.confirm_keyspace(),
             Action::DeleteKeyspaceFilter => self.delete_keyspace_filter(),
+            Action::OpenConsole => self.open_console(),
+            Action::ExecuteConsoleCommand => self.execute_console_command(),
+            Action::CompleteConsoleInput => self.console.complete(),
+            Action::ConsoleHistoryPrevious => self.console.history_previous(),
+            Action::ConsoleHistoryNext => self.console.history_next(),
+            Action::LoadConsoleReplies => self.load_console_replies(),
+            Action::LoadCommandDocs => self.load_command_docs(),
+            Action::DeleteSelectedKey => self.confirm_delete_key(),
+            Action::DeleteKeys(keys) => self.delete_keys(keys),
+            Action::ConfirmUndo => self.confirm_undo(),
+            Action::Undo => self.send_redis_event(RedisEvent::Undo(1)),
+            Action::OpenUndoHistory => self.open_undo_history(),
+            Action::UndoSelected => self.undo_selected(),
+            Action::LoadUndoHistory => self.load_undo_history(),
+            Action::OpenScripts => self.open_scripts(),
+            Action::Select => self.select(),
+            Action::NextFocus => self.next_focus(),
+            Action::Run => self.run_selected(),
+            Action::Refresh => self.refresh(),
+            Action::LoadFunctions => self.load_functions(),
+            Action::LoadScriptResult => self.load_script_result(),
+            Action::BeginTransaction => self.begin_transaction(),
+            Action::ConfirmExecTransaction => self.confirm_exec_transaction(),
+            Action::ExecTransaction => self.exec_transaction(),
+            Action::ConfirmDiscardTransaction => self.confirm_discard_transaction(),
+            Action::DiscardTransaction => self.discard_transaction(),
+            Action::RemoveQueuedCommand => self.transaction.remove_selected(),
+            Action::ToggleTransactionWatch => self.toggle_transaction_watch(),
+            Action::LoadTransactionResults => self.load_transaction_results(),
+            Action::ToggleKeyTree => self.keyspace.toggle_view(),
+            Action::ApplyKeyFilter(generation) => self.apply_key_filter(generation),
+            Action::NextColumn => self.next_column(),
+            Action::PreviousColumn => self.previous_column(),
+            Action::SortByColumn => self.sort_by_column(),
+            Action::GrowColumn => self.keyspace.columns_mut().grow(),
+            Action::ShrinkColumn => self.keyspace.columns_mut().shrink(),
+            Action::ToggleSelection => self.keyspace.toggle_selection(),
+            Action::RangeSelect => self.keyspace.range_select(),
+            Action::SelectPage => self.keyspace.select_page(),
+            Action::InvertSelection => self.keyspace.invert_selection(),
+            Action::ClearSelection => self.keyspace.clear_selection(),
+            Action::PromptKeysTtl => self.prompt_keys_ttl(),
+            Action::SetKeysTtl(keys, ttl) => self.set_keys_ttl(keys, &ttl),
+            Action::ExportKeys => self.export_keys(),
+            Action::CopyKeyNames => self.copy_key_names(),
+            Action::Search => self.keyspace.details_mut().start_search(),
+            Action::NextMatch => self.keyspace.details_mut().next_match(),
+            Action::PreviousMatch => self.keyspace.details_mut().previous_match(),
+            Action::OpenInspector => self.open_inspector(),
+            Action::ScrollLeft => self.inspector.scroll_left(),
+            Action::ScrollRight => self.inspector.scroll_right(),
+            Action::ToggleWrap => self.inspector.toggle_wrap(),
+            Action::Yank => self.yank_inspected(),
+            Action::EditInEditor => self.edit_in_editor(tui)?,
+            Action::ToggleBookmark => self.toggle_bookmark(),
+            Action::BookmarkPattern => self.bookmark_pattern(),
+            Action::OpenBookmarks => self.open_bookmarks(),
+            Action::RemoveBookmark => {
+                self.bookmarks.remove_selected();
+                self.save_bookmarks();
+            }
+            Action::LoadFetchedKey => self.load_fetched_key(),
+            Action::PromptGoToKey => self.prompt_go_to_key(),
+            Action::OpenDashboard => self.switch_mode(Mode::Dashboard),
+            Action::OpenClients => self.open_clients(),
+            Action::LoadClients => self.load_clients(),
+            Action::ConfirmKillClient => self.confirm_kill_client(),
+            Action::KillClient(id) => self.send_redis_event(RedisEvent::KillClient(id)),
+            Action::OpenSlowlog => self.open_slowlog(),
+            Action::LoadSlowlog => self.load_slowlog(),
+            Action::ConfirmReset => self.confirm_reset(),
+            Action::ResetSlowlog => self.send_redis_event(RedisEvent::ResetSlowlog),
+            Action::OpenLatency => {
+                self.switch_mode(Mode::Latency);
+                self.latency.request();
+                self.send_redis_event(RedisEvent::FetchLatency);
+            }
+            Action::LoadLatency => match self.state.latency.lock().unwrap().take() {
+                Some(report) => self.latency.set_report(report),
+                None => self.latency.fail(),
+            },
+            Action::OpenMemory => {
+                self.switch_mode(Mode::Memory);
+                if !self.memory.is_started() {
+                    self.analyze_memory();
+                }
+            }
+            Action::LoadMemory => {
+                if let Some(analysis) = self.state.memory.lock().unwrap().take() {
+                    self.memory.set_analysis(analysis);
+                }
+            }
+            Action::StopAnalysis => self.stop_analysis(),
+            Action::OpenHotKeys => {
+                self.switch_mode(Mode::HotKeys);
+                if !self.hot_keys.is_started() {
+                    self.find_hot_keys();
+                }
+            }
+            Action::LoadHotKeys => {
+                if let Some(report) = self.state.hot_keys.lock().unwrap().take() {
+                    self.hot_keys.set_report(report);
+                }
+            }
+            Action::DrillDown => self.drill_down(),
+            Action::ExportTable(format) => self.export_table(format),
+            Action::OpenMonitor => self.open_monitor(),
+            Action::LoadMonitor => {
+                let entries = std::mem::take(&mut *self.state.monitor.lock().unwrap());
+                self.monitor.push(entries);
+            }
+            Action::TogglePause => self.monitor.toggle_pause(),
+            Action::OpenPubSub => {
+                self.switch_mode(Mode::PubSub);
+                self.send_redis_event(RedisEvent::FetchChannels);
+            }
+            Action::LoadPubSub => {
+                let messages = std::mem::take(&mut *self.state.pubsub.lock().unwrap());
+                self.pubsub.push(messages);
+            }
+            Action::LoadChannels => {
+                if let Some(channels) = self.state.channels.lock().unwrap().take() {
+                    self.pubsub.set_channels(channels);
+                }
+            }
+            Action::PromptSubscribe(kind) => self.prompt_subscribe(kind),
+            Action::Subscribe(kind, name) if !name.is_empty() => {
+                if self.pubsub.subscribe(kind, name) {
+                    self.send_subscriptions();
+                }
+            }
+            Action::Unsubscribe => {
+                if self.pubsub.unsubscribe_selected() {
+                    self.send_subscriptions();
+                }
+            }
+            Action::PromptPublish => {
+                self.popup = Some(Popup::input(
+                    "Publish",
+                    "The channel, a space and the message",
+                    Action::Publish(String::new()),
+                ))
+            }
+            Action::Publish(input) => self.publish(&input),
+            Action::KeysRefreshed => {
+                self.refreshing_keys = false;
+                let refreshed = std::mem::take(&mut *self.state.refreshed_keys.lock().unwrap());
+                self.keyspace.refresh_keys(refreshed);
+            }
+            Action::OfferNotifications(flags) => {
+                self.popup = Some(Popup::confirm(
+                    "Keyspace notifications",
+                    format!(
+                        "notify-keyspace-events is off, so changes to keys only show after a \
+                         refresh. Set it to {flags:?} to follow them?"
+                    ),
+                    Action::EnableNotifications(flags),
+                ))
+            }
+            Action::EnableNotifications(flags) => {
+                self.send_redis_event(RedisEvent::EnableNotifications(flags))
+            }
+            Action::PromptFilter => self.prompt_filter(),
+            Action::ApplyFilter(filter) => self.apply_filter(filter),
+            Action::GoToKey(key) if !key.is_empty() => {
+                self.send_redis_event(RedisEvent::FetchKey(key))
+            }
+            Action::WriteBack {
+                key,
+                original,
+                commands,
+            } => self.send_redis_event(RedisEvent::WriteBack {
+                key,
+                original,
+                commands,
+            }),
+            Action::Error(message) => self.popup = Some(Popup::error(message)),
+            Action::Info(message) => self.popup = Some(Popup::info(message)),
             _ => {}
         }
 
@@ -195,9 +558,16 @@ impl StatefulWidget for AppWidget {
     type State = App;
 
     fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
-        Block::default()
-            .bg(config::get().colors.base00)
-            .render(area, buf);
+        let mut block = Block::default().bg(config::get().colors.base00);
+
+        if state.profile.production {
+            block = block
+                .borders(Borders::ALL)
+                .border_type(BorderType::Thick)
+                .border_style(config::get().colors.base08);
+        }
+
+        block.render(area, buf);
 
         use ratatui::layout::Constraint;
 
@@ -208,6 +578,10 @@ impl StatefulWidget for AppWidget {
 
         StatefulWidget::render(InfoWidget, footer, buf, &mut state.summary);
         state.render_main_block(main, buf);
+
+        if let Some(ref mut popup) = state.popup {
+            StatefulWidget::render(PopupWidget, area, buf, popup);
+        }
     }
 }
 
@@ -216,23 +590,839 @@ impl App {
     fn render_main_block(&mut self, area: Rect, buf: &mut Buffer) {
         match self.mode {
             Mode::KeySpace => self.render_key_space(area, buf),
+            Mode::Console => self.render_console(area, buf),
+            Mode::Transaction | Mode::Details => self.render_key_space(area, buf),
+            Mode::UndoHistory => {
+                self.render_key_space(area, buf);
+                StatefulWidget::render(UndoHistoryWidget, area, buf, &mut self.undo_history);
+            }
+            Mode::Scripts => StatefulWidget::render(ScriptsWidget, area, buf, &mut self.scripts),
+            Mode::Inspector => {
+                StatefulWidget::render(InspectorWidget, area, buf, &mut self.inspector)
+            }
+            Mode::Dashboard => {
+                StatefulWidget::render(DashboardWidget, area, buf, &mut self.dashboard)
+            }
+            Mode::Clients => StatefulWidget::render(ClientsWidget, area, buf, &mut self.clients),
+            Mode::Slowlog => StatefulWidget::render(SlowlogWidget, area, buf, &mut self.slowlog),
+            Mode::Latency => StatefulWidget::render(LatencyWidget, area, buf, &mut self.latency),
+            Mode::Memory => StatefulWidget::render(MemoryWidget, area, buf, &mut self.memory),
+            Mode::HotKeys => StatefulWidget::render(HotKeysWidget, area, buf, &mut self.hot_keys),
+            Mode::Monitor => StatefulWidget::render(MonitorWidget, area, buf, &mut self.monitor),
+            Mode::PubSub => StatefulWidget::render(PubSubWidget, area, buf, &mut self.pubsub),
+            Mode::Bookmarks => {
+                self.render_key_space(area, buf);
+                StatefulWidget::render(BookmarksWidget, area, buf, &mut self.bookmarks);
+            }
             _ => {}
         }
     }
 
+    fn render_console(&mut self, area: Rect, buf: &mut Buffer) {
+        StatefulWidget::render(ConsoleWidget, area, buf, &mut self.console);
+    }
+
     fn render_key_space(&mut self, area: Rect, buf: &mut Buffer) {
-        StatefulWidget::render(KeySpaceWidget, area, buf, &mut self.keyspace);
+        if !self.transaction.is_visible() {
+            StatefulWidget::render(
+                KeySpaceWidget::new(self.mode == Mode::Details),
+                area,
+                buf,
+                &mut self.keyspace,
+            );
+            return;
+        }
+
+        use ratatui::layout::Constraint;
+
+        let [keyspace_area, transaction_area] =
+            Layout::vertical([Constraint::Fill(1), Constraint::Length(10)]).areas(area);
+
+        StatefulWidget::render(
+            KeySpaceWidget::new(self.mode == Mode::Details),
+            keyspace_area,
+            buf,
+            &mut self.keyspace,
+        );
+        StatefulWidget::render(
+            TransactionWidget::new(self.mode == Mode::Transaction),
+            transaction_area,
+            buf,
+            &mut self.transaction,
+        );
     }
 }
 
 /// Handling events logic
 impl App {
     fn switch_to_previous_mode(&mut self) {
+        // The stream only runs while the pane is open
+        if self.mode == Mode::Monitor {
+            self.send_redis_event(RedisEvent::StopMonitor);
+        }
         if let Some(ref mut m) = self.previous_mode.or(Some(Mode::KeySpace)) {
             std::mem::swap(&mut self.mode, m);
         }
     }
 
+    fn switch_mode(&mut self, mode: Mode) {
+        if self.mode != mode {
+            self.previous_mode = Some(self.mode);
+            self.mode = mode;
+        }
+    }
+
+    fn open_console(&mut self) {
+        self.switch_mode(Mode::Console);
+
+        if !self.console.has_docs() {
+            self.send_redis_event(RedisEvent::FetchCommandDocs);
+        }
+    }
+
+    fn execute_console_command(&mut self) {
+        let Some(args) = self.console.submit() else {
+            return;
+        };
+
+        let name = String::from_utf8_lossy(&args[0]).to_uppercase();
+        let command = args
+            .iter()
+            .map(|arg| String::from_utf8_lossy(arg))
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        // The connection is shared with the rest of the app, so transactions typed in the
+        // console are queued on the client side just like the TUI edits
+        let reply = match name.as_str() {
+            "MULTI" if self.transaction.is_active() => {
+                Reply::Error("ERR MULTI calls can not be nested".into())
+            }
+            "MULTI" => {
+                self.transaction.begin();
+                Reply::Status("OK".into())
+            }
+            "EXEC" | "DISCARD" if !self.transaction.is_active() => {
+                Reply::Error(format!("ERR {name} without MULTI"))
+            }
+            "EXEC" => {
+                self.exec_transaction();
+                return;
+            }
+            "DISCARD" => {
+                self.discard_transaction();
+                Reply::Status("OK".into())
+            }
+            _ if self.transaction.is_active() => {
+                match args.into_iter().map(String::from_utf8).collect() {
+                    Ok(args) => {
+                        self.queue_command(args);
+                        Reply::Status("QUEUED".into())
+                    }
+                    Err(_) => Reply::Error("ERR only valid UTF-8 arguments can be queued".into()),
+                }
+            }
+            _ => {
+                self.send_redis_event(RedisEvent::ExecuteCommand(args));
+                return;
+            }
+        };
+
+        self.console.push_entry(ConsoleEntry { command, reply });
+    }
+
+    fn load_console_replies(&mut self) {
+        let entries = std::mem::take(&mut *self.state.console.lock().unwrap());
+        for entry in entries {
+            self.console.push_entry(entry);
+        }
+    }
+
+    fn load_command_docs(&mut self) {
+        self.console
+            .set_docs(self.state.command_docs.lock().unwrap().clone());
+    }
+
+    fn confirm_delete_key(&mut self) {
+        let keys = self.keyspace.targets();
+        let target = match keys.as_slice() {
+            [] => return,
+            [key] => key.clone(),
+            keys => format!("{} keys", keys.len()),
+        };
+
+        let message = if self.transaction.is_active() {
+            format!("Queue the deletion of {target} in the transaction?")
+        } else {
+            format!("Delete {target}? It can be restored with undo.")
+        };
+
+        self.popup = Some(Popup::confirm(
+            "Delete keys",
+            message,
+            Action::DeleteKeys(keys),
+        ));
+    }
+
+    fn delete_keys(&mut self, keys: Vec<String>) {
+        let mut args = vec!["DEL".to_owned()];
+        args.extend(keys);
+
+        self.submit_edits(vec![args]);
+        self.keyspace.clear_selection();
+    }
+
+    fn prompt_keys_ttl(&mut self) {
+        let keys = self.keyspace.targets();
+        if keys.is_empty() {
+            return;
+        }
+
+        self.popup = Some(Popup::input(
+            "Set TTL",
+            format!(
+                "TTL in seconds for {} keys, empty or -1 to persist",
+                keys.len()
+            ),
+            Action::SetKeysTtl(keys, String::new()),
+        ));
+    }
+
+    fn set_keys_ttl(&mut self, keys: Vec<String>, ttl: &str) {
+        let ttl = match ttl.trim() {
+            "" | "-1" => None,
+            ttl => match ttl.parse::<u64>() {
+                Ok(seconds) if seconds > 0 => Some(seconds.to_string()),
+                _ => {
+                    self.popup = Some(Popup::error(format!(
+                        "Invalid TTL {ttl}, expected a positive number of seconds"
+                    )));
+                    return;
+                }
+            },
+        };
+
+        let commands = keys
+            .into_iter()
+            .map(|key| match ttl {
+                Some(ref seconds) => vec!["EXPIRE".into(), key, seconds.clone()],
+                None => vec!["PERSIST".into(), key],
+            })
+            .collect();
+        self.submit_edits(commands);
+    }
+
+    fn export_keys(&mut self) {
+        let keys = self.keyspace.targets();
+        if !keys.is_empty() {
+            self.send_redis_event(RedisEvent::ExportKeys(keys));
+        }
+    }
+
+    fn copy_key_names(&mut self) {
+        let keys = self.keyspace.targets();
+        if keys.is_empty() {
+            return;
+        }
+
+        if let Err(err) = clipboard::copy(&keys.join("\n")) {
+            self.popup = Some(Popup::error(format!("Failed to copy key names: {err}")));
+        }
+    }
+
+    /// Opens the selected value on the whole screen, or just the selected element of it when
+    /// the details pane has the focus.
+    fn open_inspector(&mut self) {
+        let Some(meta) = self.keyspace.selected_key().cloned() else {
+            return;
+        };
+
+        let element = if self.mode == Mode::Details {
+            self.keyspace.details_mut().selected_element()
+        } else {
+            None
+        };
+
+        let (title, text) = match element {
+            Some((label, text)) => (format!("{} {label}", meta.key), text),
+            None => (meta.key.clone(), inspector::value_text(&meta.value)),
+        };
+
+        self.inspector.open(title, text);
+        self.switch_mode(Mode::Inspector);
+        self.remember_selected_key();
+    }
+
+    fn yank_inspected(&mut self) {
+        match clipboard::copy(self.inspector.text()) {
+            Ok(()) => self.inspector.set_yanked(),
+            Err(err) => self.popup = Some(Popup::error(format!("Failed to yank value: {err}"))),
+        }
+    }
+
+    /// Hands the terminal over to the editor with the selected value, then asks before writing
+    /// the changes back.
+    fn edit_in_editor(&mut self, tui: &mut tui::Tui) -> Result<()> {
+        let Some(meta) = self.keyspace.selected_key().cloned() else {
+            return Ok(());
+        };
+        let Some(original) = edit::editable_text(&meta.value) else {
+            self.popup = Some(Popup::error(format!(
+                "Only strings and hashes can be edited, {} is a {}",
+                meta.key,
+                meta.r_type.name()
+            )));
+            return Ok(());
+        };
+
+        self.remember_selected_key();
+        tui.exit()?;
+        let edited = editor::edit(&meta.key, edit::extension(&meta.value), &original);
+        tui.enter()?;
+        tui.terminal.clear()?;
+
+        let edited = match edited {
+            Ok(edited) => edited,
+            Err(err) => {
+                self.popup = Some(Popup::error(format!("Failed to edit {}: {err}", meta.key)));
+                return Ok(());
+            }
+        };
+
+        self.popup = Some(match edit::diff(&meta, &original, &edited) {
+            Ok(Some(changes)) => Popup::confirm(
+                "Write back",
+                format!("Write the changes to {}? {}", meta.key, changes.summary),
+                Action::WriteBack {
+                    key: meta.key,
+                    original,
+                    commands: changes.commands,
+                },
+            ),
+            Ok(None) => Popup::info(format!("No changes to {}", meta.key)),
+            Err(err) => Popup::error(format!("Can not write back {}: {err}", meta.key)),
+        });
+        Ok(())
+    }
+
+    fn toggle_bookmark(&mut self) {
+        let Some(key) = self.keyspace.selected_key().map(|meta| meta.key.clone()) else {
+            return;
+        };
+
+        let message = if self.bookmarks.toggle(Bookmark::Key(key.clone())) {
+            format!("Bookmarked {key}")
+        } else {
+            format!("Removed the bookmark of {key}")
+        };
+        self.save_bookmarks();
+        self.popup = Some(Popup::info(message));
+    }
+
+    /// Bookmarks the `SCAN MATCH` pattern the key space is narrowed to.
+    fn bookmark_pattern(&mut self) {
+        let pattern = self.state.keyspace_state.lock().unwrap().pattern.clone();
+        let Some(pattern) = pattern else {
+            self.popup = Some(Popup::error("Set a pattern to bookmark first"));
+            return;
+        };
+
+        let message = if self.bookmarks.toggle(Bookmark::Pattern(pattern.clone())) {
+            format!("Bookmarked pattern {pattern}")
+        } else {
+            format!("Removed the bookmark of pattern {pattern}")
+        };
+        self.save_bookmarks();
+        self.popup = Some(Popup::info(message));
+    }
+
+    fn open_bookmarks(&mut self) {
+        self.bookmarks.reset_selection();
+        self.switch_mode(Mode::Bookmarks);
+    }
+
+    /// Keys are fetched on their own, patterns narrow the scan of the key space.
+    fn jump_to_bookmark(&mut self) {
+        let Some(bookmark) = self.bookmarks.selected() else {
+            return;
+        };
+
+        self.switch_to_previous_mode();
+        match bookmark {
+            Bookmark::Key(key) => self.send_redis_event(RedisEvent::FetchKey(key)),
+            Bookmark::Pattern(pattern) => {
+                self.switch_mode(Mode::KeySpace);
+                self.set_scan_pattern(Some(pattern));
+            }
+        }
+    }
+
+    fn next_column(&mut self) {
+        match self.mode {
+            Mode::Clients => self.clients.next_column(),
+            Mode::Memory => self.memory.next_column(),
+            _ => self.keyspace.columns_mut().next(),
+        }
+    }
+
+    fn previous_column(&mut self) {
+        match self.mode {
+            Mode::Clients => self.clients.previous_column(),
+            Mode::Memory => self.memory.previous_column(),
+            _ => self.keyspace.columns_mut().previous(),
+        }
+    }
+
+    fn sort_by_column(&mut self) {
+        match self.mode {
+            Mode::Clients => self.clients.toggle_sort(),
+            Mode::Memory => self.memory.toggle_sort(),
+            _ => self.keyspace.toggle_sort(),
+        }
+    }
+
+    /// Asks for the text the list of the current mode is narrowed to.
+    fn prompt_filter(&mut self) {
+        let (current, hint) = match self.mode {
+            Mode::Clients => (
+                self.clients.filter(),
+                "Text in the address, name, last command or flags",
+            ),
+            Mode::Slowlog => (self.slowlog.filter(), "Text in the command"),
+            Mode::Monitor => (
+                self.monitor.filter(),
+                "Glob over the command and its arguments",
+            ),
+            _ => return,
+        };
+
+        self.popup = Some(Popup::input(
+            "Filter",
+            format!("{hint}, currently {current:?}"),
+            Action::ApplyFilter(String::new()),
+        ));
+    }
+
+    fn apply_filter(&mut self, filter: String) {
+        match self.mode {
+            Mode::Clients => self.clients.set_filter(filter),
+            Mode::Slowlog => self.slowlog.set_filter(filter),
+            Mode::Monitor => self.monitor.set_filter(filter),
+            _ => {}
+        }
+    }
+
+    fn open_clients(&mut self) {
+        self.switch_mode(Mode::Clients);
+        self.send_redis_event(RedisEvent::FetchClients);
+    }
+
+    fn load_clients(&mut self) {
+        if let Some(list) = self.state.clients.lock().unwrap().take() {
+            self.clients.set_list(list);
+        }
+    }
+
+    fn prompt_subscribe(&mut self, kind: SubscriptionKind) {
+        let (title, message) = match kind {
+            SubscriptionKind::Channel => ("Subscribe", "Channel name"),
+            SubscriptionKind::Pattern => ("Subscribe to pattern", "Glob pattern, e.g. news.*"),
+            SubscriptionKind::Shard => ("Subscribe to shard channel", "Channel name, Redis 7.0+"),
+        };
+        self.popup = Some(Popup::input(
+            title,
+            message,
+            Action::Subscribe(kind, String::new()),
+        ));
+    }
+
+    /// The Pub/Sub connection is opened again with every change of the subscriptions.
+    fn send_subscriptions(&self) {
+        self.send_redis_event(RedisEvent::Subscribe(self.pubsub.subscriptions().to_vec()));
+    }
+
+    fn publish(&mut self, input: &str) {
+        let Some((channel, message)) = input.split_once(' ') else {
+            self.popup = Some(Popup::error("Type the channel, a space and the message"));
+            return;
+        };
+
+        self.send_redis_event(RedisEvent::Publish {
+            channel: channel.into(),
+            message: message.into(),
+            kind: self.pubsub.publish_kind(channel),
+        });
+    }
+
+    fn open_monitor(&mut self) {
+        self.switch_mode(Mode::Monitor);
+        self.monitor.clear();
+        self.send_redis_event(RedisEvent::StartMonitor);
+    }
+
+    fn analyze_memory(&mut self) {
+        self.memory.start();
+        self.send_redis_event(RedisEvent::AnalyzeMemory);
+    }
+
+    fn stop_analysis(&mut self) {
+        match self.mode {
+            Mode::Memory => {
+                self.memory.stop();
+                self.send_redis_event(RedisEvent::StopAnalysis);
+            }
+            Mode::HotKeys => {
+                self.hot_keys.stop();
+                self.send_redis_event(RedisEvent::StopHotKeys);
+            }
+            _ => {}
+        }
+    }
+
+    fn find_hot_keys(&mut self) {
+        self.hot_keys.start();
+        self.send_redis_event(RedisEvent::FindHotKeys);
+    }
+
+    /// Opens the details of a hot key in the key list, prefixes are drilled into in place.
+    fn drill_down(&mut self) {
+        if let Some(DrillDown::Key(key)) = self.hot_keys.drill_down() {
+            self.switch_mode(Mode::KeySpace);
+            self.send_redis_event(RedisEvent::FetchKey(key));
+        }
+    }
+
+    /// Writes the table of the current view to the `exports` folder.
+    fn export_table(&mut self, format: ExportFormat) {
+        let (name, headers, rows) = match self.mode {
+            Mode::Memory => self.memory.export(),
+            Mode::HotKeys => self.hot_keys.export(),
+            _ => return,
+        };
+
+        self.popup = Some(match export::write_table(name, format, &headers, &rows) {
+            Ok(path) => Popup::info(format!(
+                "Exported {} rows to {}",
+                rows.len(),
+                path.display()
+            )),
+            Err(err) => Popup::error(format!("Failed to export the table: {err}")),
+        });
+    }
+
+    fn open_slowlog(&mut self) {
+        self.switch_mode(Mode::Slowlog);
+        self.slowlog.request();
+        self.send_redis_event(RedisEvent::FetchSlowlog);
+    }
+
+    fn load_slowlog(&mut self) {
+        match self.state.slowlog.lock().unwrap().take() {
+            Some(entries) => self.slowlog.set_entries(entries),
+            None => self.slowlog.fail(),
+        }
+    }
+
+    /// Asks before clearing what the current mode shows on the server.
+    fn confirm_reset(&mut self) {
+        if self.mode == Mode::Slowlog {
+            self.popup = Some(Popup::confirm(
+                "Reset slowlog",
+                "Remove every entry of the slowlog on the server?",
+                Action::ResetSlowlog,
+            ));
+        }
+    }
+
+    fn confirm_kill_client(&mut self) {
+        let Some(client) = self.clients.selected() else {
+            return;
+        };
+
+        self.popup = Some(Popup::confirm(
+            "Kill client",
+            format!(
+                "Close the connection {} from {} ({})?",
+                client.id,
+                client.addr,
+                if client.name.is_empty() {
+                    "no name"
+                } else {
+                    &client.name
+                }
+            ),
+            Action::KillClient(client.id),
+        ));
+    }
+
+    /// Asks for an exact key name, completed from the loaded page and the bookmarks.
+    fn prompt_go_to_key(&mut self) {
+        let mut completions = self.bookmarks.keys();
+        for key in self.keyspace.loaded_keys() {
+            if !completions.contains(key) {
+                completions.push(key.clone());
+            }
+        }
+        completions.sort();
+
+        self.popup = Some(
+            Popup::input(
+                "Go to key",
+                "Exact key name, <Tab> completes",
+                Action::GoToKey(String::new()),
+            )
+            .completions(completions),
+        );
+    }
+
+    fn load_fetched_key(&mut self) {
+        let Some(meta) = self.state.fetched_key.lock().unwrap().take() else {
+            return;
+        };
+
+        self.keyspace.show_key(meta);
+        self.remember_selected_key();
+        if matches!(self.mode, Mode::KeySpace | Mode::Details) {
+            self.mode = Mode::KeySpace;
+            self.switch_mode(Mode::Details);
+        }
+    }
+
+    /// Puts the selected key on top of the recently viewed ones.
+    fn remember_selected_key(&mut self) {
+        if let Some(meta) = self.keyspace.selected_key() {
+            self.bookmarks.visit(&meta.key);
+            self.save_bookmarks();
+        }
+    }
+
+    fn save_bookmarks(&mut self) {
+        if let Err(err) = self.bookmarks.save() {
+            log::error!("Failed to save bookmarks: {err:?}");
+        }
+    }
+
+    /// Changes made from the key list go into the open transaction, or straight to the server.
+    fn submit_edits(&mut self, commands: Vec<Vec<String>>) {
+        if self.transaction.is_active() {
+            for args in commands {
+                self.queue_command(args);
+            }
+        } else {
+            self.send_redis_event(RedisEvent::Batch(commands));
+        }
+    }
+
+    fn begin_transaction(&mut self) {
+        if self.transaction.is_active() {
+            self.switch_mode(Mode::Transaction);
+        } else {
+            self.transaction.begin();
+        }
+    }
+
+    fn queue_command(&mut self, args: Vec<String>) {
+        if self.transaction.is_watching() {
+            self.send_redis_event(RedisEvent::Watch(vec![args.clone()]));
+        }
+        self.transaction.queue(args);
+    }
+
+    fn toggle_transaction_watch(&mut self) {
+        if !self.transaction.is_active() {
+            return;
+        }
+
+        if self.transaction.toggle_watch() {
+            let queued = self.transaction.queued().to_vec();
+            self.send_redis_event(RedisEvent::Watch(queued));
+        } else {
+            self.send_redis_event(RedisEvent::Unwatch);
+        }
+    }
+
+    fn confirm_exec_transaction(&mut self) {
+        if !self.transaction.is_active() {
+            return;
+        }
+
+        self.popup = Some(Popup::confirm(
+            "Execute transaction",
+            format!(
+                "Submit {} queued commands with MULTI/EXEC?",
+                self.transaction.queued().len()
+            ),
+            Action::ExecTransaction,
+        ));
+    }
+
+    fn exec_transaction(&mut self) {
+        let queued = self.transaction.take_queued();
+        if !queued.is_empty() {
+            self.send_redis_event(RedisEvent::ExecTransaction(queued));
+        }
+    }
+
+    fn confirm_discard_transaction(&mut self) {
+        if !self.transaction.is_visible() {
+            return;
+        }
+
+        if !self.transaction.is_active() {
+            self.discard_transaction();
+            return;
+        }
+
+        self.popup = Some(Popup::confirm(
+            "Discard transaction",
+            format!("Drop {} queued commands?", self.transaction.queued().len()),
+            Action::DiscardTransaction,
+        ));
+    }
+
+    fn discard_transaction(&mut self) {
+        if self.transaction.is_watching() {
+            self.send_redis_event(RedisEvent::Unwatch);
+        }
+        self.transaction.discard();
+
+        if self.mode == Mode::Transaction {
+            self.switch_to_previous_mode();
+        }
+    }
+
+    fn load_transaction_results(&mut self) {
+        let Some(outcome) = self.state.transaction.lock().unwrap().take() else {
+            return;
+        };
+
+        let reply = match outcome {
+            TransactionOutcome::Executed(ref entries) => {
+                Reply::Array(entries.iter().map(|entry| entry.reply.clone()).collect())
+            }
+            TransactionOutcome::Aborted => Reply::Nil,
+            TransactionOutcome::Failed(ref err) => Reply::Error(err.clone()),
+        };
+        self.console.push_entry(ConsoleEntry {
+            command: "EXEC".into(),
+            reply,
+        });
+
+        self.transaction.set_outcome(outcome);
+    }
+
+    fn confirm_undo(&mut self) {
+        let Some(entry) = self.state.undo.entries().into_iter().next() else {
+            self.popup = Some(Popup::error("Nothing to undo"));
+            return;
+        };
+
+        let target = match entry.keys.as_slice() {
+            [] => "nothing".to_owned(),
+            [snapshot] => snapshot.key.clone(),
+            keys => format!("{} keys", keys.len()),
+        };
+        self.popup = Some(Popup::confirm(
+            "Undo",
+            format!("Restore {target} as it was before `{}`?", entry.command),
+            Action::Undo,
+        ));
+    }
+
+    fn open_undo_history(&mut self) {
+        self.load_undo_history();
+        self.switch_mode(Mode::UndoHistory);
+    }
+
+    fn load_undo_history(&mut self) {
+        self.undo_history.set_entries(self.state.undo.entries());
+    }
+
+    fn undo_selected(&mut self) {
+        if let Some(count) = self.undo_history.selected_depth() {
+            self.send_redis_event(RedisEvent::Undo(count));
+        }
+    }
+
+    fn open_scripts(&mut self) {
+        self.switch_mode(Mode::Scripts);
+        self.refresh();
+    }
+
+    fn load_functions(&mut self) {
+        self.scripts
+            .set_functions(self.state.functions.lock().unwrap().clone());
+    }
+
+    fn load_script_result(&mut self) {
+        if let Some(result) = self.state.script_result.lock().unwrap().take() {
+            self.scripts.set_result(result);
+        }
+    }
+
+    fn select(&mut self) {
+        match self.mode {
+            Mode::Scripts => self.scripts.select(),
+            Mode::Bookmarks => self.jump_to_bookmark(),
+            _ => {}
+        }
+    }
+
+    fn next_focus(&mut self) {
+        match self.mode {
+            Mode::Scripts => self.scripts.next_focus(),
+            Mode::KeySpace if self.keyspace.selected_key().is_some() => {
+                self.remember_selected_key();
+                self.switch_mode(Mode::Details)
+            }
+            Mode::Details => self.switch_mode(Mode::KeySpace),
+            Mode::Dashboard => self.dashboard.toggle_charts(),
+            Mode::PubSub => self.pubsub.next_focus(),
+            Mode::Memory => self.memory.next_view(),
+            Mode::HotKeys => self.hot_keys.next_view(),
+            _ => {}
+        }
+    }
+
+    fn run_selected(&mut self) {
+        if self.mode != Mode::Scripts {
+            return;
+        }
+
+        if let Some(event) = self.scripts.run() {
+            self.send_redis_event(event);
+        }
+    }
+
+    fn refresh(&mut self) {
+        match self.mode {
+            Mode::KeySpace => self.refresh_space(),
+            Mode::Scripts => {
+                self.scripts.load_files();
+                self.send_redis_event(RedisEvent::FetchFunctions);
+            }
+            Mode::Clients => self.send_redis_event(RedisEvent::FetchClients),
+            Mode::PubSub => self.send_redis_event(RedisEvent::FetchChannels),
+            Mode::Slowlog => {
+                self.slowlog.request();
+                self.send_redis_event(RedisEvent::FetchSlowlog);
+            }
+            Mode::Latency => {
+                self.latency.request();
+                self.send_redis_event(RedisEvent::FetchLatency);
+            }
+            Mode::Memory => self.analyze_memory(),
+            Mode::HotKeys => self.find_hot_keys(),
+            _ => {}
+        }
+    }
+
+    fn send_redis_event(&self, event: RedisEvent) {
+        if let Err(err) = self.redis_tx.send(event) {
+            log::error!("Failed to send redis event: {err:?}");
+        }
+    }
+
     fn enter_filter_popup(&mut self) {
         self.keyspace.enter_filter_pattern();
     }
@@ -242,25 +1432,76 @@ impl App {
             return;
         }
 
-        self.keyspace.exit_popup();
+        self.filter_generation += 1;
+        let pattern = self.keyspace.cancel_filter_pattern();
+        self.set_scan_pattern(pattern);
     }
 
-    fn set_keyspace_filter(&mut self) {
-        if !self.keyspace.is_popup() {
-            return;
+    /// The loaded keys are filtered right away, the server side scan waits until typing pauses.
+    fn schedule_filter_scan(&mut self) {
+        self.filter_generation += 1;
+
+        let generation = self.filter_generation;
+        let tx = self.tx.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(FILTER_DEBOUNCE).await;
+            _ = tx.send(Action::ApplyKeyFilter(generation));
+        });
+    }
+
+    fn apply_key_filter(&mut self, generation: u64) {
+        if generation == self.filter_generation {
+            self.set_scan_pattern(self.keyspace.filter_scan_pattern());
         }
+    }
 
+    fn set_scan_pattern(&mut self, pattern: Option<String>) {
         {
-            let pattern = self.keyspace.confirm_filter_pattern();
             let mut state = self.state.keyspace_state.lock().unwrap();
+            if state.pattern == pattern {
+                return;
+            }
+
             state.set_pattern(pattern.clone());
+            self.keyspace.update_filters(pattern, None);
+        }
 
+        self.keyspace.clear_tree();
+        self.refresh_space();
+    }
+
+    fn confirm_keyspace(&mut self) {
+        if self.keyspace.is_popup() {
+            self.set_keyspace_filter();
+        } else if self.keyspace.is_tree() {
+            self.toggle_tree_folder();
+        }
+    }
+
+    /// Expanding a folder narrows the scan to its prefix, collapsing it goes back to the parent.
+    fn toggle_tree_folder(&mut self) {
+        let Some(pattern) = self.keyspace.toggle_folder() else {
+            return;
+        };
+
+        {
+            let mut state = self.state.keyspace_state.lock().unwrap();
+            state.set_pattern(pattern.clone());
             self.keyspace.update_filters(pattern, None);
         }
         self.refresh_space();
     }
 
+    fn set_keyspace_filter(&mut self) {
+        self.filter_generation += 1;
+        let pattern = self.keyspace.confirm_filter_pattern();
+        self.set_scan_pattern(pattern);
+    }
+
     fn delete_keyspace_filter(&mut self) {
+        self.keyspace.clear_tree();
+        self.keyspace.clear_filter();
+
         {
             let mut state = self.state.keyspace_state.lock().unwrap();
             state.delete_pattern();
@@ -289,6 +1530,22 @@ impl App {
         self.refresh_space();
     }
 
+    /// Fetches the changed keys that are on the loaded page again and flashes their rows, at
+    /// most once per tick.
+    fn refresh_changed_keys(&mut self) {
+        let changed = std::mem::take(&mut *self.state.changed_keys.lock().unwrap());
+        let keys: Vec<String> = changed
+            .into_iter()
+            .filter(|key| self.keyspace.loaded_keys().any(|loaded| loaded == key))
+            .collect();
+
+        if !keys.is_empty() {
+            self.keyspace.flash(&keys);
+            self.refreshing_keys = true;
+            self.send_redis_event(RedisEvent::RefreshKeys(keys));
+        }
+    }
+
     fn load_new_keys(&mut self) {
         self.keyspace
             .set_keys(self.state.keys.lock().unwrap().clone());
@@ -300,6 +1557,12 @@ impl App {
         }
     }
 
+    /// Reloads the current page from scratch, the keys accumulated by the tree may be stale.
+    fn reload_space(&mut self) {
+        self.keyspace.clear_tree();
+        self.refresh_space();
+    }
+
     fn refresh_space(&mut self) {
         self.keyspace.refresh();
         if let Err(err) = self.redis_tx.send(RedisEvent::FetchKeys) {
@@ -310,6 +1573,20 @@ impl App {
     fn scroll_down(&mut self) {
         match self.mode {
             Mode::KeySpace => self.keyspace.scroll_next(),
+            Mode::Console => self.console.scroll_down(),
+            Mode::UndoHistory => self.undo_history.scroll_next(),
+            Mode::Scripts => self.scripts.scroll_next(),
+            Mode::Transaction => self.transaction.scroll_next(),
+            Mode::Details => self.keyspace.details_mut().scroll_next(),
+            Mode::Inspector => self.inspector.scroll_down(),
+            Mode::Bookmarks => self.bookmarks.scroll_next(),
+            Mode::Clients => self.clients.scroll_next(),
+            Mode::Slowlog => self.slowlog.scroll_next(),
+            Mode::Latency => self.latency.scroll_next(),
+            Mode::Memory => self.memory.scroll_next(),
+            Mode::HotKeys => self.hot_keys.scroll_next(),
+            Mode::Monitor => self.monitor.scroll_next(),
+            Mode::PubSub => self.pubsub.scroll_next(),
             _ => {}
         }
     }
@@ -317,6 +1594,20 @@ impl App {
     fn scroll_up(&mut self) {
         match self.mode {
             Mode::KeySpace => self.keyspace.scroll_previous(),
+            Mode::Console => self.console.scroll_up(),
+            Mode::UndoHistory => self.undo_history.scroll_previous(),
+            Mode::Scripts => self.scripts.scroll_previous(),
+            Mode::Transaction => self.transaction.scroll_previous(),
+            Mode::Details => self.keyspace.details_mut().scroll_previous(),
+            Mode::Inspector => self.inspector.scroll_up(),
+            Mode::Bookmarks => self.bookmarks.scroll_previous(),
+            Mode::Clients => self.clients.scroll_previous(),
+            Mode::Slowlog => self.slowlog.scroll_previous(),
+            Mode::Latency => self.latency.scroll_previous(),
+            Mode::Memory => self.memory.scroll_previous(),
+            Mode::HotKeys => self.hot_keys.scroll_previous(),
+            Mode::Monitor => self.monitor.scroll_previous(),
+            Mode::PubSub => self.pubsub.scroll_previous(),
             _ => {}
         }
     }
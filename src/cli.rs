@@ -22,4 +22,15 @@ pub struct Cli {
         default_value_t = 4.0
     )]
     pub frame_rate: f64,
+
+    #[arg(
+        short,
+        long,
+        value_name = "NAME",
+        help = "Connection profile from the config file"
+    )]
+    pub profile: Option<String>,
+
+    #[arg(long, help = "Reject every command that could modify data")]
+    pub read_only: bool,
 }
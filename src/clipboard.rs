@@ -0,0 +1,13 @@
+use std::io::Write;
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+
+use crate::tui;
+
+/// Puts `text` on the system clipboard with an OSC 52 escape sequence. The terminal does the
+/// copying, so it works over SSH too, as long as the terminal allows it.
+pub fn copy(text: &str) -> std::io::Result<()> {
+    let mut io = tui::io();
+    write!(io, "\x1b]52;c;{}\x07", STANDARD.encode(text))?;
+    io.flush()
+}
@@ -14,4 +14,72 @@ pub enum Command {
     DeletePattern,
     ClosePopup,
     EnterPopup,
+    OpenConsole,
+    Execute,
+    Complete,
+    HistoryPrevious,
+    HistoryNext,
+    DeleteKey,
+    Undo,
+    UndoHistory,
+    UndoSelected,
+    OpenScripts,
+    Select,
+    NextFocus,
+    Run,
+    Refresh,
+    Multi,
+    Exec,
+    Discard,
+    RemoveQueued,
+    ToggleWatch,
+    ToggleTree,
+    NextColumn,
+    PreviousColumn,
+    Sort,
+    GrowColumn,
+    ShrinkColumn,
+    ToggleSelection,
+    RangeSelect,
+    SelectPage,
+    InvertSelection,
+    ClearSelection,
+    SetTtl,
+    Export,
+    CopyNames,
+    Search,
+    NextMatch,
+    PreviousMatch,
+    Inspect,
+    ScrollLeft,
+    ScrollRight,
+    ToggleWrap,
+    Yank,
+    Edit,
+    Bookmark,
+    BookmarkPattern,
+    Bookmarks,
+    RemoveBookmark,
+    GoTo,
+    Dashboard,
+    Clients,
+    Kill,
+    Filter,
+    Slowlog,
+    Reset,
+    Monitor,
+    Pause,
+    PubSub,
+    Subscribe,
+    PatternSubscribe,
+    ShardSubscribe,
+    Unsubscribe,
+    Publish,
+    Latency,
+    Memory,
+    Stop,
+    ExportCsv,
+    ExportJson,
+    HotKeys,
+    DrillDown,
 }
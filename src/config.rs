@@ -1,14 +1,19 @@
 use std::{collections::HashMap, path::PathBuf, str::FromStr, sync::OnceLock};
 
-use color_eyre::eyre::Result;
+use color_eyre::eyre::{eyre, Result};
 use derive_deref::{Deref, DerefMut};
 use ratatui::style::{Color, Modifier, Style};
 use serde::{de::Deserializer, Deserialize, Serialize};
 use serde_with::{serde_as, DisplayFromStr};
+use strum::Display;
 
 use crate::{keybindings::KeyBindings, mode::Mode};
 
 const CONFIG_PATH: &str = include_str!("../.config/config.json5");
+const DEFAULT_PROFILE: &str = "default";
+const DEFAULT_URL: &str = "redis://localhost:6379";
+const DEFAULT_UNDO_LIMIT: usize = 50;
+const DEFAULT_KEY_DELIMITER: &str = ":";
 static CONFIG: OnceLock<Config> = OnceLock::new();
 
 pub fn get() -> &'static Config {
@@ -35,9 +40,109 @@ pub struct Config {
     pub colors: Base16Palette,
     #[serde(default)]
     pub keyspace: KeyspacePalette,
+    #[serde(default)]
+    pub profiles: HashMap<String, Profile>,
+    /// How many changes are kept on the undo stack of a connection.
+    #[serde(default = "default_undo_limit")]
+    pub undo_limit: usize,
+    /// Folder with `.lua` files listed in the scripts pane, `<config dir>/scripts` by default.
+    #[serde(default)]
+    pub scripts_dir: Option<PathBuf>,
+    /// Namespace separator the tree view groups keys on.
+    #[serde(default = "default_key_delimiter")]
+    pub key_delimiter: String,
+    /// Columns of the key table, in order.
+    #[serde(default = "default_key_columns")]
+    pub key_columns: Vec<ColumnConfig>,
+    /// Capture the mouse for clicking and scrolling, turn off to select text with the terminal.
+    #[serde(default = "default_mouse")]
+    pub mouse: bool,
+    /// Refresh the loaded keys when they change on the server, offering to turn on
+    /// `notify-keyspace-events` when it is off.
+    #[serde(default = "default_keyspace_notifications")]
+    pub keyspace_notifications: bool,
+}
+
+/// A column the key table can show.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize, Display)]
+pub enum KeyColumn {
+    Key,
+    Type,
+    Ttl,
+    Memory,
+    Encoding,
+    Length,
+    Idle,
+    Freq,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+pub struct ColumnConfig {
+    pub column: KeyColumn,
+    /// Percentage of the table width.
+    pub width: u16,
+}
+
+/// A named connection from the `profiles` section of the config file.
+#[derive(Clone, Debug, Deserialize)]
+pub struct Profile {
+    #[serde(skip)]
+    pub name: String,
+    #[serde(default = "default_url")]
+    pub url: String,
+    /// Reject every command that could modify data before it reaches the server.
+    #[serde(default)]
+    pub read_only: bool,
+    /// Highlight the whole UI so that a production server is never mistaken for a local one.
+    #[serde(default)]
+    pub production: bool,
+}
+
+impl Default for Profile {
+    fn default() -> Self {
+        Self {
+            name: DEFAULT_PROFILE.into(),
+            url: default_url(),
+            read_only: false,
+            production: false,
+        }
+    }
+}
+
+fn default_url() -> String {
+    DEFAULT_URL.into()
+}
+
+fn default_undo_limit() -> usize {
+    DEFAULT_UNDO_LIMIT
+}
+
+fn default_key_delimiter() -> String {
+    DEFAULT_KEY_DELIMITER.into()
+}
+
+fn default_mouse() -> bool {
+    true
+}
+
+fn default_keyspace_notifications() -> bool {
+    true
+}
+
+fn default_key_columns() -> Vec<ColumnConfig> {
+    [
+        (KeyColumn::Type, 20),
+        (KeyColumn::Key, 35),
+        (KeyColumn::Ttl, 20),
+        (KeyColumn::Memory, 25),
+    ]
+    .into_iter()
+    .map(|(column, width)| ColumnConfig { column, width })
+    .collect()
 }
 
 impl Config {
+    #[allow(clippy::clone_on_copy)]
     pub fn new() -> Result<Self, config::ConfigError> {
         let default_config: Config = json5::from_str(CONFIG_PATH).unwrap();
         let data_dir = crate::utils::get_data_dir();
@@ -91,6 +196,37 @@ impl Config {
 
         Ok(cfg)
     }
+
+    pub fn scripts_dir(&self) -> PathBuf {
+        self.scripts_dir
+            .clone()
+            .unwrap_or_else(|| self.config._config_dir.join("scripts"))
+    }
+
+    /// Resolves the profile to connect to, falling back to a local server when no name is given
+    /// and no `default` profile is configured.
+    pub fn profile(&self, name: Option<&str>) -> Result<Profile> {
+        let Some(name) = name else {
+            return Ok(self
+                .profiles
+                .get(DEFAULT_PROFILE)
+                .cloned()
+                .map(|profile| Profile {
+                    name: DEFAULT_PROFILE.into(),
+                    ..profile
+                })
+                .unwrap_or_default());
+        };
+
+        self.profiles
+            .get(name)
+            .cloned()
+            .map(|profile| Profile {
+                name: name.into(),
+                ..profile
+            })
+            .ok_or_else(|| eyre!("Profile `{name}` is not defined in the config"))
+    }
 }
 
 #[serde_as]
@@ -395,6 +531,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::identity_op)]
     fn test_parse_color_rgb() {
         let color = parse_color("rgb123");
         let expected = 16 + 1 * 36 + 2 * 6 + 3;
@@ -0,0 +1,93 @@
+#[cfg(unix)]
+use std::os::unix::fs::{DirBuilderExt, OpenOptionsExt};
+use std::{
+    fs::{DirBuilder, OpenOptions},
+    io::{Error, ErrorKind, Write},
+    path::{Path, PathBuf},
+    process::Command,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// `$VISUAL` or `$EDITOR`, with its arguments, falling back to `vi`.
+fn editor_command() -> Vec<String> {
+    std::env::var("VISUAL")
+        .or_else(|_| std::env::var("EDITOR"))
+        .ok()
+        .map(|editor| editor.split_whitespace().map(String::from).collect())
+        .filter(|args: &Vec<String>| !args.is_empty())
+        .unwrap_or_else(|| vec!["vi".into()])
+}
+
+/// A directory only the user can enter, so the value is not readable by others and the file
+/// name can not be raced for with a symlink.
+fn private_dir() -> std::io::Result<PathBuf> {
+    let mut builder = DirBuilder::new();
+    #[cfg(unix)]
+    builder.mode(0o700);
+
+    let mut attempt = 0;
+    loop {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.subsec_nanos())
+            .unwrap_or_default();
+        let dir = std::env::temp_dir().join(format!(
+            "{}-{}-{nanos:x}",
+            env!("CARGO_PKG_NAME"),
+            std::process::id()
+        ));
+
+        match builder.create(&dir) {
+            Ok(()) => return Ok(dir),
+            Err(err) if err.kind() == ErrorKind::AlreadyExists && attempt < 10 => attempt += 1,
+            Err(err) => return Err(err),
+        }
+    }
+}
+
+fn file_name(name: &str, extension: &str) -> String {
+    let name: String = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .take(64)
+        .collect();
+    format!("{name}.{extension}")
+}
+
+fn write_new(path: &Path, text: &str) -> std::io::Result<()> {
+    let mut options = OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    options.mode(0o600);
+
+    options.open(path)?.write_all(text.as_bytes())
+}
+
+/// Writes `text` to a file in a private temp directory, waits for the editor to close and returns the new content.
+/// The terminal has to be handed over to the editor before calling this.
+pub fn edit(name: &str, extension: &str, text: &str) -> std::io::Result<String> {
+    let dir = private_dir()?;
+    let path = dir.join(file_name(name, extension));
+    if let Err(err) = write_new(&path, text) {
+        let _ = std::fs::remove_dir_all(&dir);
+        return Err(err);
+    }
+
+    let editor = editor_command();
+    let status = Command::new(&editor[0])
+        .args(&editor[1..])
+        .arg(&path)
+        .status();
+
+    let result = match status {
+        Ok(status) if status.success() => std::fs::read_to_string(&path),
+        Ok(status) => Err(Error::new(
+            ErrorKind::Other,
+            format!("Editor exited with {status}"),
+        )),
+        Err(err) => Err(err),
+    };
+
+    let _ = std::fs::remove_dir_all(&dir);
+    result
+}
@@ -21,6 +21,7 @@ impl KeyBindings {
         }
     }
 
+    #[allow(dead_code)]
     pub fn get_keybindings_for_command(&self, mode: Mode, command: Command) -> Vec<Vec<KeyEvent>> {
         let bindings_for_mode = self.0.get(&mode).cloned().unwrap_or_default();
         bindings_for_mode
@@ -30,6 +31,7 @@ impl KeyBindings {
             .collect_vec()
     }
 
+    #[allow(dead_code)]
     pub fn get_config_for_command(&self, mode: Mode, command: Command) -> Vec<String> {
         self.get_keybindings_for_command(mode, command)
             .iter()
@@ -173,6 +175,7 @@ fn parse_key_code_with_modifiers(
     Ok(KeyEvent::new(c, modifiers))
 }
 
+#[allow(dead_code)]
 pub fn key_event_to_string(key_event: &KeyEvent) -> String {
     let char;
     let key_code = match key_event.code {
@@ -215,15 +218,15 @@ pub fn key_event_to_string(key_event: &KeyEvent) -> String {
     let mut modifiers = Vec::with_capacity(3);
 
     if key_event.modifiers.intersects(KeyModifiers::CONTROL) {
-        modifiers.push("Ctrl");
+        modifiers.push("ctrl");
     }
 
     if key_event.modifiers.intersects(KeyModifiers::SHIFT) {
-        modifiers.push("Shift");
+        modifiers.push("shift");
     }
 
     if key_event.modifiers.intersects(KeyModifiers::ALT) {
-        modifiers.push("Alt");
+        modifiers.push("alt");
     }
 
     let mut key = modifiers.join("-");
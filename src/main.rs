@@ -1,8 +1,10 @@
 mod action;
 mod app;
 mod cli;
+mod clipboard;
 mod command;
 mod config;
+mod editor;
 mod keybindings;
 mod mappings;
 mod mode;
@@ -24,6 +26,7 @@ use tokio_util::sync::CancellationToken;
 use crate::{
     app::App,
     utils::{initialize_logging, initialize_panic_handler},
+    widgets::columns::KeyColumns,
 };
 
 async fn tokio_main(args: Cli) -> Result<()> {
@@ -35,14 +38,27 @@ async fn tokio_main(args: Cli) -> Result<()> {
 
     let state = SharedState::default();
 
+    let mut profile = config::get().profile(args.profile.as_deref())?;
+    profile.read_only |= args.read_only;
+
     // TODO: fix error handling. Move to Trait
-    let client = redis::Client::open("redis://localhost:6379").unwrap();
-    let manager: ConnectionManager = ConnectionManager::new(client).await.unwrap();
+    let client = redis::Client::open(profile.url.as_str()).unwrap();
+    let manager: ConnectionManager = ConnectionManager::new(client.clone()).await.unwrap();
 
-    let mut watcher = Runner::new(manager.clone(), state.clone(), tx.clone())
-        .cancelation_token(cancellation_token.clone());
+    let mut watcher = Runner::new(client, manager.clone(), state.clone(), tx.clone())
+        .cancelation_token(cancellation_token.clone())
+        .read_only(profile.read_only)
+        .object_fields(KeyColumns::new(config::get().key_columns.clone()).object_fields());
 
-    let mut app = App::new(state, tx, rx, watcher.tx(), args.tick_rate, args.frame_rate)?;
+    let mut app = App::new(
+        state,
+        tx,
+        rx,
+        watcher.tx(),
+        profile,
+        args.tick_rate,
+        args.frame_rate,
+    )?;
 
     watcher.start();
     app.run(cancellation_token).await?;
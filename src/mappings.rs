@@ -1,4 +1,8 @@
-use crate::{action::Action, command::Command};
+use crate::{
+    action::Action,
+    command::Command,
+    redis_client::{export::ExportFormat, pubsub::SubscriptionKind},
+};
 
 impl From<Command> for Action {
     fn from(cmd: Command) -> Self {
@@ -14,6 +18,74 @@ impl From<Command> for Action {
             Command::SetPattern => Self::SetKeyspaceFilter,
             Command::DeletePattern => Self::DeleteKeyspaceFilter,
             Command::EnterPopup => Self::ConfirmKeyspacePopup,
+            Command::OpenConsole => Self::OpenConsole,
+            Command::Execute => Self::ExecuteConsoleCommand,
+            Command::Complete => Self::CompleteConsoleInput,
+            Command::HistoryPrevious => Self::ConsoleHistoryPrevious,
+            Command::HistoryNext => Self::ConsoleHistoryNext,
+            Command::DeleteKey => Self::DeleteSelectedKey,
+            Command::Undo => Self::ConfirmUndo,
+            Command::UndoHistory => Self::OpenUndoHistory,
+            Command::UndoSelected => Self::UndoSelected,
+            Command::OpenScripts => Self::OpenScripts,
+            Command::Select => Self::Select,
+            Command::NextFocus => Self::NextFocus,
+            Command::Run => Self::Run,
+            Command::Refresh => Self::Refresh,
+            Command::Multi => Self::BeginTransaction,
+            Command::Exec => Self::ConfirmExecTransaction,
+            Command::Discard => Self::ConfirmDiscardTransaction,
+            Command::RemoveQueued => Self::RemoveQueuedCommand,
+            Command::ToggleWatch => Self::ToggleTransactionWatch,
+            Command::ToggleTree => Self::ToggleKeyTree,
+            Command::NextColumn => Self::NextColumn,
+            Command::PreviousColumn => Self::PreviousColumn,
+            Command::Sort => Self::SortByColumn,
+            Command::GrowColumn => Self::GrowColumn,
+            Command::ShrinkColumn => Self::ShrinkColumn,
+            Command::ToggleSelection => Self::ToggleSelection,
+            Command::RangeSelect => Self::RangeSelect,
+            Command::SelectPage => Self::SelectPage,
+            Command::InvertSelection => Self::InvertSelection,
+            Command::ClearSelection => Self::ClearSelection,
+            Command::SetTtl => Self::PromptKeysTtl,
+            Command::Export => Self::ExportKeys,
+            Command::CopyNames => Self::CopyKeyNames,
+            Command::Search => Self::Search,
+            Command::NextMatch => Self::NextMatch,
+            Command::PreviousMatch => Self::PreviousMatch,
+            Command::Inspect => Self::OpenInspector,
+            Command::ScrollLeft => Self::ScrollLeft,
+            Command::ScrollRight => Self::ScrollRight,
+            Command::ToggleWrap => Self::ToggleWrap,
+            Command::Yank => Self::Yank,
+            Command::Edit => Self::EditInEditor,
+            Command::Bookmark => Self::ToggleBookmark,
+            Command::BookmarkPattern => Self::BookmarkPattern,
+            Command::Bookmarks => Self::OpenBookmarks,
+            Command::RemoveBookmark => Self::RemoveBookmark,
+            Command::GoTo => Self::PromptGoToKey,
+            Command::Dashboard => Self::OpenDashboard,
+            Command::Clients => Self::OpenClients,
+            Command::Kill => Self::ConfirmKillClient,
+            Command::Filter => Self::PromptFilter,
+            Command::Slowlog => Self::OpenSlowlog,
+            Command::Reset => Self::ConfirmReset,
+            Command::Monitor => Self::OpenMonitor,
+            Command::Pause => Self::TogglePause,
+            Command::PubSub => Self::OpenPubSub,
+            Command::Subscribe => Self::PromptSubscribe(SubscriptionKind::Channel),
+            Command::PatternSubscribe => Self::PromptSubscribe(SubscriptionKind::Pattern),
+            Command::ShardSubscribe => Self::PromptSubscribe(SubscriptionKind::Shard),
+            Command::Unsubscribe => Self::Unsubscribe,
+            Command::Publish => Self::PromptPublish,
+            Command::Latency => Self::OpenLatency,
+            Command::Memory => Self::OpenMemory,
+            Command::Stop => Self::StopAnalysis,
+            Command::ExportCsv => Self::ExportTable(ExportFormat::Csv),
+            Command::ExportJson => Self::ExportTable(ExportFormat::Json),
+            Command::HotKeys => Self::OpenHotKeys,
+            Command::DrillDown => Self::DrillDown,
         }
     }
 }
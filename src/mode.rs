@@ -5,6 +5,7 @@ pub enum PopupMode {
     Error,
     Info,
     Confirm,
+    Input,
 }
 
 #[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -12,5 +13,23 @@ pub enum Mode {
     Common,
     #[default]
     KeySpace,
+    Console,
+    UndoHistory,
+    Scripts,
+    Transaction,
+    /// The value of the selected key has the focus instead of the key list.
+    Details,
+    /// A value opened on the whole screen.
+    Inspector,
+    Bookmarks,
+    /// Every section of `INFO` on the whole screen.
+    Dashboard,
+    Clients,
+    Slowlog,
+    Monitor,
+    PubSub,
+    Latency,
+    Memory,
+    HotKeys,
     Popup(PopupMode),
 }
@@ -1,5 +1,23 @@
 pub mod client;
+pub mod clients;
+pub mod docs;
+pub mod edit;
 pub mod event;
+pub mod export;
+pub mod guard;
+pub mod hotkeys;
+pub mod info;
+pub mod latency;
+pub mod memory;
+pub mod monitor;
+pub mod notifications;
+pub mod pubsub;
+pub mod reply;
 pub mod runner;
+pub mod samples;
+pub mod scripts;
+pub mod slowlog;
 pub mod storage;
 pub mod types;
+pub mod undo;
+pub mod value;
@@ -1,34 +1,63 @@
-use std::collections::{HashMap, HashSet};
+use std::{
+    collections::{HashMap, HashSet},
+    time::{Duration, Instant},
+};
 
 use color_eyre::eyre::Result;
 
 use redis::{aio::ConnectionManager, AsyncCommands};
 
-use super::types::{KeyMeta, KeyValue, RedisInfo, RedisType};
+use super::{
+    info::RedisInfo,
+    types::{KeyMeta, KeyValue, RedisType},
+};
 
-// TODO: should be a better solution to handle this.
 pub async fn redis_info(manager: &mut ConnectionManager) -> Result<RedisInfo> {
     let info: String = redis::cmd("INFO").query_async(manager).await?;
 
-    let mut map = std::collections::HashMap::new();
+    Ok(RedisInfo::parse(&info))
+}
 
-    for c in info.split_terminator("\n") {
-        if c.starts_with("#") || c == "" {
-            continue;
-        }
+/// The round trip of a `PING`, as the app sees it.
+pub async fn ping(manager: &mut ConnectionManager) -> Result<Duration> {
+    let start = Instant::now();
+    redis::cmd("PING").query_async::<()>(manager).await?;
 
-        let pair_op = c.split_once(":");
+    Ok(start.elapsed())
+}
 
-        let Some((header, value)) = pair_op else {
-            continue;
-        };
+/// Builds a command from already split arguments, the first one being the command name.
+pub fn command_from_args(args: &[impl AsRef<[u8]>]) -> Option<redis::Cmd> {
+    let (name, rest) = args.split_first()?;
 
-        map.insert(header, value.trim());
+    let mut cmd = redis::Cmd::new();
+    cmd.arg(name.as_ref());
+    for arg in rest {
+        cmd.arg(arg.as_ref());
     }
 
-    Ok(serde_json::from_value(serde_json::json!(map))?)
+    Some(cmd)
+}
+
+/// Renders a command the way it would be typed, quoting arguments that contain whitespace.
+pub fn display_command(cmd: &redis::Cmd) -> String {
+    cmd.args_iter()
+        .filter_map(|arg| match arg {
+            redis::Arg::Simple(bytes) => Some(String::from_utf8_lossy(bytes).into_owned()),
+            redis::Arg::Cursor => None,
+        })
+        .map(|arg| {
+            if arg.is_empty() || arg.contains(char::is_whitespace) {
+                format!("{arg:?}")
+            } else {
+                arg
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
 }
 
+#[allow(dead_code)]
 pub async fn keys(
     manager: &mut ConnectionManager,
     cursor: Option<usize>,
@@ -101,10 +130,55 @@ pub async fn retrieve_ttl(
     Ok(manager.ttl(key).await?)
 }
 
+/// Which of the `OBJECT` subcommands to send for every key.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ObjectFields {
+    pub encoding: bool,
+    pub idle: bool,
+    pub freq: bool,
+}
+
+/// `OBJECT ENCODING`, `OBJECT IDLETIME` and `OBJECT FREQ`, each only when asked for. Depending on
+/// the eviction policy either the idle time or the frequency is not tracked, the server answers
+/// those with an error.
+pub async fn retrieve_object_info(
+    manager: redis::aio::ConnectionManager,
+    key: &str,
+    fields: ObjectFields,
+) -> (Option<String>, Option<u64>, Option<u64>) {
+    tokio::join!(
+        object(manager.clone(), "ENCODING", key, fields.encoding),
+        object(manager.clone(), "IDLETIME", key, fields.idle),
+        object(manager, "FREQ", key, fields.freq),
+    )
+}
+
+async fn object<T: redis::FromRedisValue>(
+    mut manager: redis::aio::ConnectionManager,
+    subcommand: &str,
+    key: &str,
+    wanted: bool,
+) -> Option<T> {
+    if !wanted {
+        return None;
+    }
+    redis::cmd("OBJECT")
+        .arg(subcommand)
+        .arg(key)
+        .query_async::<Option<T>>(&mut manager)
+        .await
+        .ok()
+        .flatten()
+}
+
 pub async fn fetch_meta(
     manager: redis::aio::ConnectionManager,
     key: &str,
+    fields: ObjectFields,
 ) -> Result<KeyMeta, Box<dyn std::error::Error + Sync + Send>> {
+    // Before reading the value, which resets the idle time and bumps the frequency
+    let (encoding, idle, freq) = retrieve_object_info(manager.clone(), key, fields).await;
+
     let ((r_type, value), size, ttl) = tokio::try_join!(
         retrieve_type_and_value(manager.clone(), key),
         retrieve_memory_usage(manager.clone(), key),
@@ -117,5 +191,8 @@ pub async fn fetch_meta(
         size,
         ttl,
         key: key.into(),
+        encoding,
+        idle,
+        freq,
     })
 }
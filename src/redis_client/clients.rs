@@ -0,0 +1,116 @@
+use std::collections::HashMap;
+
+/// A connection as `CLIENT LIST` and `CLIENT INFO` describe it.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ClientInfo {
+    pub id: u64,
+    pub addr: String,
+    pub name: String,
+    pub db: u32,
+    /// Seconds since the connection was made.
+    pub age: u64,
+    /// Seconds since the last command.
+    pub idle: u64,
+    /// Last command run, `NULL` before the first one.
+    pub cmd: String,
+    /// Total memory of the connection, `tot-mem`, in bytes.
+    pub memory: u64,
+    pub flags: String,
+}
+
+impl ClientInfo {
+    /// Parses one `id=3 addr=127.0.0.1:5000 ...` line, `None` without an id.
+    pub fn parse(line: &str) -> Option<Self> {
+        let fields: HashMap<&str, &str> = line
+            .split_whitespace()
+            .filter_map(|field| field.split_once('='))
+            .collect();
+
+        let text = |name: &str| fields.get(name).unwrap_or(&"").to_string();
+        let number = |name: &str| {
+            fields
+                .get(name)
+                .and_then(|value| value.parse().ok())
+                .unwrap_or_default()
+        };
+
+        Some(Self {
+            id: fields.get("id")?.parse().ok()?,
+            addr: text("addr"),
+            name: text("name"),
+            db: fields
+                .get("db")
+                .and_then(|db| db.parse().ok())
+                .unwrap_or_default(),
+            age: number("age"),
+            idle: number("idle"),
+            cmd: text("cmd"),
+            memory: number("tot-mem"),
+            flags: text("flags"),
+        })
+    }
+
+    /// Whether the filter appears in the address, name, last command or flags.
+    pub fn matches(&self, filter: &str) -> bool {
+        let filter = filter.to_lowercase();
+        [&self.addr, &self.name, &self.cmd, &self.flags]
+            .iter()
+            .any(|field| field.to_lowercase().contains(&filter))
+    }
+}
+
+/// Parses a `CLIENT LIST` reply, one client per line.
+pub fn parse_client_list(text: &str) -> Vec<ClientInfo> {
+    text.lines().filter_map(ClientInfo::parse).collect()
+}
+
+/// The connections of the server, with the one the app itself uses.
+#[derive(Debug, Default, Clone)]
+pub struct ClientList {
+    pub clients: Vec<ClientInfo>,
+    /// From `CLIENT INFO`, missing before Redis 6.2.
+    pub own: Option<ClientInfo>,
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_client_list() {
+        let list = "id=3 addr=127.0.0.1:52555 laddr=127.0.0.1:6379 fd=8 name=worker age=855 \
+                    idle=2 flags=N db=1 sub=0 psub=0 multi=-1 cmd=client|list tot-mem=22298\n\
+                    id=4 addr=10.0.0.9:40112 fd=9 name= age=10 idle=10 flags=P db=0 cmd=subscribe\n\
+                    garbage\n";
+
+        assert_eq!(
+            parse_client_list(list),
+            [
+                ClientInfo {
+                    id: 3,
+                    addr: "127.0.0.1:52555".into(),
+                    name: "worker".into(),
+                    db: 1,
+                    age: 855,
+                    idle: 2,
+                    cmd: "client|list".into(),
+                    memory: 22298,
+                    flags: "N".into(),
+                },
+                ClientInfo {
+                    id: 4,
+                    addr: "10.0.0.9:40112".into(),
+                    name: String::new(),
+                    db: 0,
+                    age: 10,
+                    idle: 10,
+                    cmd: "subscribe".into(),
+                    memory: 0,
+                    flags: "P".into(),
+                },
+            ]
+        );
+    }
+}
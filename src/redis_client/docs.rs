@@ -0,0 +1,225 @@
+use redis::Value;
+
+use super::value::{as_string, pairs, strings};
+
+/// A single command as described by `COMMAND DOCS`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CommandDoc {
+    pub name: String,
+    pub summary: String,
+    pub group: String,
+    pub arguments: Vec<CommandArgument>,
+    pub subcommands: Vec<CommandDoc>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CommandArgument {
+    pub name: String,
+    pub kind: String,
+    pub token: Option<String>,
+    pub optional: bool,
+    pub multiple: bool,
+    pub arguments: Vec<CommandArgument>,
+}
+
+impl CommandDoc {
+    /// Parses the whole `COMMAND DOCS` reply, both the RESP2 flat array and the RESP3 map.
+    pub fn parse_all(value: &Value) -> Vec<CommandDoc> {
+        let mut docs: Vec<CommandDoc> = pairs(value)
+            .into_iter()
+            .map(|(name, doc)| Self::parse(name, doc))
+            .collect();
+        docs.sort_by(|a, b| a.name.cmp(&b.name));
+        docs
+    }
+
+    fn parse(name: String, value: &Value) -> CommandDoc {
+        let mut doc = CommandDoc {
+            // Subcommands are reported as `container|subcommand`
+            name: name.rsplit('|').next().unwrap_or_default().to_uppercase(),
+            ..Default::default()
+        };
+
+        for (field, value) in pairs(value) {
+            match field.as_str() {
+                "summary" => doc.summary = as_string(value).unwrap_or_default(),
+                "group" => doc.group = as_string(value).unwrap_or_default(),
+                "arguments" => doc.arguments = CommandArgument::parse_all(value),
+                "subcommands" => doc.subcommands = Self::parse_all(value),
+                _ => {}
+            }
+        }
+
+        doc
+    }
+
+    /// Usage line in the same notation as the Redis documentation, e.g. `key [NX | XX]`.
+    pub fn syntax(&self) -> String {
+        self.arguments
+            .iter()
+            .map(CommandArgument::syntax)
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// Every keyword token accepted by the command, used for tab completion.
+    pub fn tokens(&self) -> Vec<String> {
+        let mut tokens = Vec::new();
+        for arg in &self.arguments {
+            arg.collect_tokens(&mut tokens);
+        }
+        tokens.sort();
+        tokens.dedup();
+        tokens
+    }
+}
+
+impl CommandArgument {
+    fn parse_all(value: &Value) -> Vec<CommandArgument> {
+        let Value::Array(args) = value else {
+            return Vec::new();
+        };
+
+        args.iter().map(Self::parse).collect()
+    }
+
+    fn parse(value: &Value) -> CommandArgument {
+        let mut arg = CommandArgument::default();
+
+        for (field, value) in pairs(value) {
+            match field.as_str() {
+                "name" => arg.name = as_string(value).unwrap_or_default(),
+                "type" => arg.kind = as_string(value).unwrap_or_default(),
+                "token" => arg.token = as_string(value),
+                "flags" => {
+                    let flags = strings(value);
+                    arg.optional = flags.iter().any(|f| f == "optional");
+                    arg.multiple = flags.iter().any(|f| f == "multiple");
+                }
+                "arguments" => arg.arguments = Self::parse_all(value),
+                _ => {}
+            }
+        }
+
+        arg
+    }
+
+    pub fn syntax(&self) -> String {
+        let inner = match self.kind.as_str() {
+            "oneof" => self
+                .arguments
+                .iter()
+                .map(Self::syntax)
+                .collect::<Vec<_>>()
+                .join(" | "),
+            "block" => self
+                .arguments
+                .iter()
+                .map(Self::syntax)
+                .collect::<Vec<_>>()
+                .join(" "),
+            "pure-token" => self.token.clone().unwrap_or_default(),
+            _ => self.name.clone(),
+        };
+
+        let inner = match self.token {
+            Some(ref token) if self.kind != "pure-token" => format!("{token} {inner}"),
+            _ => inner,
+        };
+
+        let inner = if self.multiple {
+            format!("{inner} [{inner} ...]")
+        } else {
+            inner
+        };
+
+        if self.optional {
+            format!("[{inner}]")
+        } else {
+            inner
+        }
+    }
+
+    fn collect_tokens(&self, tokens: &mut Vec<String>) {
+        if let Some(ref token) = self.token {
+            tokens.push(token.to_uppercase());
+        }
+        for arg in &self.arguments {
+            arg.collect_tokens(tokens);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::redis_client::value::test_support::bulk;
+
+    fn token(name: &str) -> Value {
+        Value::Array(vec![
+            bulk("name"),
+            bulk(&name.to_lowercase()),
+            bulk("type"),
+            bulk("pure-token"),
+            bulk("token"),
+            bulk(name),
+        ])
+    }
+
+    fn set_docs() -> Value {
+        Value::Array(vec![
+            bulk("set"),
+            Value::Array(vec![
+                bulk("summary"),
+                bulk("Sets the string value of a key"),
+                bulk("group"),
+                bulk("string"),
+                bulk("arguments"),
+                Value::Array(vec![
+                    Value::Array(vec![bulk("name"), bulk("key"), bulk("type"), bulk("key")]),
+                    Value::Array(vec![
+                        bulk("name"),
+                        bulk("value"),
+                        bulk("type"),
+                        bulk("string"),
+                    ]),
+                    Value::Array(vec![
+                        bulk("name"),
+                        bulk("condition"),
+                        bulk("type"),
+                        bulk("oneof"),
+                        bulk("flags"),
+                        Value::Array(vec![Value::SimpleString("optional".into())]),
+                        bulk("arguments"),
+                        Value::Array(vec![token("NX"), token("XX")]),
+                    ]),
+                ]),
+            ]),
+        ])
+    }
+
+    #[test]
+    fn test_parse_docs() {
+        let docs = CommandDoc::parse_all(&set_docs());
+
+        assert_eq!(docs.len(), 1);
+        assert_eq!(docs[0].name, "SET");
+        assert_eq!(docs[0].group, "string");
+        assert_eq!(docs[0].arguments.len(), 3);
+        assert!(docs[0].arguments[2].optional);
+    }
+
+    #[test]
+    fn test_syntax() {
+        let docs = CommandDoc::parse_all(&set_docs());
+        assert_eq!(docs[0].syntax(), "key value [NX | XX]");
+    }
+
+    #[test]
+    fn test_tokens() {
+        let docs = CommandDoc::parse_all(&set_docs());
+        assert_eq!(docs[0].tokens(), ["NX", "XX"]);
+    }
+}
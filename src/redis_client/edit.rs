@@ -0,0 +1,185 @@
+use serde_json::{Map, Value};
+
+use super::{
+    export,
+    types::{KeyMeta, KeyValue},
+};
+
+/// What editing a value changes, ready to be confirmed and written back.
+#[derive(Debug, PartialEq)]
+pub struct Edit {
+    pub commands: Vec<Vec<String>>,
+    pub summary: String,
+}
+
+/// The value as it is written to the editor, strings as they are and hashes as a JSON object.
+/// Other types can not be edited as text.
+pub fn editable_text(value: &KeyValue) -> Option<String> {
+    match value {
+        KeyValue::String(value) => Some(value.clone()),
+        KeyValue::Hash(_) => serde_json::to_string_pretty(&export::value_to_json(value)).ok(),
+        _ => None,
+    }
+}
+
+/// File extension of the temp file, so that the editor picks the right syntax.
+pub fn extension(value: &KeyValue) -> &'static str {
+    match value {
+        KeyValue::Hash(_) => "json",
+        _ => "txt",
+    }
+}
+
+/// Diffs the edited text against the value, `None` when nothing changed.
+pub fn diff(meta: &KeyMeta, original: &str, edited: &str) -> Result<Option<Edit>, String> {
+    // Most editors end the file with a newline
+    let edited = match edited.strip_suffix('\n') {
+        Some(stripped) if !original.ends_with('\n') => stripped,
+        _ => edited,
+    };
+
+    if edited == original {
+        return Ok(None);
+    }
+
+    match meta.value {
+        KeyValue::String(_) => Ok(Some(Edit {
+            commands: vec![vec![
+                "SET".into(),
+                meta.key.clone(),
+                edited.into(),
+                "KEEPTTL".into(),
+            ]],
+            summary: format!(
+                "{} lines ({} bytes) -> {} lines ({} bytes)",
+                original.lines().count(),
+                original.len(),
+                edited.lines().count(),
+                edited.len()
+            ),
+        })),
+        KeyValue::Hash(_) => diff_hash(&meta.key, original, edited).map(Some),
+        _ => Err(format!("{} values can not be edited", meta.r_type.name())),
+    }
+}
+
+fn parse_fields(text: &str) -> Result<Map<String, Value>, String> {
+    serde_json::from_str(text).map_err(|err| format!("Not a JSON object: {err}"))
+}
+
+fn field_value(field: &str, value: &Value) -> Result<String, String> {
+    match value {
+        Value::String(value) => Ok(value.clone()),
+        Value::Number(_) | Value::Bool(_) => Ok(value.to_string()),
+        _ => Err(format!("Field {field} must be a string")),
+    }
+}
+
+fn diff_hash(key: &str, original: &str, edited: &str) -> Result<Edit, String> {
+    let before = parse_fields(original)?;
+    let after = parse_fields(edited)?;
+
+    let mut set = Vec::new();
+    let (mut added, mut changed) = (0, 0);
+    for (field, value) in &after {
+        let value = field_value(field, value)?;
+        match before.get(field) {
+            Some(Value::String(old)) if *old == value => continue,
+            Some(_) => changed += 1,
+            None => added += 1,
+        }
+        set.push(field.clone());
+        set.push(value);
+    }
+
+    let removed: Vec<String> = before
+        .keys()
+        .filter(|field| !after.contains_key(*field))
+        .cloned()
+        .collect();
+
+    let mut commands = Vec::new();
+    if !set.is_empty() {
+        commands.push([vec!["HSET".into(), key.into()], set].concat());
+    }
+    if !removed.is_empty() {
+        commands.push([vec!["HDEL".into(), key.into()], removed.clone()].concat());
+    }
+
+    Ok(Edit {
+        commands,
+        summary: format!(
+            "{added} fields added, {changed} changed, {} removed",
+            removed.len()
+        ),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::redis_client::types::RedisType;
+
+    fn meta(r_type: RedisType, value: KeyValue) -> KeyMeta {
+        KeyMeta {
+            key: "k".into(),
+            r_type,
+            size: 0,
+            ttl: -1,
+            value,
+            encoding: None,
+            idle: None,
+            freq: None,
+        }
+    }
+
+    #[test]
+    fn test_diff_string() {
+        let meta = meta(RedisType::String, KeyValue::String("a".into()));
+
+        assert_eq!(diff(&meta, "a", "a\n"), Ok(None));
+        assert_eq!(
+            diff(&meta, "a", "b\nc\n").unwrap().unwrap().commands,
+            [["SET", "k", "b\nc", "KEEPTTL"]]
+        );
+    }
+
+    #[test]
+    fn test_diff_hash() {
+        let value = KeyValue::Hash(HashMap::from([
+            ("a".into(), "1".into()),
+            ("b".into(), "2".into()),
+            ("c".into(), "3".into()),
+        ]));
+        let original = editable_text(&value).unwrap();
+        let meta = meta(RedisType::Hash, value);
+
+        let edit = diff(&meta, &original, r#"{"a": "1", "b": 5, "d": "4"}"#)
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            edit,
+            Edit {
+                commands: vec![
+                    vec![
+                        "HSET".into(),
+                        "k".into(),
+                        "b".into(),
+                        "5".into(),
+                        "d".into(),
+                        "4".into()
+                    ],
+                    vec!["HDEL".into(), "k".into(), "c".into()],
+                ],
+                summary: "1 fields added, 1 changed, 1 removed".into(),
+            }
+        );
+
+        assert!(diff(&meta, &original, r#"{"a": [1]}"#).is_err());
+        assert!(diff(&meta, &original, "not json").is_err());
+    }
+}
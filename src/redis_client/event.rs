@@ -1,4 +1,65 @@
+use super::pubsub::{Subscription, SubscriptionKind};
+
 #[derive(Clone, Debug)]
 pub enum RedisEvent {
     FetchKeys,
+    FetchCommandDocs,
+    ExecuteCommand(Vec<Vec<u8>>),
+    /// Commands applied together, atomically when there is more than one.
+    Batch(Vec<Vec<String>>),
+    ExportKeys(Vec<String>),
+    FetchKey(String),
+    /// Fetches the keys again, dropping the ones that are gone from the loaded page.
+    RefreshKeys(Vec<String>),
+    /// Follows keyspace notifications, or offers to turn them on when they are off.
+    WatchKeyspace,
+    /// `CONFIG SET notify-keyspace-events` to the flags, then follows the notifications.
+    EnableNotifications(String),
+    FetchClients,
+    KillClient(u64),
+    FetchSlowlog,
+    ResetSlowlog,
+    /// `LATENCY LATEST` with the `LATENCY HISTORY` of each event and `LATENCY DOCTOR`.
+    FetchLatency,
+    /// Sizes every key of the database in the background, until done or
+    /// [`RedisEvent::StopAnalysis`].
+    AnalyzeMemory,
+    StopAnalysis,
+    /// Ranks the keys by accesses with `OBJECT FREQ` under an LFU eviction policy, by sampling
+    /// `MONITOR` otherwise, until done or [`RedisEvent::StopHotKeys`].
+    FindHotKeys,
+    StopHotKeys,
+    /// Streams `MONITOR` on a connection of its own until [`RedisEvent::StopMonitor`].
+    StartMonitor,
+    StopMonitor,
+    /// Replaces the subscriptions of the Pub/Sub connection, closing it when there are none.
+    Subscribe(Vec<Subscription>),
+    FetchChannels,
+    Publish {
+        channel: String,
+        message: String,
+        kind: SubscriptionKind,
+    },
+    /// Commands from editing a value outside the app, refused when the value on the server
+    /// no longer matches the `original` text it was edited from.
+    WriteBack {
+        key: String,
+        original: String,
+        commands: Vec<Vec<String>>,
+    },
+    Undo(usize),
+    FetchFunctions,
+    RunScript {
+        source: String,
+        keys: Vec<String>,
+        args: Vec<String>,
+    },
+    CallFunction {
+        name: String,
+        keys: Vec<String>,
+        args: Vec<String>,
+    },
+    Watch(Vec<Vec<String>>),
+    Unwatch,
+    ExecTransaction(Vec<Vec<String>>),
 }
@@ -0,0 +1,210 @@
+use std::{
+    path::PathBuf,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+
+use super::types::{KeyMeta, KeyValue};
+use crate::utils;
+
+pub fn value_to_json(value: &KeyValue) -> Value {
+    match value {
+        KeyValue::String(value) => json!(value),
+        KeyValue::List(items) => json!(items),
+        KeyValue::Set(members) => {
+            // Sorted so that exports of the same data are identical
+            let mut members: Vec<&String> = members.iter().collect();
+            members.sort();
+            json!(members)
+        }
+        KeyValue::Hash(fields) => Value::Object(
+            fields
+                .iter()
+                .map(|(field, value)| (field.clone(), json!(value)))
+                .collect::<Map<_, _>>(),
+        ),
+        KeyValue::Zset(members) => members
+            .iter()
+            .map(|(member, score)| json!({ "member": member, "score": score }))
+            .collect(),
+        KeyValue::Json(value) => value.clone(),
+        KeyValue::Unknown => Value::Null,
+    }
+}
+
+/// One object per key with its type, TTL and value.
+pub fn keys_to_json(keys: &[KeyMeta]) -> Value {
+    keys.iter()
+        .map(|meta| {
+            json!({
+                "key": meta.key,
+                "type": meta.r_type.name(),
+                "ttl": meta.ttl,
+                "value": value_to_json(&meta.value),
+            })
+        })
+        .collect()
+}
+
+/// A new file named after `name` and the time in the `exports` folder of the data dir.
+fn export_path(name: &str, extension: &str) -> std::io::Result<PathBuf> {
+    let dir = utils::get_data_dir().join("exports");
+    std::fs::create_dir_all(&dir)?;
+
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    Ok(dir.join(format!("{name}-{timestamp}.{extension}")))
+}
+
+/// Writes the keys to a new file in the `exports` folder of the data dir and returns its path.
+pub fn write_keys(keys: &[KeyMeta]) -> std::io::Result<PathBuf> {
+    let path = export_path("keys", "json")?;
+
+    std::fs::write(&path, serde_json::to_string_pretty(&keys_to_json(keys))?)?;
+    Ok(path)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ExportFormat {
+    Csv,
+    Json,
+}
+
+fn csv_field(value: &Value) -> String {
+    let text = match value {
+        Value::String(text) => text.clone(),
+        Value::Null => String::new(),
+        value => value.to_string(),
+    };
+    if text.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text
+    }
+}
+
+/// A header line then one line per row, quoted as RFC 4180 has it.
+pub fn table_to_csv(headers: &[&str], rows: &[Vec<Value>]) -> String {
+    let mut csv = headers.join(",");
+    csv.push('\n');
+    for row in rows {
+        csv.push_str(&row.iter().map(csv_field).collect::<Vec<_>>().join(","));
+        csv.push('\n');
+    }
+    csv
+}
+
+/// One object per row, keyed by the headers.
+pub fn table_to_json(headers: &[&str], rows: &[Vec<Value>]) -> Value {
+    rows.iter()
+        .map(|row| {
+            Value::Object(
+                headers
+                    .iter()
+                    .map(|header| header.to_string())
+                    .zip(row.iter().cloned())
+                    .collect(),
+            )
+        })
+        .collect()
+}
+
+/// Writes a table to a new file in the `exports` folder of the data dir and returns its path.
+pub fn write_table(
+    name: &str,
+    format: ExportFormat,
+    headers: &[&str],
+    rows: &[Vec<Value>],
+) -> std::io::Result<PathBuf> {
+    let (extension, contents) = match format {
+        ExportFormat::Csv => ("csv", table_to_csv(headers, rows)),
+        ExportFormat::Json => (
+            "json",
+            serde_json::to_string_pretty(&table_to_json(headers, rows))?,
+        ),
+    };
+    let path = export_path(name, extension)?;
+
+    std::fs::write(&path, contents)?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{HashMap, HashSet};
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::redis_client::types::RedisType;
+
+    fn meta(key: &str, r_type: RedisType, value: KeyValue) -> KeyMeta {
+        KeyMeta {
+            key: key.into(),
+            r_type,
+            size: 0,
+            ttl: -1,
+            value,
+            encoding: None,
+            idle: None,
+            freq: None,
+        }
+    }
+
+    #[test]
+    fn test_table() {
+        let headers = ["Key", "Bytes"];
+        let rows = [
+            vec![json!("user:1"), json!(72)],
+            vec![json!("a,\"b\""), json!(8)],
+        ];
+
+        assert_eq!(
+            table_to_csv(&headers, &rows),
+            "Key,Bytes\nuser:1,72\n\"a,\"\"b\"\"\",8\n"
+        );
+        assert_eq!(
+            table_to_json(&headers, &rows),
+            json!([{ "Key": "user:1", "Bytes": 72 }, { "Key": "a,\"b\"", "Bytes": 8 }])
+        );
+    }
+
+    #[test]
+    fn test_keys_to_json() {
+        let keys = [
+            meta(
+                "tags",
+                RedisType::Set,
+                KeyValue::Set(HashSet::from(["b".into(), "a".into()])),
+            ),
+            meta(
+                "user:1",
+                RedisType::Hash,
+                KeyValue::Hash(HashMap::from([("name".into(), "ann".into())])),
+            ),
+            meta(
+                "board",
+                RedisType::Zset,
+                KeyValue::Zset(vec![("ann".into(), 1.5)]),
+            ),
+        ];
+
+        assert_eq!(
+            keys_to_json(&keys),
+            json!([
+                { "key": "tags", "type": "set", "ttl": -1, "value": ["a", "b"] },
+                { "key": "user:1", "type": "hash", "ttl": -1, "value": { "name": "ann" } },
+                {
+                    "key": "board",
+                    "type": "zset",
+                    "ttl": -1,
+                    "value": [{ "member": "ann", "score": 1.5 }]
+                },
+            ])
+        );
+    }
+}
@@ -0,0 +1,437 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+    sync::{Arc, Mutex},
+};
+
+use redis::{aio::ConnectionManager, Cmd, Value};
+
+/// Admin subcommands that change server state but carry no `write` flag in `COMMAND INFO`.
+const MUTATING_SUBCOMMANDS: &[(&str, &str)] = &[
+    ("ACL", "DELUSER"),
+    ("ACL", "LOAD"),
+    ("ACL", "SAVE"),
+    ("ACL", "SETUSER"),
+    ("CLIENT", "KILL"),
+    ("CONFIG", "RESETSTAT"),
+    ("CONFIG", "REWRITE"),
+    ("CONFIG", "SET"),
+    ("LATENCY", "RESET"),
+    ("MEMORY", "PURGE"),
+    ("MODULE", "LOAD"),
+    ("MODULE", "LOADEX"),
+    ("MODULE", "UNLOAD"),
+    ("SCRIPT", "FLUSH"),
+    ("SCRIPT", "KILL"),
+    ("SLOWLOG", "RESET"),
+];
+
+/// Top-level commands that must never be sent over a read-only connection.
+const MUTATING_COMMANDS: &[&str] = &["BGREWRITEAOF", "BGSAVE", "DEBUG", "SAVE", "SHUTDOWN"];
+
+/// Commands flagged `admin` that only read, the only admin commands a read-only connection
+/// lets through, `None` standing for every subcommand.
+const READ_ONLY_ADMIN: &[(&str, Option<&str>)] = &[
+    ("ACL", Some("GETUSER")),
+    ("ACL", Some("HELP")),
+    ("ACL", Some("LIST")),
+    ("ACL", Some("LOG")),
+    ("ACL", Some("USERS")),
+    ("CLIENT", Some("GETNAME")),
+    ("CLIENT", Some("HELP")),
+    ("CLIENT", Some("ID")),
+    ("CLIENT", Some("INFO")),
+    ("CLIENT", Some("LIST")),
+    ("CLUSTER", Some("INFO")),
+    ("CLUSTER", Some("MYID")),
+    ("CLUSTER", Some("NODES")),
+    ("CLUSTER", Some("SHARDS")),
+    ("CLUSTER", Some("SLOTS")),
+    ("CONFIG", Some("GET")),
+    ("CONFIG", Some("HELP")),
+    ("LATENCY", Some("DOCTOR")),
+    ("LATENCY", Some("GRAPH")),
+    ("LATENCY", Some("HELP")),
+    ("LATENCY", Some("HISTOGRAM")),
+    ("LATENCY", Some("HISTORY")),
+    ("LATENCY", Some("LATEST")),
+    ("MONITOR", None),
+    ("SLOWLOG", Some("GET")),
+    ("SLOWLOG", Some("HELP")),
+    ("SLOWLOG", Some("LEN")),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReadOnlyError {
+    pub command: String,
+}
+
+impl fmt::Display for ReadOnlyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} is not allowed: the connection is read-only",
+            self.command
+        )
+    }
+}
+
+impl std::error::Error for ReadOnlyError {}
+
+/// Checks every outgoing command against the `COMMAND INFO` flags reported by the server
+/// and rejects the ones that could modify data when the connection is read-only.
+///
+/// Besides `write`, the `may_replicate` flag is treated as mutating, which covers
+/// `EVAL`/`FCALL` (but not their `_RO` variants) and `PUBLISH`.
+#[derive(Clone, Debug, Default)]
+pub struct CommandGuard {
+    read_only: bool,
+    /// `COMMAND INFO` by command name, `None` for the commands the server does not know.
+    commands: Arc<Mutex<HashMap<String, Option<CommandInfo>>>>,
+}
+
+impl CommandGuard {
+    pub fn new(read_only: bool) -> Self {
+        Self {
+            read_only,
+            commands: Arc::default(),
+        }
+    }
+
+    pub fn is_read_only(&self) -> bool {
+        self.read_only
+    }
+
+    /// Returns whether the command mutates data, rejecting it when the connection is read-only.
+    pub async fn check(
+        &self,
+        manager: &mut ConnectionManager,
+        cmd: &Cmd,
+    ) -> Result<bool, Box<dyn std::error::Error + Send + Sync>> {
+        let mut args = cmd.args_iter().filter_map(|arg| match arg {
+            redis::Arg::Simple(bytes) => Some(String::from_utf8_lossy(bytes).to_uppercase()),
+            redis::Arg::Cursor => None,
+        });
+
+        let Some(name) = args.next() else {
+            return Ok(false);
+        };
+        let subcommand = args.next();
+
+        let info = self.command_info(manager, &name).await?;
+        let flags = info
+            .as_ref()
+            .and_then(|info| info.flags(subcommand.as_deref()));
+
+        let mutating = flags.is_some_and(|flags| is_mutating(&name, subcommand.as_deref(), flags));
+
+        if self.read_only && !is_allowed_read_only(&name, subcommand.as_deref(), info.as_ref()) {
+            let command = match subcommand {
+                Some(sub)
+                    if is_mutating_subcommand(&name, &sub)
+                        || info.as_ref().is_some_and(CommandInfo::is_container) =>
+                {
+                    format!("{name} {sub}")
+                }
+                _ => name,
+            };
+            return Err(Box::new(ReadOnlyError { command }));
+        }
+
+        Ok(mutating)
+    }
+
+    async fn command_info(
+        &self,
+        manager: &mut ConnectionManager,
+        name: &str,
+    ) -> redis::RedisResult<Option<CommandInfo>> {
+        if let Some(info) = self.commands.lock().unwrap().get(name) {
+            return Ok(info.clone());
+        }
+
+        let reply: Value = redis::cmd("COMMAND")
+            .arg("INFO")
+            .arg(name)
+            .query_async(manager)
+            .await?;
+
+        let info = parse_command_info(&reply);
+        self.commands
+            .lock()
+            .unwrap()
+            .insert(name.to_owned(), info.clone());
+
+        Ok(info)
+    }
+}
+
+/// What the guard needs of a `COMMAND INFO` entry.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CommandInfo {
+    pub flags: HashSet<String>,
+    /// Flags of each subcommand by upper case name, for the container commands of Redis 7.0
+    /// and later such as `XGROUP` or `FUNCTION`, whose own flags are empty.
+    pub subcommands: HashMap<String, HashSet<String>>,
+}
+
+impl CommandInfo {
+    pub fn is_container(&self) -> bool {
+        !self.subcommands.is_empty()
+    }
+
+    /// The flags that apply to the command called with the subcommand, `None` for a container
+    /// called without one of its subcommands.
+    pub fn flags(&self, subcommand: Option<&str>) -> Option<&HashSet<String>> {
+        if !self.is_container() {
+            return Some(&self.flags);
+        }
+        self.subcommands.get(subcommand?)
+    }
+}
+
+/// Whether a read-only connection may send the command. Commands without flag information,
+/// unknown ones or unknown subcommands of a container, are refused, as are the ones that
+/// mutate and the `admin` ones that are not known to only read.
+pub fn is_allowed_read_only(
+    name: &str,
+    subcommand: Option<&str>,
+    info: Option<&CommandInfo>,
+) -> bool {
+    let Some(flags) = info.and_then(|info| info.flags(subcommand)) else {
+        return false;
+    };
+    if is_mutating(name, subcommand, flags) {
+        return false;
+    }
+
+    !flags.contains("admin")
+        || READ_ONLY_ADMIN
+            .iter()
+            .any(|(cmd, sub)| *cmd == name && sub.map_or(true, |sub| subcommand == Some(sub)))
+}
+
+fn is_mutating_subcommand(name: &str, subcommand: &str) -> bool {
+    MUTATING_SUBCOMMANDS
+        .iter()
+        .any(|(cmd, sub)| *cmd == name && *sub == subcommand)
+}
+
+pub fn is_mutating(name: &str, subcommand: Option<&str>, flags: &HashSet<String>) -> bool {
+    flags.contains("write")
+        || flags.contains("may_replicate")
+        || MUTATING_COMMANDS.contains(&name)
+        || subcommand.is_some_and(|sub| is_mutating_subcommand(name, sub))
+}
+
+/// Extracts the flags of the first entry of a `COMMAND INFO <name>` reply.
+///
+/// An unknown command yields an empty set, see [`parse_command_info`] to tell them apart.
+pub fn parse_command_flags(info: &Value) -> HashSet<String> {
+    let Value::Array(entries) = info else {
+        return HashSet::new();
+    };
+
+    match entries.first() {
+        Some(Value::Array(entry)) => entry_flags(entry),
+        _ => HashSet::new(),
+    }
+}
+
+/// Reads the first entry of a `COMMAND INFO <name>` reply with its subcommands, `None` for an
+/// unknown command.
+pub fn parse_command_info(info: &Value) -> Option<CommandInfo> {
+    let Value::Array(entries) = info else {
+        return None;
+    };
+    let Some(Value::Array(entry)) = entries.first() else {
+        return None;
+    };
+
+    // Subcommands come tenth, named `container|subcommand`
+    let subcommands = match entry.get(9) {
+        Some(Value::Array(subcommands)) => subcommands
+            .iter()
+            .filter_map(|subcommand| {
+                let Value::Array(subcommand) = subcommand else {
+                    return None;
+                };
+                let name = match subcommand.first()? {
+                    Value::SimpleString(s) => s.clone(),
+                    Value::BulkString(b) => String::from_utf8_lossy(b).into_owned(),
+                    _ => return None,
+                };
+                let (_, sub) = name.split_once('|')?;
+                Some((sub.to_uppercase(), entry_flags(subcommand)))
+            })
+            .collect(),
+        _ => HashMap::new(),
+    };
+
+    Some(CommandInfo {
+        flags: parse_command_flags(info),
+        subcommands,
+    })
+}
+
+fn entry_flags(entry: &[Value]) -> HashSet<String> {
+    match entry.get(2) {
+        Some(Value::Array(flags) | Value::Set(flags)) => flags
+            .iter()
+            .filter_map(|flag| match flag {
+                Value::SimpleString(s) => Some(s.to_lowercase()),
+                Value::BulkString(b) => Some(String::from_utf8_lossy(b).to_lowercase()),
+                _ => None,
+            })
+            .collect(),
+        _ => HashSet::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn command_info(flags: &[&str]) -> Value {
+        Value::Array(vec![Value::Array(vec![
+            Value::BulkString(b"set".to_vec()),
+            Value::Int(-3),
+            Value::Array(
+                flags
+                    .iter()
+                    .map(|f| Value::SimpleString(f.to_string()))
+                    .collect(),
+            ),
+            Value::Int(1),
+            Value::Int(1),
+            Value::Int(1),
+        ])])
+    }
+
+    #[test]
+    fn test_parse_command_flags() {
+        let flags = parse_command_flags(&command_info(&["write", "denyoom"]));
+        assert_eq!(
+            flags,
+            HashSet::from(["write".to_string(), "denyoom".to_string()])
+        );
+    }
+
+    fn container_info(name: &str, subcommands: &[(&str, &[&str])]) -> Value {
+        let entry = |name: &str, flags: &[&str]| {
+            Value::Array(vec![
+                Value::BulkString(name.as_bytes().to_vec()),
+                Value::Int(-2),
+                Value::Array(
+                    flags
+                        .iter()
+                        .map(|f| Value::SimpleString(f.to_string()))
+                        .collect(),
+                ),
+                Value::Int(0),
+                Value::Int(0),
+                Value::Int(0),
+                Value::Array(Vec::new()),
+                Value::Array(Vec::new()),
+                Value::Array(Vec::new()),
+                Value::Array(Vec::new()),
+            ])
+        };
+
+        let Value::Array(mut container) = entry(name, &[]) else {
+            unreachable!()
+        };
+        container[9] = Value::Array(
+            subcommands
+                .iter()
+                .map(|(sub, flags)| entry(&format!("{name}|{sub}"), flags))
+                .collect(),
+        );
+        Value::Array(vec![Value::Array(container)])
+    }
+
+    #[test]
+    fn test_read_only_container_commands() {
+        let xgroup = parse_command_info(&container_info(
+            "xgroup",
+            &[
+                ("create", &["write", "denyoom"]),
+                ("help", &["loading", "stale"]),
+            ],
+        ));
+        assert!(!is_allowed_read_only(
+            "XGROUP",
+            Some("CREATE"),
+            xgroup.as_ref()
+        ));
+        assert!(is_allowed_read_only(
+            "XGROUP",
+            Some("HELP"),
+            xgroup.as_ref()
+        ));
+        assert!(!is_allowed_read_only(
+            "XGROUP",
+            Some("NOPE"),
+            xgroup.as_ref()
+        ));
+        assert!(!is_allowed_read_only("XGROUP", None, xgroup.as_ref()));
+
+        let function = parse_command_info(&container_info(
+            "function",
+            &[("delete", &["write", "noscript"]), ("list", &["noscript"])],
+        ));
+        assert!(!is_allowed_read_only(
+            "FUNCTION",
+            Some("DELETE"),
+            function.as_ref()
+        ));
+        assert!(is_allowed_read_only(
+            "FUNCTION",
+            Some("LIST"),
+            function.as_ref()
+        ));
+    }
+
+    #[test]
+    fn test_read_only_unknown_and_admin_commands() {
+        assert_eq!(parse_command_info(&Value::Array(vec![Value::Nil])), None);
+        assert!(!is_allowed_read_only("NOPE", None, None));
+
+        let admin = parse_command_info(&command_info(&["admin", "noscript"]));
+        assert!(!is_allowed_read_only(
+            "CLUSTER",
+            Some("RESET"),
+            admin.as_ref()
+        ));
+        assert!(is_allowed_read_only("SLOWLOG", Some("GET"), admin.as_ref()));
+        assert!(is_allowed_read_only("CONFIG", Some("GET"), admin.as_ref()));
+        assert!(!is_allowed_read_only("CONFIG", Some("SET"), admin.as_ref()));
+
+        let read = parse_command_info(&command_info(&["readonly", "fast"]));
+        assert!(is_allowed_read_only("GET", Some("key"), read.as_ref()));
+    }
+
+    #[test]
+    fn test_parse_unknown_command() {
+        assert!(parse_command_flags(&Value::Array(vec![Value::Nil])).is_empty());
+    }
+
+    #[test]
+    fn test_is_mutating() {
+        let write = parse_command_flags(&command_info(&["write", "denyoom"]));
+        let read = parse_command_flags(&command_info(&["readonly", "fast"]));
+        let admin = parse_command_flags(&command_info(&["admin", "noscript"]));
+
+        assert!(is_mutating("SET", None, &write));
+        assert!(!is_mutating("GET", None, &read));
+        assert!(!is_mutating("SLOWLOG", Some("GET"), &admin));
+        assert!(is_mutating("SLOWLOG", Some("RESET"), &admin));
+        assert!(is_mutating("CLIENT", Some("KILL"), &admin));
+        assert!(is_mutating("SHUTDOWN", None, &admin));
+
+        let script = parse_command_flags(&command_info(&["noscript", "may_replicate"]));
+        assert!(is_mutating("EVAL", None, &script));
+    }
+}
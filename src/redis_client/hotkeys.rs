@@ -0,0 +1,409 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use futures::StreamExt;
+use redis::Value;
+use tokio::sync::mpsc::UnboundedSender;
+use tokio_util::sync::CancellationToken;
+
+use crate::action::Action;
+
+use super::{
+    clients::{parse_client_list, ClientInfo},
+    memory::prefix,
+    monitor::MonitorEntry,
+    storage::Storage,
+};
+
+/// Keys kept in a report, the hottest ones.
+const CAPACITY: usize = 10_000;
+/// Keys looked at with `OBJECT FREQ`, out of the whole keyspace.
+const SAMPLE_KEYS: usize = 10_000;
+const SCAN_COUNT: usize = 500;
+/// How long `MONITOR` is listened to when the server does not keep access frequencies.
+pub const MONITOR_SECONDS: u64 = 10;
+
+/// Where the access counts of a report come from.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum HotKeysSource {
+    /// The logarithmic access counters the server keeps under an LFU eviction policy.
+    #[default]
+    Lfu,
+    /// The commands seen on `MONITOR`.
+    Monitor,
+}
+
+/// Whether the `maxmemory-policy` keeps access frequencies, which `OBJECT FREQ` needs.
+pub fn is_lfu(policy: &str) -> bool {
+    policy.ends_with("-lfu")
+}
+
+/// Accesses of the keys sharing a namespace.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct PrefixHits {
+    pub prefix: String,
+    pub keys: u64,
+    pub hits: u64,
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct HotKeysReport {
+    pub source: HotKeysSource,
+    /// Keys looked at with `OBJECT FREQ`, or commands seen on `MONITOR`.
+    pub sampled: u64,
+    pub finished: bool,
+    /// The keys by accesses, hottest first.
+    pub keys: Vec<(String, u64)>,
+    /// The namespaces by accesses, hottest first.
+    pub prefixes: Vec<PrefixHits>,
+}
+
+impl HotKeysReport {
+    /// Ranks the access counts of the keys and of their prefixes.
+    pub fn rank(
+        source: HotKeysSource,
+        sampled: u64,
+        counts: &HashMap<String, u64>,
+        delimiter: &str,
+    ) -> Self {
+        let mut prefixes: HashMap<&str, PrefixHits> = HashMap::new();
+        for (key, hits) in counts {
+            let prefix = prefix(key, delimiter);
+            let stats = prefixes.entry(prefix).or_insert_with(|| PrefixHits {
+                prefix: prefix.to_string(),
+                ..PrefixHits::default()
+            });
+            stats.keys += 1;
+            stats.hits += hits;
+        }
+        let mut prefixes: Vec<PrefixHits> = prefixes.into_values().collect();
+        prefixes.sort_by(|a, b| b.hits.cmp(&a.hits).then_with(|| a.prefix.cmp(&b.prefix)));
+
+        let mut keys: Vec<(String, u64)> = counts
+            .iter()
+            .map(|(key, hits)| (key.clone(), *hits))
+            .collect();
+        keys.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        keys.truncate(CAPACITY);
+
+        Self {
+            source,
+            sampled,
+            finished: false,
+            keys,
+            prefixes,
+        }
+    }
+}
+
+/// Where the keys of a command are, the legacy `first`, `last` and `step` of `COMMAND INFO`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyPositions {
+    first: i64,
+    /// Negative counts from the end, `-1` being the last argument.
+    last: i64,
+    step: i64,
+}
+
+impl KeyPositions {
+    /// Reads the positions out of `COMMAND INFO <name>`, `None` for unknown commands and
+    /// commands without keys.
+    pub fn parse(reply: &Value) -> Option<Self> {
+        let Value::Array(commands) = reply else {
+            return None;
+        };
+        let Some(Value::Array(info)) = commands.first() else {
+            return None;
+        };
+        let position = |idx: usize| match info.get(idx) {
+            Some(Value::Int(n)) => Some(*n),
+            _ => None,
+        };
+
+        let positions = Self {
+            first: position(3)?,
+            last: position(4)?,
+            step: position(5)?,
+        };
+        (positions.first > 0 && positions.step > 0).then_some(positions)
+    }
+
+    /// The keys among the arguments, the command name being the first one.
+    pub fn keys<'a>(&self, args: &'a [String]) -> impl Iterator<Item = &'a String> {
+        let last = if self.last < 0 {
+            args.len() as i64 + self.last
+        } else {
+            self.last
+        };
+        (self.first..=last)
+            .step_by(self.step as usize)
+            .filter_map(|idx| args.get(idx as usize))
+    }
+}
+
+fn publish(
+    report: HotKeysReport,
+    shared: &Mutex<Option<HotKeysReport>>,
+    tx: &UnboundedSender<Action>,
+) {
+    *shared.lock().unwrap() = Some(report);
+    let _ = tx.send(Action::LoadHotKeys);
+}
+
+/// Reads the access frequency of up to [`SAMPLE_KEYS`] keys with `OBJECT FREQ`, until done or
+/// the token is cancelled. The ranking so far is put into `shared` after each batch, and the
+/// app told with [`Action::LoadHotKeys`].
+pub async fn sample_lfu(
+    storage: Storage,
+    delimiter: String,
+    shared: Arc<Mutex<Option<HotKeysReport>>>,
+    tx: UnboundedSender<Action>,
+    cancelation_token: CancellationToken,
+) {
+    let mut counts = HashMap::new();
+    let mut cursor = 0u64;
+
+    loop {
+        let mut scan = redis::cmd("SCAN");
+        scan.arg(cursor).arg("COUNT").arg(SCAN_COUNT);
+        let keys = tokio::select! {
+            result = storage.query::<(u64, Vec<String>)>(&scan) => match result {
+                Ok((next, keys)) => {
+                    cursor = next;
+                    keys
+                }
+                Err(err) => {
+                    let _ = tx.send(Action::Error(format!("Hot keys sampling failed: {err}")));
+                    return;
+                }
+            },
+            _ = cancelation_token.cancelled() => return,
+        };
+
+        let freqs = futures::future::join_all(keys.into_iter().map(|key| {
+            let storage = &storage;
+            async move {
+                let freq = storage
+                    .query::<u64>(redis::cmd("OBJECT").arg("FREQ").arg(&key))
+                    .await
+                    .ok();
+                freq.map(|freq| (key, freq))
+            }
+        }));
+        let freqs = tokio::select! {
+            freqs = freqs => freqs,
+            _ = cancelation_token.cancelled() => return,
+        };
+        counts.extend(freqs.into_iter().flatten());
+
+        let mut report =
+            HotKeysReport::rank(HotKeysSource::Lfu, counts.len() as u64, &counts, &delimiter);
+        report.finished = cursor == 0 || counts.len() >= SAMPLE_KEYS;
+        let finished = report.finished;
+        publish(report, &shared, &tx);
+
+        if finished {
+            return;
+        }
+    }
+}
+
+/// The address the server sees the connection of the app coming from.
+async fn own_addr(storage: &Storage) -> Option<String> {
+    // Not there before Redis 6.2
+    let info = storage
+        .query::<String>(redis::cmd("CLIENT").arg("INFO"))
+        .await
+        .ok()
+        .and_then(|info| ClientInfo::parse(&info));
+    if let Some(info) = info {
+        return Some(info.addr);
+    }
+
+    let id = storage
+        .query::<u64>(redis::cmd("CLIENT").arg("ID"))
+        .await
+        .ok()?;
+    let list = storage
+        .query::<String>(redis::cmd("CLIENT").arg("LIST").arg("ID").arg(id))
+        .await
+        .ok()?;
+    parse_client_list(&list)
+        .into_iter()
+        .next()
+        .map(|client| client.addr)
+}
+
+/// Whether a command seen on `MONITOR` counts, the ones of the app itself do not: reading the
+/// keys on screen would make them the hottest, and the sampler asks for `COMMAND INFO` too.
+fn is_counted(entry: &MonitorEntry, db: i64, own_addr: Option<&str>) -> bool {
+    i64::from(entry.db) == db && own_addr != Some(entry.client.as_str())
+}
+
+/// Counts the keys of the commands seen on `MONITOR` for [`MONITOR_SECONDS`], on a connection
+/// of its own, until done or the token is cancelled. The ranking so far is put into `shared`
+/// every second, and the app told with [`Action::LoadHotKeys`].
+pub async fn sample_monitor(
+    client: redis::Client,
+    storage: Storage,
+    delimiter: String,
+    shared: Arc<Mutex<Option<HotKeysReport>>>,
+    tx: UnboundedSender<Action>,
+    cancelation_token: CancellationToken,
+) {
+    let db = client.get_connection_info().redis.db;
+    let own_addr = own_addr(&storage).await;
+    let mut monitor = match client.get_async_monitor().await {
+        Ok(monitor) => monitor,
+        Err(err) => {
+            let _ = tx.send(Action::Error(format!(
+                "Failed to connect for MONITOR: {err}"
+            )));
+            return;
+        }
+    };
+    if let Err(err) = monitor.monitor().await {
+        let _ = tx.send(Action::Error(format!("Failed to start MONITOR: {err}")));
+        return;
+    }
+
+    let mut positions: HashMap<String, Option<KeyPositions>> = HashMap::new();
+    let mut counts: HashMap<String, u64> = HashMap::new();
+    let mut sampled = 0u64;
+
+    let deadline = tokio::time::sleep(Duration::from_secs(MONITOR_SECONDS));
+    tokio::pin!(deadline);
+    let mut progress = tokio::time::interval(Duration::from_secs(1));
+
+    let mut lines = monitor.into_on_message::<String>();
+    loop {
+        tokio::select! {
+            line = lines.next() => {
+                let Some(line) = line else {
+                    let _ = tx.send(Action::Error("The MONITOR connection was closed".into()));
+                    break;
+                };
+                let Some(entry) = MonitorEntry::parse(&line)
+                    .filter(|entry| is_counted(entry, db, own_addr.as_deref()))
+                else {
+                    continue;
+                };
+
+                let name = entry.command();
+                if !positions.contains_key(&name) {
+                    let info = storage
+                        .query::<Value>(redis::cmd("COMMAND").arg("INFO").arg(&name))
+                        .await
+                        .ok();
+                    positions.insert(name.clone(), info.as_ref().and_then(KeyPositions::parse));
+                }
+
+                sampled += 1;
+                if let Some(Some(positions)) = positions.get(&name) {
+                    for key in positions.keys(&entry.args) {
+                        *counts.entry(key.clone()).or_default() += 1;
+                    }
+                }
+            },
+            _ = progress.tick() => {
+                let report = HotKeysReport::rank(HotKeysSource::Monitor, sampled, &counts, &delimiter);
+                publish(report, &shared, &tx);
+            },
+            _ = &mut deadline => break,
+            _ = cancelation_token.cancelled() => return,
+        }
+    }
+
+    let mut report = HotKeysReport::rank(HotKeysSource::Monitor, sampled, &counts, &delimiter);
+    report.finished = true;
+    publish(report, &shared, &tx);
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn test_key_positions() {
+        let info = |first, last, step| {
+            Value::Array(vec![Value::Array(vec![
+                Value::BulkString(b"mset".to_vec()),
+                Value::Int(-3),
+                Value::Array(Vec::new()),
+                Value::Int(first),
+                Value::Int(last),
+                Value::Int(step),
+            ])])
+        };
+
+        let mset = KeyPositions::parse(&info(1, -1, 2)).unwrap();
+        let mset_args = args(&["mset", "a", "1", "b", "2"]);
+        let keys: Vec<_> = mset.keys(&mset_args).collect();
+        assert_eq!(keys, ["a", "b"]);
+
+        let get = KeyPositions::parse(&info(1, 1, 1)).unwrap();
+        let get_args = args(&["get", "a"]);
+        let keys: Vec<_> = get.keys(&get_args).collect();
+        assert_eq!(keys, ["a"]);
+
+        assert_eq!(KeyPositions::parse(&info(0, 0, 0)), None);
+        assert_eq!(KeyPositions::parse(&Value::Array(vec![Value::Nil])), None);
+    }
+
+    #[test]
+    fn test_is_counted() {
+        let entry = |line: &str| MonitorEntry::parse(line).unwrap();
+
+        let app = entry(r#"1339518083.107412 [0 127.0.0.1:60866] "get" "a""#);
+        let other = entry(r#"1339518083.107412 [0 127.0.0.1:60900] "get" "a""#);
+        assert!(!is_counted(&app, 0, Some("127.0.0.1:60866")));
+        assert!(is_counted(&other, 0, Some("127.0.0.1:60866")));
+        assert!(!is_counted(&other, 1, Some("127.0.0.1:60866")));
+        assert!(is_counted(&app, 0, None));
+    }
+
+    #[test]
+    fn test_rank() {
+        let counts = HashMap::from([
+            ("user:1".to_string(), 5),
+            ("user:2".to_string(), 3),
+            ("counter".to_string(), 7),
+        ]);
+        let report = HotKeysReport::rank(HotKeysSource::Monitor, 15, &counts, ":");
+
+        assert!(is_lfu("allkeys-lfu"));
+        assert!(!is_lfu("allkeys-lru"));
+        assert_eq!(
+            report.keys,
+            [
+                ("counter".to_string(), 7),
+                ("user:1".to_string(), 5),
+                ("user:2".to_string(), 3),
+            ]
+        );
+        assert_eq!(
+            report.prefixes,
+            [
+                PrefixHits {
+                    prefix: "user:".into(),
+                    keys: 2,
+                    hits: 8,
+                },
+                PrefixHits {
+                    prefix: String::new(),
+                    keys: 1,
+                    hits: 7,
+                },
+            ]
+        );
+    }
+}
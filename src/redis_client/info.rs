@@ -0,0 +1,410 @@
+use std::{collections::HashMap, str::FromStr};
+
+/// Fields of one `# Section` of the `INFO` reply.
+struct Fields<'a>(HashMap<&'a str, &'a str>);
+
+impl Fields<'_> {
+    fn text(&self, name: &str) -> String {
+        self.0
+            .get(name)
+            .map(|value| value.to_string())
+            .unwrap_or_default()
+    }
+
+    /// Fields missing on older servers, or not numbers, read as zero.
+    fn number<T: FromStr + Default>(&self, name: &str) -> T {
+        self.0
+            .get(name)
+            .and_then(|value| value.parse().ok())
+            .unwrap_or_default()
+    }
+}
+
+/// Splits `a=1,b=2` values, used by the keyspace, module and error lines.
+fn attributes(value: &str) -> HashMap<&str, &str> {
+    value
+        .split(',')
+        .filter_map(|pair| pair.split_once('='))
+        .collect()
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ServerInfo {
+    pub version: String,
+    pub mode: String,
+    pub os: String,
+    pub arch_bits: u32,
+    pub process_id: u64,
+    pub tcp_port: u16,
+    pub uptime_in_seconds: u64,
+    pub config_file: String,
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ClientsInfo {
+    pub connected: u64,
+    pub blocked: u64,
+    pub tracking: u64,
+    pub max_clients: u64,
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct MemoryInfo {
+    pub used: u64,
+    pub used_human: String,
+    pub used_rss: u64,
+    pub used_peak_human: String,
+    pub used_lua: u64,
+    pub total_system: u64,
+    pub total_system_human: String,
+    /// Zero when there is no limit.
+    pub max_memory: u64,
+    pub max_memory_human: String,
+    pub max_memory_policy: String,
+    pub fragmentation_ratio: f64,
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct PersistenceInfo {
+    pub loading: bool,
+    pub rdb_changes_since_last_save: u64,
+    pub rdb_bgsave_in_progress: bool,
+    pub rdb_last_save_time: u64,
+    pub rdb_last_bgsave_status: String,
+    pub aof_enabled: bool,
+    pub aof_rewrite_in_progress: bool,
+    pub aof_last_write_status: String,
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct StatsInfo {
+    pub total_connections_received: u64,
+    pub total_commands_processed: u64,
+    pub instantaneous_ops_per_sec: u64,
+    pub total_net_input_bytes: u64,
+    pub total_net_output_bytes: u64,
+    pub instantaneous_input_kbps: f64,
+    pub instantaneous_output_kbps: f64,
+    pub rejected_connections: u64,
+    pub expired_keys: u64,
+    pub evicted_keys: u64,
+    pub keyspace_hits: u64,
+    pub keyspace_misses: u64,
+    pub pubsub_channels: u64,
+    pub pubsub_patterns: u64,
+    pub total_error_replies: u64,
+}
+
+impl StatsInfo {
+    /// Share of key lookups that found the key, `None` before the first lookup.
+    pub fn hit_ratio(&self) -> Option<f64> {
+        let lookups = self.keyspace_hits + self.keyspace_misses;
+        (lookups > 0).then(|| self.keyspace_hits as f64 / lookups as f64)
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ReplicationInfo {
+    pub role: String,
+    pub connected_replicas: u64,
+    pub master_host: String,
+    pub master_port: u16,
+    pub master_link_status: String,
+    pub master_repl_offset: u64,
+    /// `slave0:ip=...,port=...,state=online,...` lines, as they are.
+    pub replicas: Vec<String>,
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct CpuInfo {
+    pub used_cpu_sys: f64,
+    pub used_cpu_user: f64,
+    pub used_cpu_sys_children: f64,
+    pub used_cpu_user_children: f64,
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ModuleInfo {
+    pub name: String,
+    pub version: String,
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct KeyspaceInfo {
+    pub db: u32,
+    pub keys: u64,
+    pub expires: u64,
+    pub avg_ttl: u64,
+}
+
+/// Everything `INFO` reports that the app shows, section by section.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct RedisInfo {
+    pub server: ServerInfo,
+    pub clients: ClientsInfo,
+    pub memory: MemoryInfo,
+    pub persistence: PersistenceInfo,
+    pub stats: StatsInfo,
+    pub replication: ReplicationInfo,
+    pub cpu: CpuInfo,
+    pub modules: Vec<ModuleInfo>,
+    pub keyspace: Vec<KeyspaceInfo>,
+    /// Error replies by error prefix, e.g. `ERR` or `WRONGTYPE`.
+    pub errors: Vec<(String, u64)>,
+}
+
+impl RedisInfo {
+    /// Parses the `INFO` reply, sections the server did not send are left empty.
+    pub fn parse(text: &str) -> Self {
+        let mut sections: HashMap<String, Vec<(&str, &str)>> = HashMap::new();
+        let mut section = String::new();
+
+        for line in text.lines().map(str::trim) {
+            if let Some(name) = line.strip_prefix('#') {
+                section = name.trim().to_lowercase();
+            } else if let Some((name, value)) = line.split_once(':') {
+                sections
+                    .entry(section.clone())
+                    .or_default()
+                    .push((name, value));
+            }
+        }
+
+        let lines = |name: &str| sections.get(name).cloned().unwrap_or_default();
+        let fields = |name: &str| Fields(lines(name).into_iter().collect());
+
+        let server = fields("server");
+        let clients = fields("clients");
+        let memory = fields("memory");
+        let persistence = fields("persistence");
+        let stats = fields("stats");
+        let replication = fields("replication");
+        let cpu = fields("cpu");
+
+        Self {
+            server: ServerInfo {
+                version: server.text("redis_version"),
+                mode: server.text("redis_mode"),
+                os: server.text("os"),
+                arch_bits: server.number("arch_bits"),
+                process_id: server.number("process_id"),
+                tcp_port: server.number("tcp_port"),
+                uptime_in_seconds: server.number("uptime_in_seconds"),
+                config_file: server.text("config_file"),
+            },
+            clients: ClientsInfo {
+                connected: clients.number("connected_clients"),
+                blocked: clients.number("blocked_clients"),
+                tracking: clients.number("tracking_clients"),
+                max_clients: clients.number("maxclients"),
+            },
+            memory: MemoryInfo {
+                used: memory.number("used_memory"),
+                used_human: memory.text("used_memory_human"),
+                used_rss: memory.number("used_memory_rss"),
+                used_peak_human: memory.text("used_memory_peak_human"),
+                used_lua: memory.number("used_memory_lua"),
+                total_system: memory.number("total_system_memory"),
+                total_system_human: memory.text("total_system_memory_human"),
+                max_memory: memory.number("maxmemory"),
+                max_memory_human: memory.text("maxmemory_human"),
+                max_memory_policy: memory.text("maxmemory_policy"),
+                fragmentation_ratio: memory.number("mem_fragmentation_ratio"),
+            },
+            persistence: PersistenceInfo {
+                loading: persistence.number::<u8>("loading") == 1,
+                rdb_changes_since_last_save: persistence.number("rdb_changes_since_last_save"),
+                rdb_bgsave_in_progress: persistence.number::<u8>("rdb_bgsave_in_progress") == 1,
+                rdb_last_save_time: persistence.number("rdb_last_save_time"),
+                rdb_last_bgsave_status: persistence.text("rdb_last_bgsave_status"),
+                aof_enabled: persistence.number::<u8>("aof_enabled") == 1,
+                aof_rewrite_in_progress: persistence.number::<u8>("aof_rewrite_in_progress") == 1,
+                aof_last_write_status: persistence.text("aof_last_write_status"),
+            },
+            stats: StatsInfo {
+                total_connections_received: stats.number("total_connections_received"),
+                total_commands_processed: stats.number("total_commands_processed"),
+                instantaneous_ops_per_sec: stats.number("instantaneous_ops_per_sec"),
+                total_net_input_bytes: stats.number("total_net_input_bytes"),
+                total_net_output_bytes: stats.number("total_net_output_bytes"),
+                instantaneous_input_kbps: stats.number("instantaneous_input_kbps"),
+                instantaneous_output_kbps: stats.number("instantaneous_output_kbps"),
+                rejected_connections: stats.number("rejected_connections"),
+                expired_keys: stats.number("expired_keys"),
+                evicted_keys: stats.number("evicted_keys"),
+                keyspace_hits: stats.number("keyspace_hits"),
+                keyspace_misses: stats.number("keyspace_misses"),
+                pubsub_channels: stats.number("pubsub_channels"),
+                pubsub_patterns: stats.number("pubsub_patterns"),
+                total_error_replies: stats.number("total_error_replies"),
+            },
+            replication: ReplicationInfo {
+                role: replication.text("role"),
+                connected_replicas: replication.number("connected_slaves"),
+                master_host: replication.text("master_host"),
+                master_port: replication.number("master_port"),
+                master_link_status: replication.text("master_link_status"),
+                master_repl_offset: replication.number("master_repl_offset"),
+                replicas: lines("replication")
+                    .into_iter()
+                    .filter(|(name, _)| {
+                        name.strip_prefix("slave")
+                            .is_some_and(|n| n.parse::<u32>().is_ok())
+                    })
+                    .map(|(name, value)| format!("{name}: {value}"))
+                    .collect(),
+            },
+            cpu: CpuInfo {
+                used_cpu_sys: cpu.number("used_cpu_sys"),
+                used_cpu_user: cpu.number("used_cpu_user"),
+                used_cpu_sys_children: cpu.number("used_cpu_sys_children"),
+                used_cpu_user_children: cpu.number("used_cpu_user_children"),
+            },
+            modules: lines("modules")
+                .into_iter()
+                .map(|(_, value)| {
+                    let module = attributes(value);
+                    ModuleInfo {
+                        name: module.get("name").unwrap_or(&"").to_string(),
+                        version: module.get("ver").unwrap_or(&"").to_string(),
+                    }
+                })
+                .collect(),
+            keyspace: lines("keyspace")
+                .into_iter()
+                .filter_map(|(name, value)| {
+                    let db = Fields(attributes(value));
+                    Some(KeyspaceInfo {
+                        db: name.strip_prefix("db")?.parse().ok()?,
+                        keys: db.number("keys"),
+                        expires: db.number("expires"),
+                        avg_ttl: db.number("avg_ttl"),
+                    })
+                })
+                .collect(),
+            errors: lines("errorstats")
+                .into_iter()
+                .filter_map(|(name, value)| {
+                    let count = Fields(attributes(value)).number("count");
+                    Some((name.strip_prefix("errorstat_")?.to_string(), count))
+                })
+                .collect(),
+        }
+    }
+
+    pub fn redis_version(&self) -> String {
+        format!("Redis version: {}", self.server.version)
+    }
+
+    pub fn os(&self) -> String {
+        format!("OS: {}", self.server.os)
+    }
+
+    pub fn cpu(&self) -> String {
+        format!("CPU: {}", self.cpu.used_cpu_sys)
+    }
+
+    pub fn memory(&self) -> String {
+        format!(
+            "RAM: {}/{}",
+            self.memory.used_human, self.memory.total_system_human
+        )
+    }
+
+    pub fn clients(&self) -> String {
+        format!(
+            "Connected clients: {}/{}",
+            self.clients.connected, self.clients.max_clients
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    const INFO: &str = "# Server\r\n\
+        redis_version:7.2.4\r\n\
+        redis_mode:standalone\r\n\
+        os:Linux 6.1.0 x86_64\r\n\
+        tcp_port:6379\r\n\
+        \r\n\
+        # Clients\r\n\
+        connected_clients:3\r\n\
+        maxclients:10000\r\n\
+        \r\n\
+        # Memory\r\n\
+        used_memory:1048576\r\n\
+        used_memory_human:1.00M\r\n\
+        maxmemory:0\r\n\
+        mem_fragmentation_ratio:1.25\r\n\
+        \r\n\
+        # Stats\r\n\
+        instantaneous_ops_per_sec:42\r\n\
+        keyspace_hits:3\r\n\
+        keyspace_misses:1\r\n\
+        \r\n\
+        # Replication\r\n\
+        role:master\r\n\
+        connected_slaves:1\r\n\
+        slave0:ip=10.0.0.2,port=6380,state=online,offset=42,lag=0\r\n\
+        \r\n\
+        # Modules\r\n\
+        module:name=search,ver=20604,api=1,filters=0\r\n\
+        \r\n\
+        # Errorstats\r\n\
+        errorstat_ERR:count=5\r\n\
+        errorstat_WRONGTYPE:count=2\r\n\
+        \r\n\
+        # Keyspace\r\n\
+        db0:keys=10,expires=2,avg_ttl=3000\r\n\
+        db3:keys=1,expires=0,avg_ttl=0\r\n";
+
+    #[test]
+    fn test_parse() {
+        let info = RedisInfo::parse(INFO);
+
+        assert_eq!(info.server.version, "7.2.4");
+        assert_eq!(info.server.tcp_port, 6379);
+        assert_eq!(info.clients.connected, 3);
+        assert_eq!(info.memory.used, 1_048_576);
+        assert_eq!(info.memory.fragmentation_ratio, 1.25);
+        assert_eq!(info.stats.hit_ratio(), Some(0.75));
+        assert_eq!(
+            info.replication.replicas,
+            ["slave0: ip=10.0.0.2,port=6380,state=online,offset=42,lag=0"]
+        );
+        assert_eq!(
+            info.modules,
+            [ModuleInfo {
+                name: "search".into(),
+                version: "20604".into()
+            }]
+        );
+        assert_eq!(
+            info.keyspace,
+            [
+                KeyspaceInfo {
+                    db: 0,
+                    keys: 10,
+                    expires: 2,
+                    avg_ttl: 3000
+                },
+                KeyspaceInfo {
+                    db: 3,
+                    keys: 1,
+                    expires: 0,
+                    avg_ttl: 0
+                },
+            ]
+        );
+        assert_eq!(
+            info.errors,
+            [("ERR".to_string(), 5), ("WRONGTYPE".to_string(), 2)]
+        );
+        // Not sent, left empty
+        assert_eq!(info.persistence, PersistenceInfo::default());
+    }
+}
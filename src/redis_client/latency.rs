@@ -0,0 +1,160 @@
+use std::{
+    collections::VecDeque,
+    time::{Duration, Instant},
+};
+
+use redis::Value;
+
+use super::value::{as_string, as_u64};
+
+/// 5 minutes of pings at the 2 seconds `INFO` is polled with.
+const PING_CAPACITY: usize = 150;
+
+/// The latest spike of one event from `LATENCY LATEST`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LatencyEvent {
+    pub name: String,
+    /// Unix time of the latest spike.
+    pub timestamp: u64,
+    pub latest_ms: u64,
+    pub max_ms: u64,
+}
+
+/// What the latency view shows of the server side.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct LatencyReport {
+    pub events: Vec<LatencyEvent>,
+    /// `LATENCY HISTORY` of each event, as (unix time, milliseconds), oldest first.
+    pub histories: Vec<(String, Vec<(u64, u64)>)>,
+    pub doctor: String,
+    /// `latency-monitor-threshold` in milliseconds, zero when the monitor is off.
+    pub threshold: Option<u64>,
+}
+
+impl LatencyReport {
+    pub fn history(&self, event: &str) -> &[(u64, u64)] {
+        self.histories
+            .iter()
+            .find(|(name, _)| name == event)
+            .map_or(&[], |(_, history)| history)
+    }
+}
+
+/// Parses `LATENCY LATEST`, one `[event, time, latest, max]` array per event.
+pub fn parse_latest(reply: &Value) -> Vec<LatencyEvent> {
+    let Value::Array(events) = reply else {
+        return Vec::new();
+    };
+
+    events
+        .iter()
+        .filter_map(|event| {
+            let Value::Array(fields) = event else {
+                return None;
+            };
+            Some(LatencyEvent {
+                name: as_string(fields.first()?)?,
+                timestamp: as_u64(fields.get(1)?)?,
+                latest_ms: as_u64(fields.get(2)?)?,
+                max_ms: as_u64(fields.get(3)?)?,
+            })
+        })
+        .collect()
+}
+
+/// Parses `LATENCY HISTORY`, one `[time, latency]` array per spike.
+pub fn parse_history(reply: &Value) -> Vec<(u64, u64)> {
+    let Value::Array(samples) = reply else {
+        return Vec::new();
+    };
+
+    samples
+        .iter()
+        .filter_map(|sample| match sample {
+            Value::Array(fields) => Some((as_u64(fields.first()?)?, as_u64(fields.get(1)?)?)),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Round trips of `PING` measured by the app, oldest first.
+#[derive(Debug, Default)]
+pub struct PingStats {
+    samples: VecDeque<(Instant, Duration)>,
+}
+
+impl PingStats {
+    pub fn push(&mut self, at: Instant, rtt: Duration) {
+        if self.samples.len() == PING_CAPACITY {
+            self.samples.pop_front();
+        }
+        self.samples.push_back((at, rtt));
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &(Instant, Duration)> {
+        self.samples.iter()
+    }
+
+    pub fn latest(&self) -> Option<Duration> {
+        self.samples.back().map(|(_, rtt)| *rtt)
+    }
+
+    pub fn average(&self) -> Option<Duration> {
+        let total: Duration = self.samples.iter().map(|(_, rtt)| *rtt).sum();
+        (!self.samples.is_empty()).then(|| total / self.samples.len() as u32)
+    }
+
+    pub fn max(&self) -> Option<Duration> {
+        self.samples.iter().map(|(_, rtt)| *rtt).max()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::redis_client::value::test_support::bulk;
+
+    #[test]
+    fn test_parse_latest_and_history() {
+        let latest = Value::Array(vec![Value::Array(vec![
+            bulk("command"),
+            Value::Int(1_700_000_000),
+            Value::Int(12),
+            Value::Int(250),
+        ])]);
+        assert_eq!(
+            parse_latest(&latest),
+            [LatencyEvent {
+                name: "command".into(),
+                timestamp: 1_700_000_000,
+                latest_ms: 12,
+                max_ms: 250,
+            }]
+        );
+
+        let history = Value::Array(vec![
+            Value::Array(vec![Value::Int(10), Value::Int(5)]),
+            Value::Array(vec![Value::Int(20), Value::Int(7)]),
+        ]);
+        assert_eq!(parse_history(&history), [(10, 5), (20, 7)]);
+    }
+
+    #[test]
+    fn test_ping_stats() {
+        let start = Instant::now();
+        let mut stats = PingStats::default();
+        assert_eq!(stats.average(), None);
+
+        for (i, ms) in [1, 3, 2].into_iter().enumerate() {
+            stats.push(
+                start + Duration::from_secs(i as u64),
+                Duration::from_millis(ms),
+            );
+        }
+        assert_eq!(stats.latest(), Some(Duration::from_millis(2)));
+        assert_eq!(stats.average(), Some(Duration::from_millis(2)));
+        assert_eq!(stats.max(), Some(Duration::from_millis(3)));
+    }
+}
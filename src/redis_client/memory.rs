@@ -0,0 +1,248 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use tokio::sync::mpsc::UnboundedSender;
+use tokio_util::sync::CancellationToken;
+
+use crate::action::Action;
+
+use super::storage::Storage;
+
+/// Keys asked for per `SCAN`, each batch is sized before the next one is asked for.
+const SCAN_COUNT: usize = 500;
+/// Elements of aggregates `MEMORY USAGE` looks at, the server's own default.
+const SAMPLES: usize = 5;
+/// Keys kept per type for the biggest keys, and in total for the highest cardinalities.
+pub const TOP_N: usize = 20;
+
+/// What one key was found to take.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyUsage {
+    pub key: String,
+    /// As `TYPE` replies, e.g. `stream` or `ReJSON-RL`.
+    pub key_type: String,
+    pub bytes: u64,
+    /// Elements of an aggregate, `None` for strings and the types without a count.
+    pub cardinality: Option<u64>,
+}
+
+/// Keys sharing the namespace before the first delimiter.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct PrefixUsage {
+    pub prefix: String,
+    pub keys: u64,
+    pub bytes: u64,
+}
+
+/// The command counting the elements of a type, if it has any.
+fn cardinality_command(key_type: &str) -> Option<&'static str> {
+    match key_type {
+        "list" => Some("LLEN"),
+        "set" => Some("SCARD"),
+        "zset" => Some("ZCARD"),
+        "hash" => Some("HLEN"),
+        "stream" => Some("XLEN"),
+        _ => None,
+    }
+}
+
+/// The namespace of the key up to and including the first delimiter, empty for keys without
+/// one.
+pub fn prefix<'a>(key: &'a str, delimiter: &str) -> &'a str {
+    if delimiter.is_empty() {
+        return "";
+    }
+    key.find(delimiter)
+        .map_or("", |idx| &key[..idx + delimiter.len()])
+}
+
+/// Inserts into a list sorted by `by` descending, keeping at most [`TOP_N`] of them.
+fn insert_top(list: &mut Vec<KeyUsage>, usage: KeyUsage, by: impl Fn(&KeyUsage) -> u64) {
+    if list.len() == TOP_N && list.last().is_some_and(|last| by(last) >= by(&usage)) {
+        return;
+    }
+    let idx = list.partition_point(|other| by(other) >= by(&usage));
+    list.insert(idx, usage);
+    list.truncate(TOP_N);
+}
+
+/// The state of a walk through the keyspace, updated batch by batch.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct MemoryAnalysis {
+    pub scanned: u64,
+    /// `DBSIZE` when the walk started.
+    pub total: u64,
+    pub bytes: u64,
+    pub finished: bool,
+    /// The biggest keys of each type, biggest first.
+    pub biggest: HashMap<String, Vec<KeyUsage>>,
+    /// The aggregates with the most elements, of any type.
+    pub cardinality: Vec<KeyUsage>,
+    pub prefixes: HashMap<String, PrefixUsage>,
+}
+
+impl MemoryAnalysis {
+    pub fn add(&mut self, usage: KeyUsage, delimiter: &str) {
+        self.scanned += 1;
+        self.bytes += usage.bytes;
+
+        let prefix = prefix(&usage.key, delimiter);
+        let stats = self
+            .prefixes
+            .entry(prefix.to_string())
+            .or_insert_with(|| PrefixUsage {
+                prefix: prefix.to_string(),
+                ..PrefixUsage::default()
+            });
+        stats.keys += 1;
+        stats.bytes += usage.bytes;
+
+        if usage.cardinality.is_some() {
+            insert_top(&mut self.cardinality, usage.clone(), |u| {
+                u.cardinality.unwrap_or_default()
+            });
+        }
+        let biggest = self.biggest.entry(usage.key_type.clone()).or_default();
+        insert_top(biggest, usage, |u| u.bytes);
+    }
+}
+
+/// Sizes one key, `None` when it expired since it was scanned.
+async fn key_usage(storage: &Storage, key: String) -> Option<KeyUsage> {
+    let key_type = storage
+        .query::<String>(redis::cmd("TYPE").arg(&key))
+        .await
+        .ok()
+        .filter(|key_type| key_type != "none")?;
+    let bytes = storage
+        .query::<Option<u64>>(
+            redis::cmd("MEMORY")
+                .arg("USAGE")
+                .arg(&key)
+                .arg("SAMPLES")
+                .arg(SAMPLES),
+        )
+        .await
+        .ok()
+        .flatten()?;
+    let cardinality = match cardinality_command(&key_type) {
+        Some(command) => storage
+            .query::<u64>(redis::cmd(command).arg(&key))
+            .await
+            .ok(),
+        None => None,
+    };
+
+    Some(KeyUsage {
+        key,
+        key_type,
+        bytes,
+        cardinality,
+    })
+}
+
+/// Walks the whole keyspace with `SCAN`, sizing every key with `MEMORY USAGE`, until done or
+/// the token is cancelled. The analysis so far is put into `shared` after each batch, and the
+/// app told with [`Action::LoadMemory`].
+pub async fn analyze(
+    storage: Storage,
+    delimiter: String,
+    shared: Arc<Mutex<Option<MemoryAnalysis>>>,
+    tx: UnboundedSender<Action>,
+    cancelation_token: CancellationToken,
+) {
+    let mut analysis = MemoryAnalysis {
+        total: storage
+            .query::<u64>(&redis::cmd("DBSIZE"))
+            .await
+            .unwrap_or_default(),
+        ..MemoryAnalysis::default()
+    };
+    let mut cursor = 0u64;
+
+    loop {
+        let mut scan = redis::cmd("SCAN");
+        scan.arg(cursor).arg("COUNT").arg(SCAN_COUNT);
+        let keys = tokio::select! {
+            result = storage.query::<(u64, Vec<String>)>(&scan) => match result {
+                Ok((next, keys)) => {
+                    cursor = next;
+                    keys
+                }
+                Err(err) => {
+                    let _ = tx.send(Action::Error(format!("Memory analysis failed: {err}")));
+                    return;
+                }
+            },
+            _ = cancelation_token.cancelled() => return,
+        };
+
+        let sizes = futures::future::join_all(keys.into_iter().map(|key| key_usage(&storage, key)));
+        let usages = tokio::select! {
+            usages = sizes => usages,
+            _ = cancelation_token.cancelled() => return,
+        };
+        for usage in usages.into_iter().flatten() {
+            analysis.add(usage, &delimiter);
+        }
+
+        analysis.finished = cursor == 0;
+        *shared.lock().unwrap() = Some(analysis.clone());
+        let _ = tx.send(Action::LoadMemory);
+
+        if analysis.finished {
+            return;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn usage(key: &str, key_type: &str, bytes: u64, cardinality: Option<u64>) -> KeyUsage {
+        KeyUsage {
+            key: key.into(),
+            key_type: key_type.into(),
+            bytes,
+            cardinality,
+        }
+    }
+
+    #[test]
+    fn test_prefix() {
+        assert_eq!(prefix("user:1:name", ":"), "user:");
+        assert_eq!(prefix("counter", ":"), "");
+        assert_eq!(prefix("app::cache", "::"), "app::");
+        assert_eq!(prefix("user:1", ""), "");
+    }
+
+    #[test]
+    fn test_add() {
+        let mut analysis = MemoryAnalysis::default();
+        for i in 0..TOP_N as u64 + 5 {
+            analysis.add(usage(&format!("user:{i}"), "hash", i, Some(100 - i)), ":");
+        }
+        analysis.add(usage("counter", "string", 1000, None), ":");
+
+        assert_eq!(analysis.scanned, TOP_N as u64 + 6);
+        assert_eq!(analysis.biggest["hash"].len(), TOP_N);
+        assert_eq!(analysis.biggest["hash"][0].key, "user:24");
+        assert_eq!(analysis.biggest["string"][0].key, "counter");
+        assert_eq!(analysis.cardinality.len(), TOP_N);
+        assert_eq!(analysis.cardinality[0].key, "user:0");
+        assert_eq!(
+            analysis.prefixes["user:"],
+            PrefixUsage {
+                prefix: "user:".into(),
+                keys: TOP_N as u64 + 5,
+                bytes: (0..TOP_N as u64 + 5).sum(),
+            }
+        );
+        assert_eq!(analysis.prefixes[""].bytes, 1000);
+    }
+}
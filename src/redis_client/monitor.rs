@@ -0,0 +1,171 @@
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex},
+};
+
+use futures::StreamExt;
+use tokio::sync::mpsc::UnboundedSender;
+use tokio_util::sync::CancellationToken;
+
+use crate::action::Action;
+
+/// Entries kept by the monitor pane, and waiting to be taken by it.
+pub const CAPACITY: usize = 1000;
+
+/// One command the server ran, as `MONITOR` reports it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MonitorEntry {
+    /// Unix time with microseconds.
+    pub time: f64,
+    pub db: u32,
+    /// Address of the client, `lua` for scripts.
+    pub client: String,
+    pub args: Vec<String>,
+}
+
+impl MonitorEntry {
+    /// Parses `1339518083.107412 [0 127.0.0.1:60866] "keys" "*"`.
+    pub fn parse(line: &str) -> Option<Self> {
+        let (time, rest) = line.split_once(' ')?;
+        let (source, args) = rest.strip_prefix('[')?.split_once("] ")?;
+        let (db, client) = source.split_once(' ')?;
+
+        Some(Self {
+            time: time.parse().ok()?,
+            db: db.parse().ok()?,
+            client: client.into(),
+            args: parse_args(args)?,
+        })
+    }
+
+    /// Command name in upper case, `?` when the arguments are empty.
+    pub fn command(&self) -> String {
+        self.args
+            .first()
+            .map_or_else(|| "?".into(), |name| name.to_uppercase())
+    }
+}
+
+/// Splits `"set" "a b" "\x00\"q\""` into its quoted strings, unescaping them.
+fn parse_args(text: &str) -> Option<Vec<String>> {
+    let mut args = Vec::new();
+    let mut chars = text.chars();
+
+    loop {
+        match chars.next() {
+            None => return Some(args),
+            Some(' ') => continue,
+            Some('"') => {}
+            Some(_) => return None,
+        }
+
+        let mut arg = Vec::new();
+        loop {
+            match chars.next()? {
+                '"' => break,
+                '\\' => match chars.next()? {
+                    'n' => arg.push(b'\n'),
+                    'r' => arg.push(b'\r'),
+                    't' => arg.push(b'\t'),
+                    'a' => arg.push(0x07),
+                    'b' => arg.push(0x08),
+                    'x' => {
+                        let hex: String = chars.by_ref().take(2).collect();
+                        arg.push(u8::from_str_radix(&hex, 16).ok()?);
+                    }
+                    c => {
+                        let mut bytes = [0; 4];
+                        arg.extend_from_slice(c.encode_utf8(&mut bytes).as_bytes());
+                    }
+                },
+                c => {
+                    let mut bytes = [0; 4];
+                    arg.extend_from_slice(c.encode_utf8(&mut bytes).as_bytes());
+                }
+            }
+        }
+        args.push(String::from_utf8_lossy(&arg).into_owned());
+    }
+}
+
+/// Streams `MONITOR` from a connection of its own into `incoming` until the token is cancelled.
+/// The app is told with [`Action::LoadMonitor`] whenever entries start waiting, the oldest ones
+/// are dropped when it falls behind.
+pub async fn stream(
+    client: redis::Client,
+    incoming: Arc<Mutex<VecDeque<MonitorEntry>>>,
+    tx: UnboundedSender<Action>,
+    cancelation_token: CancellationToken,
+) {
+    let mut monitor = match client.get_async_monitor().await {
+        Ok(monitor) => monitor,
+        Err(err) => {
+            let _ = tx.send(Action::Error(format!(
+                "Failed to connect for MONITOR: {err}"
+            )));
+            return;
+        }
+    };
+    if let Err(err) = monitor.monitor().await {
+        let _ = tx.send(Action::Error(format!("Failed to start MONITOR: {err}")));
+        return;
+    }
+
+    let mut lines = monitor.into_on_message::<String>();
+    loop {
+        tokio::select! {
+            line = lines.next() => {
+                let Some(line) = line else {
+                    let _ = tx.send(Action::Error("The MONITOR connection was closed".into()));
+                    break;
+                };
+                let Some(entry) = MonitorEntry::parse(&line) else {
+                    log::debug!("Unexpected MONITOR line: {line}");
+                    continue;
+                };
+
+                let was_empty = {
+                    let mut incoming = incoming.lock().unwrap();
+                    if incoming.len() == CAPACITY {
+                        incoming.pop_front();
+                    }
+                    incoming.push_back(entry);
+                    incoming.len() == 1
+                };
+                if was_empty {
+                    let _ = tx.send(Action::LoadMonitor);
+                }
+            },
+            _ = cancelation_token.cancelled() => break,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let entry = MonitorEntry::parse(
+            r#"1339518083.107412 [3 127.0.0.1:60866] "set" "a \"b\"" "\xe2\x9c\x93\n""#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            entry,
+            MonitorEntry {
+                time: 1339518083.107412,
+                db: 3,
+                client: "127.0.0.1:60866".into(),
+                args: vec!["set".into(), "a \"b\"".into(), "✓\n".into()],
+            }
+        );
+
+        let lua = MonitorEntry::parse(r#"1339518083.1 [0 lua] "get" "user:1""#).unwrap();
+        assert_eq!(lua.client, "lua");
+        assert!(MonitorEntry::parse("OK").is_none());
+    }
+}
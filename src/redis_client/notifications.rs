@@ -0,0 +1,99 @@
+use std::sync::{Arc, Mutex};
+
+use futures::StreamExt;
+use tokio_util::sync::CancellationToken;
+
+/// Changed keys waiting to be refreshed, past which new ones are dropped.
+const CAPACITY: usize = 1000;
+/// Classes of `notify-keyspace-events` that announce something happening to keys.
+const EVENT_CLASSES: &str = "Ag$lshzxetdnm";
+
+/// Whether `notify-keyspace-events` sends keyspace events (`K`) for at least one class.
+pub fn is_enabled(flags: &str) -> bool {
+    flags.contains('K') && flags.chars().any(|c| EVENT_CLASSES.contains(c))
+}
+
+/// The flags with keyspace events for every class added, keeping what was already on.
+pub fn enable_flags(flags: &str) -> String {
+    let mut enabled = flags.to_string();
+    for flag in ['K', 'A'] {
+        if !enabled.contains(flag) {
+            enabled.push(flag);
+        }
+    }
+    enabled
+}
+
+/// The key a notification of `__keyspace@<db>__:<key>` is about.
+pub fn keyspace_key(channel: &str, db: i64) -> Option<&str> {
+    channel.strip_prefix(&format!("__keyspace@{db}__:"))
+}
+
+/// Subscribes a connection of its own to the keyspace events of the database and collects the
+/// changed keys into `changed` until the token is cancelled. The app picks them up on its
+/// ticks, one refresh at a time.
+pub async fn stream(
+    client: redis::Client,
+    changed: Arc<Mutex<Vec<String>>>,
+    cancelation_token: CancellationToken,
+) {
+    let db = client.get_connection_info().redis.db;
+    let mut pubsub = match client.get_async_pubsub().await {
+        Ok(pubsub) => pubsub,
+        Err(err) => {
+            log::error!("Failed to connect for keyspace notifications: {err:?}");
+            return;
+        }
+    };
+    if let Err(err) = pubsub.psubscribe(format!("__keyspace@{db}__:*")).await {
+        log::error!("Failed to subscribe to keyspace notifications: {err:?}");
+        return;
+    }
+
+    let mut messages = pubsub.into_on_message();
+    loop {
+        tokio::select! {
+            message = messages.next() => {
+                let Some(message) = message else {
+                    log::error!("The keyspace notifications connection was closed");
+                    break;
+                };
+                let Some(key) = keyspace_key(message.get_channel_name(), db) else {
+                    continue;
+                };
+
+                let mut changed = changed.lock().unwrap();
+                if changed.len() < CAPACITY && !changed.iter().any(|k| k == key) {
+                    changed.push(key.to_string());
+                }
+            },
+            _ = cancelation_token.cancelled() => break,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_flags() {
+        assert!(!is_enabled(""));
+        assert!(!is_enabled("Ex"));
+        assert!(!is_enabled("K"));
+        assert!(is_enabled("KEA"));
+        assert!(is_enabled("K$"));
+
+        assert_eq!(enable_flags(""), "KA");
+        assert_eq!(enable_flags("Ex"), "ExKA");
+        assert_eq!(enable_flags("AKE"), "AKE");
+    }
+
+    #[test]
+    fn test_keyspace_key() {
+        assert_eq!(keyspace_key("__keyspace@2__:user:1", 2), Some("user:1"));
+        assert_eq!(keyspace_key("__keyspace@2__:user:1", 0), None);
+    }
+}
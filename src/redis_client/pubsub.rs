@@ -0,0 +1,261 @@
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use futures::StreamExt;
+use redis::Value;
+use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc::UnboundedSender;
+use tokio_util::sync::CancellationToken;
+
+use crate::action::Action;
+
+use super::value::as_string;
+
+/// Messages kept by the pub/sub pane, and waiting to be taken by it.
+pub const CAPACITY: usize = 1000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SubscriptionKind {
+    Channel,
+    Pattern,
+    /// A channel of a cluster shard, Redis 7.0 and later.
+    Shard,
+}
+
+impl SubscriptionKind {
+    pub fn subscribe_command(self) -> &'static str {
+        match self {
+            Self::Channel => "SUBSCRIBE",
+            Self::Pattern => "PSUBSCRIBE",
+            Self::Shard => "SSUBSCRIBE",
+        }
+    }
+
+    pub fn publish_command(self) -> &'static str {
+        match self {
+            Self::Shard => "SPUBLISH",
+            _ => "PUBLISH",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Subscription {
+    pub kind: SubscriptionKind,
+    pub name: String,
+}
+
+/// A message received on one of the subscriptions.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PubSubMessage {
+    /// Unix time it was received at.
+    pub time: f64,
+    pub channel: String,
+    /// The pattern it matched for `PSUBSCRIBE`.
+    pub pattern: Option<String>,
+    pub payload: String,
+    pub sharded: bool,
+}
+
+/// Reads a message out of what a subscribed connection sends, arrays with RESP2 and pushes with
+/// RESP3. Subscription confirmations are `Ok(None)`, errors such as an unknown `SSUBSCRIBE` on
+/// an old server are `Err`.
+pub fn parse_message(value: &Value, time: f64) -> Result<Option<PubSubMessage>, String> {
+    let fields: Vec<&Value> = match value {
+        Value::Array(data) => data.iter().collect(),
+        Value::Push { kind, data } => {
+            let kind = Value::BulkString(kind.to_string().into_bytes());
+            return parse_message(
+                &Value::Array([kind].into_iter().chain(data.iter().cloned()).collect()),
+                time,
+            );
+        }
+        Value::ServerError(err) => {
+            return Err(format!(
+                "{} {}",
+                err.code(),
+                err.details().unwrap_or_default()
+            ))
+        }
+        _ => return Ok(None),
+    };
+
+    let texts: Vec<String> = fields.iter().filter_map(|field| as_string(field)).collect();
+    let message = match texts.first().map(|kind| kind.to_lowercase()).as_deref() {
+        Some("message") if texts.len() == 3 => PubSubMessage {
+            time,
+            channel: texts[1].clone(),
+            pattern: None,
+            payload: texts[2].clone(),
+            sharded: false,
+        },
+        Some("smessage") if texts.len() == 3 => PubSubMessage {
+            time,
+            channel: texts[1].clone(),
+            pattern: None,
+            payload: texts[2].clone(),
+            sharded: true,
+        },
+        Some("pmessage") if texts.len() == 4 => PubSubMessage {
+            time,
+            channel: texts[2].clone(),
+            pattern: Some(texts[1].clone()),
+            payload: texts[3].clone(),
+            sharded: false,
+        },
+        _ => return Ok(None),
+    };
+    Ok(Some(message))
+}
+
+/// Subscribes a connection of its own and streams the messages into `incoming` until the token
+/// is cancelled. The app is told with [`Action::LoadPubSub`] whenever messages start waiting.
+pub async fn stream(
+    client: redis::Client,
+    subscriptions: Vec<Subscription>,
+    incoming: Arc<Mutex<VecDeque<PubSubMessage>>>,
+    tx: UnboundedSender<Action>,
+    cancelation_token: CancellationToken,
+) {
+    // `aio::PubSub` knows neither `SSUBSCRIBE` nor its messages, so the commands are written
+    // on a plain connection without waiting for replies, the confirmations come with the stream
+    #[allow(deprecated)]
+    let mut connection = match client.get_async_connection().await {
+        Ok(connection) => connection,
+        Err(err) => {
+            let _ = tx.send(Action::Error(format!(
+                "Failed to connect for Pub/Sub: {err}"
+            )));
+            return;
+        }
+    };
+
+    for subscription in &subscriptions {
+        let mut cmd = redis::cmd(subscription.kind.subscribe_command());
+        cmd.arg(&subscription.name).set_no_response(true);
+        if let Err(err) = cmd.query_async::<Value>(&mut connection).await {
+            let _ = tx.send(Action::Error(format!(
+                "Failed to subscribe to {}: {err}",
+                subscription.name
+            )));
+            return;
+        }
+    }
+
+    // `Monitor` does nothing but frame what the server sends, which is what is needed here
+    let mut values = connection.into_monitor().into_on_message::<Value>();
+    loop {
+        tokio::select! {
+            value = values.next() => {
+                let Some(value) = value else {
+                    let _ = tx.send(Action::Error("The Pub/Sub connection was closed".into()));
+                    break;
+                };
+
+                let time = SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .map(|elapsed| elapsed.as_secs_f64())
+                    .unwrap_or_default();
+                let message = match parse_message(&value, time) {
+                    Ok(Some(message)) => message,
+                    Ok(None) => continue,
+                    Err(err) => {
+                        let _ = tx.send(Action::Error(format!("Pub/Sub: {err}")));
+                        continue;
+                    }
+                };
+
+                let was_empty = {
+                    let mut incoming = incoming.lock().unwrap();
+                    if incoming.len() == CAPACITY {
+                        incoming.pop_front();
+                    }
+                    incoming.push_back(message);
+                    incoming.len() == 1
+                };
+                if was_empty {
+                    let _ = tx.send(Action::LoadPubSub);
+                }
+            },
+            _ = cancelation_token.cancelled() => break,
+        }
+    }
+}
+
+/// Channels with subscribers on the server, with their count of subscribers.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ActiveChannels {
+    pub channels: Vec<(String, u64)>,
+    /// From `PUBSUB SHARDCHANNELS`, empty before Redis 7.0.
+    pub shard_channels: Vec<(String, u64)>,
+    /// Patterns subscribed to, `PUBSUB NUMPAT`.
+    pub patterns: u64,
+}
+
+/// Parses a `PUBSUB NUMSUB` reply, channels and counts taking turns.
+pub fn parse_numsub(reply: &Value) -> Vec<(String, u64)> {
+    let Value::Array(items) = reply else {
+        return Vec::new();
+    };
+
+    let mut channels: Vec<(String, u64)> = items
+        .chunks(2)
+        .filter_map(|pair| match pair {
+            [channel, Value::Int(count)] => Some((as_string(channel)?, (*count).max(0) as u64)),
+            _ => None,
+        })
+        .collect();
+    channels.sort();
+    channels
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::redis_client::value::test_support::bulk;
+
+    #[test]
+    fn test_parse_message() {
+        let message = Value::Array(vec![
+            bulk("pmessage"),
+            bulk("news.*"),
+            bulk("news.it"),
+            bulk("hi"),
+        ]);
+        assert_eq!(
+            parse_message(&message, 1.5),
+            Ok(Some(PubSubMessage {
+                time: 1.5,
+                channel: "news.it".into(),
+                pattern: Some("news.*".into()),
+                payload: "hi".into(),
+                sharded: false,
+            }))
+        );
+
+        let sharded = Value::Push {
+            kind: redis::PushKind::SMessage,
+            data: vec![bulk("orders"), bulk("1")],
+        };
+        assert_eq!(
+            parse_message(&sharded, 0.0)
+                .unwrap()
+                .map(|message| message.sharded),
+            Some(true)
+        );
+
+        let confirmation = Value::Array(vec![bulk("subscribe"), bulk("orders"), Value::Int(1)]);
+        assert_eq!(parse_message(&confirmation, 0.0), Ok(None));
+    }
+
+    #[test]
+    fn test_parse_numsub() {
+        let reply = Value::Array(vec![bulk("b"), Value::Int(2), bulk("a"), Value::Int(0)]);
+        assert_eq!(parse_numsub(&reply), [("a".into(), 0), ("b".into(), 2)]);
+    }
+}
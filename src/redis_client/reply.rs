@@ -0,0 +1,259 @@
+use ratatui::{
+    style::Stylize,
+    text::{Line, Span},
+};
+use redis::{RedisError, Value};
+
+use crate::config;
+
+/// A RESP reply detached from the connection, ready to be rendered the way `redis-cli` does.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Reply {
+    Nil,
+    Integer(i64),
+    Double(f64),
+    Boolean(bool),
+    BigNumber(String),
+    Status(String),
+    String(String),
+    Verbatim { format: String, text: String },
+    Error(String),
+    Array(Vec<Reply>),
+    Set(Vec<Reply>),
+    Map(Vec<(Reply, Reply)>),
+    Push { kind: String, data: Vec<Reply> },
+}
+
+impl From<Value> for Reply {
+    fn from(value: Value) -> Self {
+        match value {
+            Value::Nil => Self::Nil,
+            Value::Int(i) => Self::Integer(i),
+            Value::BulkString(bytes) => Self::String(String::from_utf8_lossy(&bytes).into_owned()),
+            Value::Array(items) => Self::Array(items.into_iter().map(Into::into).collect()),
+            Value::SimpleString(s) => Self::Status(s),
+            Value::Okay => Self::Status("OK".into()),
+            Value::Map(pairs) => Self::Map(
+                pairs
+                    .into_iter()
+                    .map(|(k, v)| (k.into(), v.into()))
+                    .collect(),
+            ),
+            Value::Attribute { data, .. } => (*data).into(),
+            Value::Set(items) => Self::Set(items.into_iter().map(Into::into).collect()),
+            Value::Double(d) => Self::Double(d),
+            Value::Boolean(b) => Self::Boolean(b),
+            Value::VerbatimString { format, text } => Self::Verbatim {
+                format: format.to_string(),
+                text,
+            },
+            Value::BigNumber(n) => Self::BigNumber(n.to_string()),
+            Value::Push { kind, data } => Self::Push {
+                kind: kind.to_string(),
+                data: data.into_iter().map(Into::into).collect(),
+            },
+            Value::ServerError(err) => Self::Error(match err.details() {
+                Some(detail) => format!("{} {detail}", err.code()),
+                None => err.code().to_owned(),
+            }),
+        }
+    }
+}
+
+impl From<RedisError> for Reply {
+    fn from(err: RedisError) -> Self {
+        match (err.code(), err.detail()) {
+            (Some(code), Some(detail)) => Self::Error(format!("{code} {detail}")),
+            _ => Self::Error(err.to_string()),
+        }
+    }
+}
+
+impl From<Box<dyn std::error::Error + Send + Sync>> for Reply {
+    fn from(err: Box<dyn std::error::Error + Send + Sync>) -> Self {
+        match err.downcast::<RedisError>() {
+            Ok(err) => (*err).into(),
+            Err(err) => Self::Error(err.to_string()),
+        }
+    }
+}
+
+impl Reply {
+    /// Renders the reply as an indented tree, arrays and sets are numbered, maps use `=>`.
+    pub fn lines(&self) -> Vec<Line<'static>> {
+        let mut lines = Vec::new();
+        self.push_lines(&mut lines, Vec::new(), 0);
+        lines
+    }
+
+    fn push_lines(
+        &self,
+        lines: &mut Vec<Line<'static>>,
+        prefix: Vec<Span<'static>>,
+        indent: usize,
+    ) {
+        let colors = config::get().colors;
+
+        let scalar = |span: Span<'static>| {
+            let mut spans = prefix.clone();
+            spans.push(span);
+            Line::from(spans)
+        };
+
+        match self {
+            Self::Nil => lines.push(scalar("(nil)".fg(colors.base03))),
+            Self::Integer(i) => lines.push(scalar(format!("(integer) {i}").fg(colors.base09))),
+            Self::Double(d) => lines.push(scalar(format!("(double) {d}").fg(colors.base09))),
+            Self::Boolean(b) => lines.push(scalar(format!("({b})").fg(colors.base09))),
+            Self::BigNumber(n) => lines.push(scalar(format!("(big number) {n}").fg(colors.base09))),
+            Self::Status(s) => lines.push(scalar(s.clone().fg(colors.base05))),
+            Self::String(s) => lines.push(scalar(format!("{s:?}").fg(colors.base0b))),
+            Self::Error(e) => lines.push(scalar(format!("(error) {e}").fg(colors.base08))),
+            Self::Verbatim { format, text } => {
+                lines.push(scalar(format!("(verbatim {format})").fg(colors.base03)));
+                let pad = " ".repeat(indent);
+                lines.extend(
+                    text.lines()
+                        .map(|line| Line::from(format!("{pad}{line}")).fg(colors.base05)),
+                );
+            }
+            Self::Array(items) => {
+                Self::push_collection(lines, prefix, indent, items.iter(), ")", "array")
+            }
+            Self::Set(items) => {
+                Self::push_collection(lines, prefix, indent, items.iter(), "~", "set")
+            }
+            Self::Push { kind, data } => {
+                lines.push(scalar(format!("(push {kind})").fg(colors.base03)));
+                Self::push_collection(lines, Vec::new(), indent, data.iter(), ")", "push")
+            }
+            Self::Map(pairs) => {
+                if pairs.is_empty() {
+                    lines.push(scalar("(empty hash)".fg(colors.base03)));
+                    return;
+                }
+
+                let width = pairs.len().to_string().len();
+                for (idx, (key, value)) in pairs.iter().enumerate() {
+                    let mut spans = if idx == 0 {
+                        prefix.clone()
+                    } else {
+                        vec![Span::raw(" ".repeat(indent))]
+                    };
+                    spans.push(format!("{:>width$}# ", idx + 1).fg(colors.base03));
+
+                    let mut key_lines = Vec::new();
+                    let key_indent = width_of(&spans);
+                    key.push_lines(&mut key_lines, spans, key_indent);
+
+                    let mut last = key_lines.pop().unwrap_or_default().spans;
+                    lines.extend(key_lines);
+                    last.push(" => ".fg(colors.base03));
+                    let value_indent = width_of(&last);
+                    value.push_lines(lines, last, value_indent);
+                }
+            }
+        }
+    }
+
+    fn push_collection<'a>(
+        lines: &mut Vec<Line<'static>>,
+        prefix: Vec<Span<'static>>,
+        indent: usize,
+        items: impl ExactSizeIterator<Item = &'a Reply>,
+        marker: &str,
+        name: &str,
+    ) {
+        let colors = config::get().colors;
+
+        if items.len() == 0 {
+            let mut spans = prefix;
+            spans.push(format!("(empty {name})").fg(colors.base03));
+            lines.push(Line::from(spans));
+            return;
+        }
+
+        let width = items.len().to_string().len();
+        for (idx, item) in items.enumerate() {
+            let mut spans = if idx == 0 {
+                prefix.clone()
+            } else {
+                vec![Span::raw(" ".repeat(indent))]
+            };
+            spans.push(format!("{:>width$}{marker} ", idx + 1).fg(colors.base03));
+            let indent = width_of(&spans);
+            item.push_lines(lines, spans, indent);
+        }
+    }
+}
+
+fn width_of(spans: &[Span<'_>]) -> usize {
+    spans.iter().map(Span::width).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::redis_client::value::test_support::bulk;
+
+    fn render(reply: &Reply) -> Vec<String> {
+        reply.lines().iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn test_scalars() {
+        assert_eq!(render(&Value::Nil.into()), ["(nil)"]);
+        assert_eq!(render(&Value::Int(42).into()), ["(integer) 42"]);
+        assert_eq!(render(&Value::Okay.into()), ["OK"]);
+        assert_eq!(render(&bulk("a \"b\"").into()), [r#""a \"b\"""#]);
+    }
+
+    #[test]
+    fn test_nested_array() {
+        let reply: Reply = Value::Array(vec![
+            bulk("foo"),
+            Value::Array(vec![Value::Int(1), Value::Nil]),
+            Value::Array(vec![]),
+        ])
+        .into();
+
+        assert_eq!(
+            render(&reply),
+            [
+                r#"1) "foo""#,
+                "2) 1) (integer) 1",
+                "   2) (nil)",
+                "3) (empty array)",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_map() {
+        let reply: Reply = Value::Map(vec![
+            (Value::SimpleString("server".into()), bulk("redis")),
+            (
+                Value::SimpleString("modules".into()),
+                Value::Array(vec![Value::Int(1), Value::Int(2)]),
+            ),
+        ])
+        .into();
+
+        assert_eq!(
+            render(&reply),
+            [
+                r#"1# server => "redis""#,
+                "2# modules => 1) (integer) 1",
+                "              2) (integer) 2",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_error() {
+        let reply = Reply::Error("ERR unknown command".into());
+        assert_eq!(render(&reply), ["(error) ERR unknown command"]);
+    }
+}
@@ -1,22 +1,41 @@
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
+use futures::future::join_all;
 use redis::aio::ConnectionManager;
 use tokio::{
-    sync::broadcast::{self, Sender},
+    sync::broadcast::{self, error::RecvError, Sender},
     sync::mpsc::UnboundedSender,
     task::JoinHandle,
 };
 use tokio_util::sync::CancellationToken;
 
-use crate::{action::Action, state::SharedState};
+use crate::{action::Action, config, state::SharedState};
 
-use super::{client, event::RedisEvent, storage::Storage, types::KeysList};
+use super::{
+    client::{self, ObjectFields},
+    clients::{self, ClientInfo, ClientList},
+    docs::CommandDoc,
+    edit,
+    event::RedisEvent,
+    export, hotkeys,
+    latency::{self, LatencyReport},
+    memory, monitor, notifications,
+    pubsub::{self, ActiveChannels},
+    reply::Reply,
+    scripts::{self, ScriptCache},
+    slowlog,
+    storage::{Storage, Watch},
+    types::{ConsoleEntry, KeysList, TransactionOutcome},
+};
 
 const BROADCAST_CAPACITY: usize = 50;
 
 pub struct Runner {
     cancelation_token: CancellationToken,
+    /// Opens the connections that can not be shared, such as the one of `MONITOR`.
+    client: redis::Client,
     manager: ConnectionManager,
+    storage: Storage,
 
     state: SharedState,
     info_task: JoinHandle<()>,
@@ -27,6 +46,7 @@ pub struct Runner {
 
 impl Runner {
     pub fn new(
+        client: redis::Client,
         manager: ConnectionManager,
         state: SharedState,
         action_tx: UnboundedSender<Action>,
@@ -37,6 +57,8 @@ impl Runner {
         let (tx, _) = broadcast::channel(BROADCAST_CAPACITY);
 
         Self {
+            storage: Storage::new(client.clone(), manager.clone(), state.undo.clone()),
+            client,
             manager,
             info_task,
             cancelation_token,
@@ -52,31 +74,52 @@ impl Runner {
         self
     }
 
+    #[must_use]
+    pub fn read_only(mut self, read_only: bool) -> Self {
+        self.storage = self.storage.read_only(read_only);
+        self
+    }
+
+    #[must_use]
+    pub fn object_fields(mut self, fields: ObjectFields) -> Self {
+        self.storage = self.storage.object_fields(fields);
+        self
+    }
+
     pub fn tx(&self) -> Sender<RedisEvent> {
         self.tx.clone()
     }
 
     pub fn start(&mut self) {
         self.launch_refresh_info_task();
-        self.launch_refresh_state_task()
+        self.launch_refresh_state_task();
+
+        if config::get().keyspace_notifications {
+            let _ = self.tx.send(RedisEvent::WatchKeyspace);
+        }
     }
 
     fn launch_refresh_state_task(&mut self) {
-        let manager = self.manager.clone();
         let cancelation_token = self.cancelation_token.clone();
 
         let action_tx = self.action_tx.clone();
         let mut rx = self.tx.subscribe();
         let state = self.state.clone();
-        let storage = Storage::new(manager);
+        let storage = self.storage.clone();
+        let client = self.client.clone();
 
         tokio::spawn(async move {
-            let mut event_handler = EventHandler::new(state, action_tx, storage);
+            let mut event_handler = EventHandler::new(state, action_tx, storage)
+                .client(client, cancelation_token.clone());
 
             loop {
                 tokio::select! {
-                    Ok(event) = rx.recv() => {
-                        event_handler.handle(event).await;
+                    event = rx.recv() => match event {
+                        Ok(event) => event_handler.handle(event).await,
+                        Err(RecvError::Lagged(skipped)) => {
+                            log::error!("Dropped {skipped} Redis events, the handler fell behind");
+                        }
+                        Err(RecvError::Closed) => break,
                     },
                     _ = cancelation_token.cancelled() => {
                         break;
@@ -89,6 +132,8 @@ impl Runner {
     fn launch_refresh_info_task(&mut self) {
         let tick: Duration = std::time::Duration::from_secs_f64(2.0);
         let info = self.state.info.clone();
+        let samples = self.state.samples.clone();
+        let ping = self.state.ping.clone();
         let mut manager = self.manager.clone();
         let cancelation_token = self.cancelation_token.clone();
 
@@ -101,11 +146,18 @@ impl Runner {
                         let info_res = client::redis_info(&mut manager).await;
 
                         match info_res {
-                            Ok(redis_info) => *info.lock().unwrap() = Some(redis_info),
+                            Ok(redis_info) => {
+                                samples.lock().unwrap().push(&redis_info, Instant::now());
+                                *info.lock().unwrap() = Some(redis_info);
+                            }
                             Err(_err) => {
                                 // TODO: show the popup
                             },
                         }
+
+                        if let Ok(rtt) = client::ping(&mut manager).await {
+                            ping.lock().unwrap().push(Instant::now(), rtt);
+                        }
                     },
                     _ = cancelation_token.cancelled() => {
                         break;
@@ -120,11 +172,46 @@ pub struct EventHandler {
     state: SharedState,
     tx: UnboundedSender<Action>,
     storage: Storage,
+    script_cache: ScriptCache,
+    client: Option<redis::Client>,
+    cancelation_token: CancellationToken,
+    /// Cancels the running `MONITOR` stream, a child of the runner's token.
+    monitor: Option<CancellationToken>,
+    /// Cancels the Pub/Sub connection, a child of the runner's token.
+    pubsub: Option<CancellationToken>,
+    /// Cancels the keyspace notifications connection, a child of the runner's token.
+    keyspace: Option<CancellationToken>,
+    /// Cancels the running memory analysis, a child of the runner's token.
+    analysis: Option<CancellationToken>,
+    /// Cancels the running hot keys sampling, a child of the runner's token.
+    hot_keys: Option<CancellationToken>,
+    /// The keys watched for the transaction pane.
+    watch: Option<Watch>,
 }
 
 impl EventHandler {
     fn new(state: SharedState, tx: UnboundedSender<Action>, storage: Storage) -> Self {
-        Self { state, tx, storage }
+        Self {
+            state,
+            tx,
+            storage,
+            script_cache: ScriptCache::default(),
+            client: None,
+            cancelation_token: CancellationToken::new(),
+            monitor: None,
+            pubsub: None,
+            keyspace: None,
+            analysis: None,
+            hot_keys: None,
+            watch: None,
+        }
+    }
+
+    #[must_use]
+    fn client(mut self, client: redis::Client, cancelation_token: CancellationToken) -> Self {
+        self.client = Some(client);
+        self.cancelation_token = cancelation_token;
+        self
     }
 
     async fn handle(&mut self, event: RedisEvent) {
@@ -165,6 +252,655 @@ impl EventHandler {
                     }
                 }
             }
+            RedisEvent::FetchCommandDocs => {
+                let docs = self
+                    .storage
+                    .query::<redis::Value>(redis::cmd("COMMAND").arg("DOCS"))
+                    .await;
+
+                match docs {
+                    Ok(docs) => {
+                        *self.state.command_docs.lock().unwrap() = CommandDoc::parse_all(&docs);
+                        self.action_hook(Action::LoadCommandDocs);
+                    }
+                    Err(err) => log::error!("Failed to fetch command docs: {err:?}"),
+                }
+            }
+            RedisEvent::ExecuteCommand(args) => {
+                let Some(cmd) = client::command_from_args(&args) else {
+                    return;
+                };
+
+                let reply = match self.storage.query::<redis::Value>(&cmd).await {
+                    Ok(value) => Reply::from(value),
+                    Err(err) => Reply::from(err),
+                };
+
+                let command = client::display_command(&cmd);
+
+                self.state
+                    .console
+                    .lock()
+                    .unwrap()
+                    .push(ConsoleEntry { command, reply });
+                self.action_hook(Action::LoadConsoleReplies);
+                self.action_hook(Action::LoadUndoHistory);
+            }
+            RedisEvent::Batch(commands) => {
+                let cmds: Vec<redis::Cmd> = commands
+                    .iter()
+                    .filter_map(|args| client::command_from_args(args))
+                    .collect();
+
+                // A single command keeps its own name on the undo stack
+                let replies = match cmds.as_slice() {
+                    [cmd] => self
+                        .storage
+                        .query::<redis::Value>(cmd)
+                        .await
+                        .map(|v| vec![v]),
+                    cmds => self
+                        .storage
+                        .transaction(cmds, None)
+                        .await
+                        .and_then(|replies| replies.ok_or_else(|| "EXEC was aborted".into())),
+                };
+
+                let failed = match replies {
+                    Ok(replies) => replies
+                        .into_iter()
+                        .find_map(|value| match Reply::from(value) {
+                            Reply::Error(err) => Some(err),
+                            _ => None,
+                        }),
+                    Err(err) => Some(err.to_string()),
+                };
+
+                if let Some(err) = failed {
+                    self.action_hook(Action::Error(format!("Failed to apply changes: {err}")));
+                }
+                self.action_hook(Action::RefreshSpace);
+                self.action_hook(Action::LoadUndoHistory);
+            }
+            RedisEvent::WriteBack {
+                key,
+                original,
+                commands,
+            } => {
+                if let Err(err) = self.write_back(&key, &original, &commands).await {
+                    self.action_hook(Action::Error(format!("Failed to write back {key}: {err}")));
+                }
+                self.action_hook(Action::RefreshSpace);
+                self.action_hook(Action::LoadUndoHistory);
+            }
+            RedisEvent::FetchKey(key) => {
+                let exists = self
+                    .storage
+                    .query::<bool>(redis::cmd("EXISTS").arg(&key))
+                    .await;
+
+                let meta = match exists {
+                    Ok(true) => self.storage.fetch_meta(&key).await,
+                    Ok(false) => Err(format!("{key} does not exist").into()),
+                    Err(err) => Err(err),
+                };
+
+                match meta {
+                    Ok(meta) => {
+                        *self.state.fetched_key.lock().unwrap() = Some(meta);
+                        self.action_hook(Action::LoadFetchedKey);
+                    }
+                    Err(err) => {
+                        self.action_hook(Action::Error(format!("Failed to fetch {key}: {err}")))
+                    }
+                }
+            }
+            RedisEvent::RefreshKeys(keys) => {
+                let storage = &self.storage;
+                let metas = join_all(keys.iter().map(|key| async move {
+                    match storage.query::<bool>(redis::cmd("EXISTS").arg(key)).await {
+                        Ok(true) => storage.fetch_meta(key).await.map(Some),
+                        Ok(false) => Ok(None),
+                        Err(err) => Err(err),
+                    }
+                }))
+                .await;
+
+                let refreshed = keys
+                    .into_iter()
+                    .zip(metas)
+                    .filter_map(|(key, meta)| match meta {
+                        Ok(meta) => Some((key, meta)),
+                        Err(err) => {
+                            log::error!("Failed to refresh {key}: {err:?}");
+                            None
+                        }
+                    })
+                    .collect();
+                *self.state.refreshed_keys.lock().unwrap() = refreshed;
+                self.action_hook(Action::KeysRefreshed);
+            }
+            RedisEvent::WatchKeyspace => {
+                let flags = self
+                    .storage
+                    .query::<Vec<String>>(
+                        redis::cmd("CONFIG")
+                            .arg("GET")
+                            .arg("notify-keyspace-events"),
+                    )
+                    .await;
+
+                match flags.as_deref() {
+                    // Turning them on would be a write
+                    Ok([_, flags])
+                        if !notifications::is_enabled(flags) && self.storage.is_read_only() =>
+                    {
+                        log::debug!("Keyspace notifications are off on a read-only connection");
+                    }
+                    Ok([_, flags]) if !notifications::is_enabled(flags) => {
+                        self.action_hook(Action::OfferNotifications(notifications::enable_flags(
+                            flags,
+                        )));
+                    }
+                    Ok(_) => self.watch_keyspace(),
+                    // `CONFIG` is often renamed away on managed servers, where the
+                    // notifications may well be on already
+                    Err(err) => {
+                        log::debug!("Failed to read notify-keyspace-events: {err:?}");
+                        self.watch_keyspace();
+                    }
+                }
+            }
+            RedisEvent::EnableNotifications(flags) => {
+                let set = self
+                    .storage
+                    .query::<redis::Value>(
+                        redis::cmd("CONFIG")
+                            .arg("SET")
+                            .arg("notify-keyspace-events")
+                            .arg(&flags),
+                    )
+                    .await;
+
+                match set.map(Reply::from) {
+                    Ok(Reply::Error(err)) => self.action_hook(Action::Error(format!(
+                        "Failed to enable keyspace notifications: {err}"
+                    ))),
+                    Err(err) => self.action_hook(Action::Error(format!(
+                        "Failed to enable keyspace notifications: {err}"
+                    ))),
+                    Ok(_) => self.watch_keyspace(),
+                }
+            }
+            RedisEvent::FetchClients => self.fetch_clients().await,
+            RedisEvent::KillClient(id) => {
+                let killed = self
+                    .storage
+                    .query::<redis::Value>(redis::cmd("CLIENT").arg("KILL").arg("ID").arg(id))
+                    .await;
+
+                match killed.map(Reply::from) {
+                    Ok(Reply::Error(err)) => self
+                        .action_hook(Action::Error(format!("Failed to kill client {id}: {err}"))),
+                    Err(err) => self
+                        .action_hook(Action::Error(format!("Failed to kill client {id}: {err}"))),
+                    Ok(_) => {}
+                }
+                self.fetch_clients().await;
+            }
+            RedisEvent::FetchSlowlog => self.fetch_slowlog().await,
+            RedisEvent::FetchLatency => self.fetch_latency().await,
+            RedisEvent::AnalyzeMemory => {
+                let token = self.cancelation_token.child_token();
+                if let Some(previous) = self.analysis.replace(token.clone()) {
+                    previous.cancel();
+                }
+
+                tokio::spawn(memory::analyze(
+                    self.storage.clone(),
+                    config::get().key_delimiter.clone(),
+                    self.state.memory.clone(),
+                    self.tx.clone(),
+                    token,
+                ));
+            }
+            RedisEvent::StopAnalysis => {
+                if let Some(token) = self.analysis.take() {
+                    token.cancel();
+                }
+            }
+            RedisEvent::FindHotKeys => self.find_hot_keys().await,
+            RedisEvent::StopHotKeys => {
+                if let Some(token) = self.hot_keys.take() {
+                    token.cancel();
+                }
+            }
+            RedisEvent::ResetSlowlog => {
+                let reset = self
+                    .storage
+                    .query::<redis::Value>(redis::cmd("SLOWLOG").arg("RESET"))
+                    .await;
+
+                if let Err(err) = reset {
+                    self.action_hook(Action::Error(format!("Failed to reset the slowlog: {err}")));
+                }
+                self.fetch_slowlog().await;
+            }
+            RedisEvent::StartMonitor => {
+                let Some(client) = self.client.clone() else {
+                    return;
+                };
+                let token = self.cancelation_token.child_token();
+                if let Some(previous) = self.monitor.replace(token.clone()) {
+                    previous.cancel();
+                }
+
+                self.state.monitor.lock().unwrap().clear();
+                tokio::spawn(monitor::stream(
+                    client,
+                    self.state.monitor.clone(),
+                    self.tx.clone(),
+                    token,
+                ));
+            }
+            RedisEvent::StopMonitor => {
+                if let Some(token) = self.monitor.take() {
+                    token.cancel();
+                }
+            }
+            RedisEvent::Subscribe(subscriptions) => {
+                if let Some(previous) = self.pubsub.take() {
+                    previous.cancel();
+                }
+                let Some(client) = self.client.clone() else {
+                    return;
+                };
+
+                if !subscriptions.is_empty() {
+                    let token = self.cancelation_token.child_token();
+                    self.pubsub = Some(token.clone());
+                    tokio::spawn(pubsub::stream(
+                        client,
+                        subscriptions,
+                        self.state.pubsub.clone(),
+                        self.tx.clone(),
+                        token,
+                    ));
+                }
+                self.fetch_channels().await;
+            }
+            RedisEvent::FetchChannels => self.fetch_channels().await,
+            RedisEvent::Publish {
+                channel,
+                message,
+                kind,
+            } => {
+                let published = self
+                    .storage
+                    .query::<redis::Value>(
+                        redis::cmd(kind.publish_command())
+                            .arg(&channel)
+                            .arg(&message),
+                    )
+                    .await;
+
+                match published.map(Reply::from) {
+                    Ok(Reply::Error(err)) => self.action_hook(Action::Error(format!(
+                        "Failed to publish to {channel}: {err}"
+                    ))),
+                    Err(err) => self.action_hook(Action::Error(format!(
+                        "Failed to publish to {channel}: {err}"
+                    ))),
+                    Ok(_) => {}
+                }
+            }
+            RedisEvent::ExportKeys(keys) => {
+                let metas = join_all(keys.iter().map(|key| self.storage.fetch_meta(key)))
+                    .await
+                    .into_iter()
+                    .collect::<Result<Vec<_>, _>>();
+
+                let result = metas.and_then(|metas| Ok(export::write_keys(&metas)?));
+                match result {
+                    Ok(path) => self.action_hook(Action::Info(format!(
+                        "Exported {} keys to {}",
+                        keys.len(),
+                        path.display()
+                    ))),
+                    Err(err) => {
+                        self.action_hook(Action::Error(format!("Failed to export keys: {err}")))
+                    }
+                }
+            }
+            RedisEvent::Undo(count) => match self.storage.undo(count).await {
+                Ok(_) => {
+                    self.action_hook(Action::RefreshSpace);
+                    self.action_hook(Action::LoadUndoHistory);
+                }
+                Err(err) => self.action_hook(Action::Error(format!("Failed to undo: {err}"))),
+            },
+            RedisEvent::FetchFunctions => {
+                let list = self
+                    .storage
+                    .query::<redis::Value>(redis::cmd("FUNCTION").arg("LIST"))
+                    .await;
+
+                match list {
+                    Ok(list) => {
+                        *self.state.functions.lock().unwrap() = scripts::parse_function_list(&list);
+                        self.action_hook(Action::LoadFunctions);
+                    }
+                    // Servers before 7.0 have no functions, scripts still work
+                    Err(err) => log::debug!("Failed to list functions: {err:?}"),
+                }
+            }
+            RedisEvent::RunScript { source, keys, args } => {
+                let result = self
+                    .script_cache
+                    .eval(&self.storage, &source, &keys, &args)
+                    .await;
+
+                let command = format!(
+                    "EVAL <script> {} {}",
+                    keys.len(),
+                    [keys, args].concat().join(" ")
+                );
+                self.set_script_result(command, result);
+            }
+            RedisEvent::CallFunction { name, keys, args } => {
+                let cmd = scripts::fcall_cmd(&self.storage, &name, &keys, &args);
+                let result = self.storage.query::<redis::Value>(&cmd).await;
+
+                self.set_script_result(client::display_command(&cmd), result);
+            }
+            RedisEvent::Watch(commands) => {
+                let cmds: Vec<redis::Cmd> = commands
+                    .iter()
+                    .filter_map(|args| client::command_from_args(args))
+                    .collect();
+
+                if let Err(err) = self.storage.watch(&mut self.watch, &cmds).await {
+                    self.action_hook(Action::Error(format!("Failed to watch keys: {err}")));
+                }
+            }
+            RedisEvent::Unwatch => self.watch = None,
+            RedisEvent::ExecTransaction(commands) => {
+                let cmds: Vec<redis::Cmd> = commands
+                    .iter()
+                    .filter_map(|args| client::command_from_args(args))
+                    .collect();
+
+                let outcome = match self.storage.transaction(&cmds, self.watch.take()).await {
+                    Ok(Some(replies)) => TransactionOutcome::Executed(
+                        cmds.iter()
+                            .zip(replies)
+                            .map(|(cmd, value)| ConsoleEntry {
+                                command: client::display_command(cmd),
+                                reply: Reply::from(value),
+                            })
+                            .collect(),
+                    ),
+                    Ok(None) => TransactionOutcome::Aborted,
+                    Err(err) => TransactionOutcome::Failed(err.to_string()),
+                };
+
+                *self.state.transaction.lock().unwrap() = Some(outcome);
+                self.action_hook(Action::LoadTransactionResults);
+                self.action_hook(Action::RefreshSpace);
+                self.action_hook(Action::LoadUndoHistory);
+            }
+        }
+    }
+
+    fn set_script_result(
+        &self,
+        command: String,
+        result: Result<redis::Value, Box<dyn std::error::Error + Send + Sync>>,
+    ) {
+        let reply = match result {
+            Ok(value) => Reply::from(value),
+            Err(err) => Reply::from(err),
+        };
+
+        *self.state.script_result.lock().unwrap() = Some(ConsoleEntry { command, reply });
+        self.action_hook(Action::LoadScriptResult);
+        self.action_hook(Action::LoadUndoHistory);
+    }
+
+    fn watch_keyspace(&mut self) {
+        let Some(client) = self.client.clone() else {
+            return;
+        };
+        let token = self.cancelation_token.child_token();
+        if let Some(previous) = self.keyspace.replace(token.clone()) {
+            previous.cancel();
+        }
+
+        tokio::spawn(notifications::stream(
+            client,
+            self.state.changed_keys.clone(),
+            token,
+        ));
+    }
+
+    /// `CLIENT LIST` with `CLIENT INFO` for the connection of the app.
+    async fn fetch_clients(&self) {
+        let list = self
+            .storage
+            .query::<String>(redis::cmd("CLIENT").arg("LIST"))
+            .await;
+        // Not there before Redis 6.2
+        let own = self
+            .storage
+            .query::<String>(redis::cmd("CLIENT").arg("INFO"))
+            .await
+            .ok()
+            .and_then(|info| ClientInfo::parse(&info));
+
+        match list {
+            Ok(list) => {
+                *self.state.clients.lock().unwrap() = Some(ClientList {
+                    clients: clients::parse_client_list(&list),
+                    own,
+                });
+                self.action_hook(Action::LoadClients);
+            }
+            Err(err) => self.action_hook(Action::Error(format!("Failed to list clients: {err}"))),
+        }
+    }
+
+    /// `PUBSUB CHANNELS` and `PUBSUB SHARDCHANNELS` with their `NUMSUB` counts.
+    async fn fetch_channels(&self) {
+        let channels = match self.numsub("CHANNELS", "NUMSUB").await {
+            Ok(channels) => channels,
+            Err(err) => {
+                self.action_hook(Action::Error(format!("Failed to list channels: {err}")));
+                return;
+            }
+        };
+
+        *self.state.channels.lock().unwrap() = Some(ActiveChannels {
+            channels,
+            // Not there before Redis 7.0
+            shard_channels: self
+                .numsub("SHARDCHANNELS", "SHARDNUMSUB")
+                .await
+                .unwrap_or_default(),
+            patterns: self
+                .storage
+                .query::<u64>(redis::cmd("PUBSUB").arg("NUMPAT"))
+                .await
+                .unwrap_or_default(),
+        });
+        self.action_hook(Action::LoadChannels);
+    }
+
+    /// The channels of one `PUBSUB` listing subcommand with the counts of the matching one.
+    async fn numsub(
+        &self,
+        list: &str,
+        count: &str,
+    ) -> Result<Vec<(String, u64)>, Box<dyn std::error::Error + Send + Sync>> {
+        let channels = self
+            .storage
+            .query::<Vec<String>>(redis::cmd("PUBSUB").arg(list))
+            .await?;
+        if channels.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let counts = self
+            .storage
+            .query::<redis::Value>(redis::cmd("PUBSUB").arg(count).arg(&channels))
+            .await?;
+        Ok(pubsub::parse_numsub(&counts))
+    }
+
+    async fn fetch_slowlog(&self) {
+        let entries = self
+            .storage
+            .query::<redis::Value>(redis::cmd("SLOWLOG").arg("GET").arg(slowlog::FETCH_COUNT))
+            .await;
+
+        match entries {
+            Ok(entries) => {
+                *self.state.slowlog.lock().unwrap() = Some(slowlog::parse_slowlog(&entries));
+                self.action_hook(Action::LoadSlowlog);
+            }
+            Err(err) => {
+                self.action_hook(Action::Error(format!("Failed to get the slowlog: {err}")));
+                // Without entries, tells the pane the fetch is over
+                self.action_hook(Action::LoadSlowlog);
+            }
+        }
+    }
+
+    /// Samples with `OBJECT FREQ` when the eviction policy keeps frequencies, with `MONITOR`
+    /// otherwise.
+    async fn find_hot_keys(&mut self) {
+        let policy = self
+            .storage
+            .query::<Vec<String>>(redis::cmd("CONFIG").arg("GET").arg("maxmemory-policy"))
+            .await
+            .ok()
+            .and_then(|reply| reply.into_iter().nth(1))
+            .unwrap_or_default();
+
+        let token = self.cancelation_token.child_token();
+        if let Some(previous) = self.hot_keys.replace(token.clone()) {
+            previous.cancel();
+        }
+
+        let delimiter = config::get().key_delimiter.clone();
+        if hotkeys::is_lfu(&policy) {
+            tokio::spawn(hotkeys::sample_lfu(
+                self.storage.clone(),
+                delimiter,
+                self.state.hot_keys.clone(),
+                self.tx.clone(),
+                token,
+            ));
+        } else if let Some(client) = self.client.clone() {
+            tokio::spawn(hotkeys::sample_monitor(
+                client,
+                self.storage.clone(),
+                delimiter,
+                self.state.hot_keys.clone(),
+                self.tx.clone(),
+                token,
+            ));
+        }
+    }
+
+    async fn fetch_latency(&self) {
+        let latest = self
+            .storage
+            .query::<redis::Value>(redis::cmd("LATENCY").arg("LATEST"))
+            .await;
+        let events = match latest {
+            Ok(latest) => latency::parse_latest(&latest),
+            Err(err) => {
+                self.action_hook(Action::Error(format!(
+                    "Failed to get the latency events: {err}"
+                )));
+                // Without a report, tells the pane the fetch is over
+                self.action_hook(Action::LoadLatency);
+                return;
+            }
+        };
+
+        let mut histories = Vec::with_capacity(events.len());
+        for event in &events {
+            let history = self
+                .storage
+                .query::<redis::Value>(redis::cmd("LATENCY").arg("HISTORY").arg(&event.name))
+                .await
+                .map(|history| latency::parse_history(&history))
+                .unwrap_or_default();
+            histories.push((event.name.clone(), history));
+        }
+
+        let doctor = self
+            .storage
+            .query::<String>(redis::cmd("LATENCY").arg("DOCTOR"))
+            .await
+            .unwrap_or_else(|err| format!("LATENCY DOCTOR failed: {err}"));
+        let threshold = self
+            .storage
+            .query::<Vec<String>>(
+                redis::cmd("CONFIG")
+                    .arg("GET")
+                    .arg("latency-monitor-threshold"),
+            )
+            .await
+            .ok()
+            .and_then(|reply| reply.get(1)?.parse().ok());
+
+        *self.state.latency.lock().unwrap() = Some(LatencyReport {
+            events,
+            histories,
+            doctor,
+            threshold,
+        });
+        self.action_hook(Action::LoadLatency);
+    }
+
+    /// Applies the commands of an edited value in a transaction, watching the key from before
+    /// the comparison so that a change made in between aborts the write too.
+    async fn write_back(
+        &self,
+        key: &str,
+        original: &str,
+        commands: &[Vec<String>],
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let cmds: Vec<redis::Cmd> = commands
+            .iter()
+            .filter_map(|args| client::command_from_args(args))
+            .collect();
+
+        let mut watch = None;
+        self.storage.watch(&mut watch, &cmds).await?;
+
+        let current = self.storage.fetch_meta(key).await?;
+        if edit::editable_text(&current.value).as_deref() != Some(original) {
+            return Err("the key was changed on the server since it was opened".into());
+        }
+
+        let replies = self
+            .storage
+            .transaction(&cmds, watch)
+            .await?
+            .ok_or("the key was changed on the server while writing")?;
+
+        match replies
+            .into_iter()
+            .map(Reply::from)
+            .find_map(|reply| match reply {
+                Reply::Error(err) => Some(err),
+                _ => None,
+            }) {
+            Some(err) => Err(err.into()),
+            None => Ok(()),
         }
     }
 
@@ -0,0 +1,156 @@
+use std::{collections::VecDeque, time::Instant};
+
+use super::info::RedisInfo;
+
+/// 5 minutes of samples at the 2 seconds `INFO` is polled with.
+const CAPACITY: usize = 150;
+
+/// What the charts plot for one `INFO` poll. Counters are turned into rates since the previous
+/// poll, so the first sample has none.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Sample {
+    pub at: Instant,
+    pub ops_per_sec: u64,
+    pub used_memory: u64,
+    /// Zero when there is no limit.
+    pub max_memory: u64,
+    /// Share of the lookups since the previous poll that found the key.
+    pub hit_ratio: Option<f64>,
+    pub evicted_per_sec: f64,
+    pub expired_per_sec: f64,
+    pub clients: u64,
+    pub input_kbps: f64,
+    pub output_kbps: f64,
+}
+
+/// Counters of the previous poll, to compute rates from.
+#[derive(Debug, Clone)]
+struct Counters {
+    at: Instant,
+    hits: u64,
+    misses: u64,
+    evicted: u64,
+    expired: u64,
+}
+
+/// The latest samples, oldest first, the oldest ones dropped once full.
+#[derive(Debug, Default)]
+pub struct Samples {
+    samples: VecDeque<Sample>,
+    last: Option<Counters>,
+}
+
+impl Samples {
+    pub fn push(&mut self, info: &RedisInfo, at: Instant) {
+        let stats = &info.stats;
+        let counters = Counters {
+            at,
+            hits: stats.keyspace_hits,
+            misses: stats.keyspace_misses,
+            evicted: stats.evicted_keys,
+            expired: stats.expired_keys,
+        };
+
+        // Counters go back to zero on `CONFIG RESETSTAT` or a restart
+        let last = self.last.replace(counters.clone()).filter(|last| {
+            last.hits <= counters.hits
+                && last.misses <= counters.misses
+                && last.evicted <= counters.evicted
+                && last.expired <= counters.expired
+        });
+
+        let (hit_ratio, evicted_per_sec, expired_per_sec) = match last {
+            Some(last) => {
+                let secs = at.duration_since(last.at).as_secs_f64().max(f64::EPSILON);
+                let hits = counters.hits - last.hits;
+                let lookups = hits + counters.misses - last.misses;
+
+                (
+                    (lookups > 0).then(|| hits as f64 / lookups as f64),
+                    (counters.evicted - last.evicted) as f64 / secs,
+                    (counters.expired - last.expired) as f64 / secs,
+                )
+            }
+            None => (None, 0.0, 0.0),
+        };
+
+        if self.samples.len() == CAPACITY {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(Sample {
+            at,
+            ops_per_sec: stats.instantaneous_ops_per_sec,
+            used_memory: info.memory.used,
+            max_memory: info.memory.max_memory,
+            hit_ratio,
+            evicted_per_sec,
+            expired_per_sec,
+            clients: info.clients.connected,
+            input_kbps: stats.instantaneous_input_kbps,
+            output_kbps: stats.instantaneous_output_kbps,
+        });
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Sample> {
+        self.samples.iter()
+    }
+
+    pub fn latest(&self) -> Option<&Sample> {
+        self.samples.back()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn info(hits: u64, misses: u64, evicted: u64) -> RedisInfo {
+        let mut info = RedisInfo::default();
+        info.stats.keyspace_hits = hits;
+        info.stats.keyspace_misses = misses;
+        info.stats.evicted_keys = evicted;
+        info
+    }
+
+    #[test]
+    fn test_rates() {
+        let start = Instant::now();
+        let mut samples = Samples::default();
+
+        samples.push(&info(10, 10, 0), start);
+        assert_eq!(samples.latest().unwrap().hit_ratio, None);
+
+        samples.push(&info(13, 11, 8), start + Duration::from_secs(2));
+        let latest = samples.latest().unwrap();
+        assert_eq!(latest.hit_ratio, Some(0.75));
+        assert_eq!(latest.evicted_per_sec, 4.0);
+
+        // A reset counts as a fresh start instead of a negative rate
+        samples.push(&info(1, 0, 0), start + Duration::from_secs(4));
+        assert_eq!(samples.latest().unwrap().evicted_per_sec, 0.0);
+    }
+
+    #[test]
+    fn test_capacity() {
+        let start = Instant::now();
+        let mut samples = Samples::default();
+
+        for i in 0..CAPACITY + 5 {
+            samples.push(&RedisInfo::default(), start + Duration::from_secs(i as u64));
+        }
+
+        assert_eq!(samples.iter().count(), CAPACITY);
+        assert_eq!(
+            samples.iter().next().unwrap().at,
+            start + Duration::from_secs(5)
+        );
+    }
+}
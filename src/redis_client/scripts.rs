@@ -0,0 +1,181 @@
+use std::collections::HashMap;
+
+use redis::{Cmd, ErrorKind, RedisError, Value};
+
+use super::{
+    storage::Storage,
+    value::{as_string, pairs, strings},
+};
+
+/// A function registered on the server, as listed by `FUNCTION LIST`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RedisFunction {
+    pub library: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub flags: Vec<String>,
+}
+
+impl RedisFunction {
+    pub fn is_read_only(&self) -> bool {
+        self.flags.iter().any(|flag| flag == "no-writes")
+    }
+}
+
+pub fn parse_function_list(value: &Value) -> Vec<RedisFunction> {
+    let Value::Array(libraries) = value else {
+        return Vec::new();
+    };
+
+    let mut functions = Vec::new();
+    for library in libraries {
+        let fields = pairs(library);
+        let library_name = fields
+            .iter()
+            .find(|(field, _)| field == "library_name")
+            .and_then(|(_, value)| as_string(value))
+            .unwrap_or_default();
+
+        let Some((_, Value::Array(entries))) =
+            fields.iter().find(|(field, _)| field == "functions")
+        else {
+            continue;
+        };
+
+        for entry in entries {
+            let mut function = RedisFunction {
+                library: library_name.clone(),
+                name: String::new(),
+                description: None,
+                flags: Vec::new(),
+            };
+
+            for (field, value) in pairs(entry) {
+                match field.as_str() {
+                    "name" => function.name = as_string(value).unwrap_or_default(),
+                    "description" => function.description = as_string(value),
+                    "flags" => function.flags = strings(value),
+                    _ => {}
+                }
+            }
+
+            functions.push(function);
+        }
+    }
+
+    functions
+}
+
+/// Remembers the SHA1 returned by `SCRIPT LOAD` for every script body already sent,
+/// so that subsequent runs only ship the digest with `EVALSHA`.
+#[derive(Debug, Default)]
+pub struct ScriptCache {
+    digests: HashMap<String, String>,
+}
+
+impl ScriptCache {
+    pub async fn eval(
+        &mut self,
+        storage: &Storage,
+        source: &str,
+        keys: &[String],
+        args: &[String],
+    ) -> Result<Value, Box<dyn std::error::Error + Send + Sync>> {
+        let sha = self.load(storage, source, false).await?;
+
+        match storage.query(&eval_cmd(storage, &sha, keys, args)).await {
+            // The script cache is flushed on restart and by SCRIPT FLUSH
+            Err(err) if is_noscript(err.as_ref()) => {
+                let sha = self.load(storage, source, true).await?;
+                storage.query(&eval_cmd(storage, &sha, keys, args)).await
+            }
+            result => result,
+        }
+    }
+
+    async fn load(
+        &mut self,
+        storage: &Storage,
+        source: &str,
+        force: bool,
+    ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        if let Some(sha) = self.digests.get(source).filter(|_| !force) {
+            return Ok(sha.clone());
+        }
+
+        let sha: String = storage
+            .query(redis::cmd("SCRIPT").arg("LOAD").arg(source))
+            .await?;
+        self.digests.insert(source.to_owned(), sha.clone());
+
+        Ok(sha)
+    }
+}
+
+/// `EVALSHA`, or `EVALSHA_RO` on a read-only connection so that a writing script fails on the server.
+fn eval_cmd(storage: &Storage, sha: &str, keys: &[String], args: &[String]) -> Cmd {
+    let name = if storage.is_read_only() {
+        "EVALSHA_RO"
+    } else {
+        "EVALSHA"
+    };
+
+    let mut cmd = redis::cmd(name);
+    cmd.arg(sha).arg(keys.len()).arg(keys).arg(args);
+    cmd
+}
+
+/// `FCALL`, or `FCALL_RO` on a read-only connection.
+pub fn fcall_cmd(storage: &Storage, name: &str, keys: &[String], args: &[String]) -> Cmd {
+    let command = if storage.is_read_only() {
+        "FCALL_RO"
+    } else {
+        "FCALL"
+    };
+
+    let mut cmd = redis::cmd(command);
+    cmd.arg(name).arg(keys.len()).arg(keys).arg(args);
+    cmd
+}
+
+fn is_noscript(err: &(dyn std::error::Error + Send + Sync + 'static)) -> bool {
+    err.downcast_ref::<RedisError>()
+        .is_some_and(|err| err.kind() == ErrorKind::NoScriptError)
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::redis_client::value::test_support::bulk;
+
+    #[test]
+    fn test_parse_function_list() {
+        let reply = Value::Array(vec![Value::Array(vec![
+            bulk("library_name"),
+            bulk("mylib"),
+            bulk("engine"),
+            bulk("LUA"),
+            bulk("functions"),
+            Value::Array(vec![Value::Array(vec![
+                bulk("name"),
+                bulk("knockknock"),
+                bulk("description"),
+                Value::Nil,
+                bulk("flags"),
+                Value::Array(vec![bulk("no-writes")]),
+            ])]),
+        ])]);
+
+        assert_eq!(
+            parse_function_list(&reply),
+            [RedisFunction {
+                library: "mylib".into(),
+                name: "knockknock".into(),
+                description: None,
+                flags: vec!["no-writes".into()],
+            }]
+        );
+    }
+}
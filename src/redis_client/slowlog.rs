@@ -0,0 +1,163 @@
+use std::collections::HashMap;
+
+use redis::Value;
+
+use super::value::{as_string, as_u64};
+
+/// How many entries `SLOWLOG GET` asks for.
+pub const FETCH_COUNT: usize = 128;
+
+/// A command that took longer than `slowlog-log-slower-than`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SlowlogEntry {
+    pub id: u64,
+    /// Unix time the command was run at.
+    pub timestamp: u64,
+    pub duration_us: u64,
+    /// The command with its arguments, long ones are cut by the server.
+    pub args: Vec<String>,
+    /// Missing before Redis 4.0.
+    pub client_addr: String,
+    pub client_name: String,
+}
+
+impl SlowlogEntry {
+    /// Command name in upper case, `?` when the arguments are empty.
+    pub fn command(&self) -> String {
+        self.args
+            .first()
+            .map_or_else(|| "?".into(), |name| name.to_uppercase())
+    }
+}
+
+/// Parses a `SLOWLOG GET` reply, newest entry first as the server sends them.
+pub fn parse_slowlog(reply: &Value) -> Vec<SlowlogEntry> {
+    let Value::Array(entries) = reply else {
+        return Vec::new();
+    };
+
+    entries
+        .iter()
+        .filter_map(|entry| {
+            let Value::Array(fields) = entry else {
+                return None;
+            };
+
+            let args = match fields.get(3) {
+                Some(Value::Array(args)) => args.iter().filter_map(as_string).collect(),
+                _ => Vec::new(),
+            };
+
+            Some(SlowlogEntry {
+                id: as_u64(fields.first()?)?,
+                timestamp: as_u64(fields.get(1)?)?,
+                duration_us: as_u64(fields.get(2)?)?,
+                args,
+                client_addr: fields.get(4).and_then(as_string).unwrap_or_default(),
+                client_name: fields.get(5).and_then(as_string).unwrap_or_default(),
+            })
+        })
+        .collect()
+}
+
+/// How slow one command was across the entries.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CommandStats {
+    pub command: String,
+    pub count: usize,
+    pub total_us: u64,
+    pub max_us: u64,
+}
+
+impl CommandStats {
+    pub fn average_us(&self) -> u64 {
+        self.total_us / self.count.max(1) as u64
+    }
+}
+
+/// Groups the entries by command, the slowest in total first.
+pub fn aggregate<'a>(entries: impl IntoIterator<Item = &'a SlowlogEntry>) -> Vec<CommandStats> {
+    let mut stats: HashMap<String, CommandStats> = HashMap::new();
+
+    for entry in entries {
+        let command = entry.command();
+        let stat = stats
+            .entry(command.clone())
+            .or_insert_with(|| CommandStats {
+                command,
+                count: 0,
+                total_us: 0,
+                max_us: 0,
+            });
+        stat.count += 1;
+        stat.total_us += entry.duration_us;
+        stat.max_us = stat.max_us.max(entry.duration_us);
+    }
+
+    let mut stats: Vec<CommandStats> = stats.into_values().collect();
+    stats.sort_by(|a, b| {
+        b.total_us
+            .cmp(&a.total_us)
+            .then_with(|| a.command.cmp(&b.command))
+    });
+    stats
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::redis_client::value::test_support::bulk;
+
+    fn entry(id: i64, duration: i64, args: &[&str]) -> Value {
+        Value::Array(vec![
+            Value::Int(id),
+            Value::Int(1_700_000_000),
+            Value::Int(duration),
+            Value::Array(args.iter().map(|arg| bulk(arg)).collect()),
+            bulk("127.0.0.1:5000"),
+            bulk("worker"),
+        ])
+    }
+
+    #[test]
+    fn test_parse_and_aggregate() {
+        let reply = Value::Array(vec![
+            entry(3, 300, &["keys", "*"]),
+            entry(2, 100, &["HGETALL", "big"]),
+            entry(1, 500, &["KEYS", "user:*"]),
+        ]);
+
+        let entries = parse_slowlog(&reply);
+        assert_eq!(
+            entries[0],
+            SlowlogEntry {
+                id: 3,
+                timestamp: 1_700_000_000,
+                duration_us: 300,
+                args: vec!["keys".into(), "*".into()],
+                client_addr: "127.0.0.1:5000".into(),
+                client_name: "worker".into(),
+            }
+        );
+
+        assert_eq!(
+            aggregate(&entries),
+            [
+                CommandStats {
+                    command: "KEYS".into(),
+                    count: 2,
+                    total_us: 800,
+                    max_us: 500,
+                },
+                CommandStats {
+                    command: "HGETALL".into(),
+                    count: 1,
+                    total_us: 100,
+                    max_us: 100,
+                },
+            ]
+        );
+    }
+}
@@ -1,28 +1,37 @@
 use futures::future::join_all;
-use redis::aio::ConnectionManager;
+use redis::{
+    aio::{ConnectionLike, ConnectionManager, MultiplexedConnection},
+    Cmd, FromRedisValue, Value,
+};
 
 use super::{
-    client::fetch_meta,
+    client::{display_command, fetch_meta, ObjectFields},
+    guard::CommandGuard,
     types::{KeyMeta, KeysList},
+    undo::{self, UndoEntry, UndoStack},
 };
 
 pub struct FetchKeysWithMeta<'a> {
     manager: redis::aio::ConnectionManager,
+    #[allow(dead_code)]
     size: Option<usize>,
     cursor: Option<usize>,
     pattern: Option<&'a str>,
+    fields: ObjectFields,
 }
 
 impl<'a> FetchKeysWithMeta<'a> {
-    pub fn new(manager: redis::aio::ConnectionManager) -> Self {
+    pub fn new(manager: redis::aio::ConnectionManager, fields: ObjectFields) -> Self {
         Self {
             manager,
             cursor: None,
             pattern: None,
             size: None,
+            fields,
         }
     }
 
+    #[allow(dead_code)]
     pub fn size(mut self, size: Option<usize>) -> Self {
         self.size = size;
         self
@@ -38,6 +47,7 @@ impl<'a> FetchKeysWithMeta<'a> {
         self
     }
 
+    #[allow(clippy::unnecessary_lazy_evaluations)]
     pub async fn execute(mut self) -> Result<KeysList, Box<dyn std::error::Error + Sync + Send>> {
         let cursor = self.cursor.unwrap_or_default();
         let pattern = self.pattern.unwrap_or_else(|| "*");
@@ -52,26 +62,212 @@ impl<'a> FetchKeysWithMeta<'a> {
             return Ok(KeysList::Empty);
         }
 
-        let keys = join_all(keys.iter().map(|key| fetch_meta(self.manager.clone(), key)))
-            .await
-            .into_iter()
-            .collect::<Result<_, _>>()?;
+        let keys = join_all(
+            keys.iter()
+                .map(|key| fetch_meta(self.manager.clone(), key, self.fields)),
+        )
+        .await
+        .into_iter()
+        .collect::<Result<_, _>>()?;
 
         Ok(KeysList::Keys { cursor, keys })
     }
 }
 
+/// A connection of its own holding `WATCH`ed keys until the `EXEC` sent on it. The shared
+/// connection would lose them to any other `MULTI`/`EXEC` or reconnect, dropping this one
+/// unwatches them.
+pub struct Watch {
+    connection: MultiplexedConnection,
+}
+
 #[derive(Clone)]
 pub struct Storage {
+    client: redis::Client,
     manager: ConnectionManager,
+    guard: CommandGuard,
+    undo: UndoStack,
+    object_fields: ObjectFields,
 }
 
 impl Storage {
-    pub fn new(manager: ConnectionManager) -> Self {
-        Self { manager }
+    pub fn new(client: redis::Client, manager: ConnectionManager, undo: UndoStack) -> Self {
+        Self {
+            client,
+            manager,
+            undo,
+            guard: CommandGuard::default(),
+            object_fields: ObjectFields::default(),
+        }
+    }
+
+    #[must_use]
+    pub fn read_only(mut self, read_only: bool) -> Self {
+        self.guard = CommandGuard::new(read_only);
+        self
+    }
+
+    /// The `OBJECT` details fetched along with every key.
+    #[must_use]
+    pub fn object_fields(mut self, fields: ObjectFields) -> Self {
+        self.object_fields = fields;
+        self
+    }
+
+    pub fn is_read_only(&self) -> bool {
+        self.guard.is_read_only()
+    }
+
+    /// Sends an arbitrary command, every mutation issued by the app must go through here
+    /// so that the read-only guard sees it before the server does and the touched keys
+    /// are snapshotted onto the undo stack.
+    pub async fn query<T: FromRedisValue>(
+        &self,
+        cmd: &Cmd,
+    ) -> Result<T, Box<dyn std::error::Error + Sync + Send>> {
+        let mut manager = self.manager.clone();
+        let mutating = self.guard.check(&mut manager, cmd).await?;
+
+        let snapshot = if mutating {
+            let keys = undo::command_keys(&mut manager, cmd).await;
+            Some(undo::snapshot(&mut manager, display_command(cmd), keys).await?)
+        } else {
+            None
+        };
+
+        let result = cmd.query_async(&mut manager).await?;
+
+        if let Some(entry) = snapshot.filter(|entry| !entry.keys.is_empty()) {
+            self.undo.push(entry);
+        }
+
+        Ok(result)
+    }
+
+    /// Runs the commands in a single `MULTI`/`EXEC`, on the connection of `watch` when given,
+    /// returns `None` when `EXEC` was aborted because a watched key changed. The touched keys
+    /// are snapshotted together so that the whole transaction is undone at once.
+    pub async fn transaction(
+        &self,
+        cmds: &[Cmd],
+        watch: Option<Watch>,
+    ) -> Result<Option<Vec<Value>>, Box<dyn std::error::Error + Sync + Send>> {
+        let mut manager = self.manager.clone();
+
+        let mut keys = Vec::new();
+        for cmd in cmds {
+            if self.guard.check(&mut manager, cmd).await? {
+                for key in undo::command_keys(&mut manager, cmd).await {
+                    if !keys.contains(&key) {
+                        keys.push(key);
+                    }
+                }
+            }
+        }
+
+        let command = format!("MULTI ({} commands)", cmds.len());
+        let snapshot = undo::snapshot(&mut manager, command, keys).await?;
+
+        let mut pipe = redis::pipe();
+        pipe.atomic();
+        for cmd in cmds {
+            pipe.add_command(cmd.clone());
+        }
+
+        // Not `query_async`, it turns the first failed command into an error for the whole
+        // transaction while the other commands were applied
+        let mut replies = match watch {
+            Some(mut watch) => {
+                watch
+                    .connection
+                    .req_packed_commands(&pipe, cmds.len() + 1, 1)
+                    .await?
+            }
+            None => {
+                manager
+                    .req_packed_commands(&pipe, cmds.len() + 1, 1)
+                    .await?
+            }
+        };
+
+        match replies.pop() {
+            Some(Value::Array(replies)) => {
+                if !snapshot.keys.is_empty() {
+                    self.undo.push(snapshot);
+                }
+                Ok(Some(replies))
+            }
+            Some(Value::Nil) => Ok(None),
+            Some(Value::ServerError(err)) => Err(redis::RedisError::from(err).into()),
+            other => Err(format!("Unexpected EXEC reply: {other:?}").into()),
+        }
+    }
+
+    /// Watches the keys the commands are going to touch on the connection of `watch`, opened
+    /// on the first keys, so that the `EXEC` sent on it is aborted when somebody else changes
+    /// them first.
+    pub async fn watch(
+        &self,
+        watch: &mut Option<Watch>,
+        cmds: &[Cmd],
+    ) -> Result<(), Box<dyn std::error::Error + Sync + Send>> {
+        let mut manager = self.manager.clone();
+
+        let mut keys = Vec::new();
+        for cmd in cmds {
+            keys.extend(undo::command_keys(&mut manager, cmd).await);
+        }
+
+        if keys.is_empty() {
+            return Ok(());
+        }
+
+        let watch = match watch {
+            Some(watch) => watch,
+            None => watch.insert(Watch {
+                connection: self.client.get_multiplexed_async_connection().await?,
+            }),
+        };
+        redis::cmd("WATCH")
+            .arg(keys)
+            .query_async::<()>(&mut watch.connection)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Reverts the last `count` changes, most recent first, and returns the reverted entries.
+    pub async fn undo(
+        &self,
+        count: usize,
+    ) -> Result<Vec<UndoEntry>, Box<dyn std::error::Error + Sync + Send>> {
+        let mut manager = self.manager.clone();
+        let mut reverted = Vec::with_capacity(count);
+
+        for _ in 0..count {
+            let Some(entry) = self.undo.pop() else {
+                break;
+            };
+
+            if let Err(err) = undo::restore(&mut manager, &entry).await {
+                self.undo.push(entry);
+                return Err(err.into());
+            }
+
+            reverted.push(entry);
+        }
+
+        Ok(reverted)
+    }
+
+    pub async fn fetch_meta(
+        &self,
+        key: &str,
+    ) -> Result<KeyMeta, Box<dyn std::error::Error + Sync + Send>> {
+        fetch_meta(self.manager.clone(), key, self.object_fields).await
     }
 
     pub fn fetch_keys_with_meta(&self) -> FetchKeysWithMeta {
-        FetchKeysWithMeta::new(self.manager.clone())
+        FetchKeysWithMeta::new(self.manager.clone(), self.object_fields)
     }
 }
@@ -1,72 +1,39 @@
 use std::collections::{HashMap, VecDeque};
 use std::{borrow::Cow, collections::HashSet};
 
+use super::reply::Reply;
 use crate::config;
 use ratatui::{
-    style::Stylize,
+    style::{Color, Stylize},
     text::{Span, Text},
 };
-use serde::Deserialize;
-
-#[derive(Debug, Deserialize, Clone)]
-pub struct CommonInfo {
-    pub os: String,
-    #[serde(rename(deserialize = "redis_version"))]
-    pub version: String,
-    #[serde(rename(deserialize = "used_memory_human"))]
-    pub memory: String,
-    #[serde(rename(deserialize = "total_system_memory_human"))]
-    pub total_memory: String,
-    #[serde(rename(deserialize = "used_cpu_sys"))]
-    pub cpu: String,
-    #[serde(rename(deserialize = "connected_clients"))]
-    pub clients: String,
-    #[serde(rename(deserialize = "maxclients"))]
-    pub max_clients: String,
-}
-
-#[derive(Debug, Deserialize, Clone)]
-pub struct RedisInfo {
-    #[serde(flatten)]
-    pub common: CommonInfo,
-}
-
-impl RedisInfo {
-    pub fn redis_version(&self) -> String {
-        format!("Redis version: {}", self.common.version)
-    }
 
-    pub fn os(&self) -> String {
-        format!("OS: {}", self.common.os)
-    }
-
-    pub fn cpu(&self) -> String {
-        format!("CPU: {}", self.common.cpu)
-    }
-
-    pub fn memory(&self) -> String {
-        format!("RAM: {}/{}", self.common.memory, self.common.total_memory)
-    }
-
-    pub fn clients(&self) -> String {
-        format!(
-            "Connected clients: {}/{}",
-            self.common.clients, self.common.max_clients
-        )
-    }
-}
-
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum KeyValue {
     String(String),
     List(Vec<String>),
     Set(HashSet<String>),
     Hash(HashMap<String, String>),
     Zset(Vec<(String, f64)>), // Tuple of value and score
+    #[allow(dead_code)]
     Json(serde_json::Value),
     Unknown,
 }
 
+impl KeyValue {
+    /// `STRLEN` for strings, the number of elements for collections.
+    pub fn len(&self) -> Option<usize> {
+        match self {
+            Self::String(value) => Some(value.len()),
+            Self::List(value) => Some(value.len()),
+            Self::Set(value) => Some(value.len()),
+            Self::Hash(value) => Some(value.len()),
+            Self::Zset(value) => Some(value.len()),
+            Self::Json(_) | Self::Unknown => None,
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct KeyMeta {
     pub key: String,
@@ -74,6 +41,33 @@ pub struct KeyMeta {
     pub size: u128,
     pub ttl: isize,
     pub value: KeyValue,
+    /// Internal representation from `OBJECT ENCODING`, e.g. `listpack` or `hashtable`.
+    pub encoding: Option<String>,
+    /// Seconds since the last access, not tracked under an LFU eviction policy.
+    pub idle: Option<u64>,
+    /// Logarithmic access counter, only tracked under an LFU eviction policy.
+    pub freq: Option<u64>,
+}
+
+/// A key fetched again after a change, `None` when it is gone.
+pub type RefreshedKey = (String, Option<KeyMeta>);
+
+/// A command sent from the console together with the reply it produced.
+#[derive(Debug, Clone)]
+pub struct ConsoleEntry {
+    pub command: String,
+    pub reply: Reply,
+}
+
+/// What came back from submitting a queued transaction.
+#[derive(Debug, Clone)]
+pub enum TransactionOutcome {
+    /// `EXEC` ran, one entry per queued command.
+    Executed(Vec<ConsoleEntry>),
+    /// A watched key changed in the meantime and `EXEC` returned nil.
+    Aborted,
+    /// The transaction was rejected as a whole, e.g. by the read-only guard or `EXECABORT`.
+    Failed(String),
 }
 
 pub enum KeysList {
@@ -93,6 +87,7 @@ pub enum RedisType {
 }
 
 impl RedisType {
+    #[allow(clippy::needless_lifetimes)]
     fn from_str<'a>(value: Cow<'a, str>) -> Self {
         match value.as_ref() {
             "string" => Self::String,
@@ -104,21 +99,48 @@ impl RedisType {
             _ => Self::Unknown,
         }
     }
+
+    /// The name `TYPE` replies with, `unknown` for types the app does not read.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::String => "string",
+            Self::Hash => "hash",
+            Self::Set => "set",
+            Self::Zset => "zset",
+            Self::Json => "json",
+            Self::List => "list",
+            Self::Unknown => "unknown",
+        }
+    }
+
+    pub fn color(&self) -> Color {
+        let palette = &config::get().keyspace;
+        match self {
+            Self::String => palette.string,
+            Self::Json => palette.json,
+            Self::List => palette.list,
+            Self::Set => palette.set,
+            Self::Zset => palette.zset,
+            Self::Hash => palette.hash,
+            Self::Unknown => palette.unknown,
+        }
+    }
 }
 
+#[allow(clippy::from_over_into)]
 impl<'a> Into<Text<'a>> for RedisType {
     fn into(self) -> Text<'a> {
-        match self {
-            Self::String => Span::raw(" STRING ")
-                .bg(config::get().keyspace.string)
-                .into(),
-            Self::Json => Span::raw(" JSON ").bg(config::get().keyspace.json).into(),
-            Self::List => Span::raw(" LIST ").bg(config::get().keyspace.list).into(),
-            Self::Set => Span::raw(" SET ").bg(config::get().keyspace.set).into(),
-            Self::Zset => Span::raw(" ZSET ").bg(config::get().keyspace.zset).into(),
-            Self::Hash => Span::raw(" HASH ").bg(config::get().keyspace.hash).into(),
-            Self::Unknown => Span::raw(" ? ").bg(config::get().keyspace.unknown).into(),
-        }
+        let label = match self {
+            Self::String => " STRING ",
+            Self::Json => " JSON ",
+            Self::List => " LIST ",
+            Self::Set => " SET ",
+            Self::Zset => " ZSET ",
+            Self::Hash => " HASH ",
+            Self::Unknown => " ? ",
+        };
+
+        Span::raw(label).bg(self.color()).into()
     }
 }
 
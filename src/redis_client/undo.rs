@@ -0,0 +1,164 @@
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use redis::{aio::ConnectionManager, Cmd, RedisResult};
+
+/// State of a key right before a command changed it.
+#[derive(Debug, Clone)]
+pub struct KeySnapshot {
+    pub key: String,
+    /// Serialized value from `DUMP`, `None` when the key did not exist yet.
+    pub dump: Option<Vec<u8>>,
+    /// Absolute expiration in unix milliseconds, so that restoring later keeps the original deadline.
+    pub expire_at: Option<u64>,
+}
+
+#[derive(Debug, Clone)]
+pub struct UndoEntry {
+    pub command: String,
+    pub at: SystemTime,
+    pub keys: Vec<KeySnapshot>,
+}
+
+/// Bounded stack of snapshots taken before every mutation sent through the `Storage`.
+#[derive(Debug, Clone)]
+pub struct UndoStack {
+    entries: Arc<Mutex<VecDeque<UndoEntry>>>,
+    limit: usize,
+}
+
+impl UndoStack {
+    pub fn new(limit: usize) -> Self {
+        Self {
+            entries: Arc::default(),
+            limit,
+        }
+    }
+
+    pub fn push(&self, entry: UndoEntry) {
+        let mut entries = self.entries.lock().unwrap();
+        entries.push_back(entry);
+        while entries.len() > self.limit {
+            entries.pop_front();
+        }
+    }
+
+    pub fn pop(&self) -> Option<UndoEntry> {
+        self.entries.lock().unwrap().pop_back()
+    }
+
+    /// Most recent change first.
+    pub fn entries(&self) -> Vec<UndoEntry> {
+        self.entries.lock().unwrap().iter().rev().cloned().collect()
+    }
+}
+
+/// Keys the command is going to touch, as resolved by the server with `COMMAND GETKEYS`.
+pub async fn command_keys(manager: &mut ConnectionManager, cmd: &Cmd) -> Vec<String> {
+    let mut getkeys = redis::cmd("COMMAND");
+    getkeys.arg("GETKEYS");
+    for arg in cmd.args_iter() {
+        if let redis::Arg::Simple(bytes) = arg {
+            getkeys.arg(bytes);
+        }
+    }
+
+    // Commands without key arguments (FLUSHALL, CLIENT KILL, ...) are answered with an error
+    getkeys.query_async(manager).await.unwrap_or_default()
+}
+
+pub async fn snapshot(
+    manager: &mut ConnectionManager,
+    command: String,
+    keys: Vec<String>,
+) -> RedisResult<UndoEntry> {
+    let mut snapshots = Vec::with_capacity(keys.len());
+
+    for key in keys {
+        let (dump, pttl): (Option<Vec<u8>>, i64) = redis::pipe()
+            .cmd("DUMP")
+            .arg(&key)
+            .cmd("PTTL")
+            .arg(&key)
+            .query_async(manager)
+            .await?;
+
+        let expire_at = (pttl > 0).then(|| unix_millis(SystemTime::now()) + pttl as u64);
+
+        snapshots.push(KeySnapshot {
+            key,
+            dump,
+            expire_at,
+        });
+    }
+
+    Ok(UndoEntry {
+        command,
+        at: SystemTime::now(),
+        keys: snapshots,
+    })
+}
+
+/// Puts every key of the entry back with `RESTORE ... REPLACE` in a single transaction,
+/// keys that did not exist before the change are deleted.
+pub async fn restore(manager: &mut ConnectionManager, entry: &UndoEntry) -> RedisResult<()> {
+    let mut pipe = redis::pipe();
+    pipe.atomic();
+
+    for snapshot in &entry.keys {
+        match snapshot.dump {
+            Some(ref dump) => {
+                pipe.cmd("RESTORE")
+                    .arg(&snapshot.key)
+                    .arg(snapshot.expire_at.unwrap_or_default())
+                    .arg(dump)
+                    .arg("REPLACE");
+                if snapshot.expire_at.is_some() {
+                    pipe.arg("ABSTTL");
+                }
+            }
+            None => {
+                pipe.cmd("DEL").arg(&snapshot.key);
+            }
+        }
+        pipe.ignore();
+    }
+
+    pipe.query_async(manager).await
+}
+
+fn unix_millis(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn entry(command: &str) -> UndoEntry {
+        UndoEntry {
+            command: command.into(),
+            at: SystemTime::now(),
+            keys: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_stack_is_bounded() {
+        let stack = UndoStack::new(2);
+        stack.push(entry("DEL a"));
+        stack.push(entry("DEL b"));
+        stack.push(entry("DEL c"));
+
+        let commands: Vec<String> = stack.entries().into_iter().map(|e| e.command).collect();
+        assert_eq!(commands, ["DEL c", "DEL b"]);
+        assert_eq!(stack.pop().map(|e| e.command), Some("DEL c".into()));
+    }
+}
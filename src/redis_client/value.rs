@@ -0,0 +1,53 @@
+use redis::Value;
+
+pub fn as_string(value: &Value) -> Option<String> {
+    match value {
+        Value::BulkString(bytes) => Some(String::from_utf8_lossy(bytes).into_owned()),
+        Value::SimpleString(s) => Some(s.clone()),
+        Value::VerbatimString { text, .. } => Some(text.clone()),
+        Value::Okay => Some("OK".into()),
+        Value::Int(i) => Some(i.to_string()),
+        _ => None,
+    }
+}
+
+/// An integer, or a number sent as a string.
+pub fn as_u64(value: &Value) -> Option<u64> {
+    match value {
+        Value::Int(n) => u64::try_from(*n).ok(),
+        value => as_string(value)?.parse().ok(),
+    }
+}
+
+pub fn strings(value: &Value) -> Vec<String> {
+    match value {
+        Value::Array(items) | Value::Set(items) => items.iter().filter_map(as_string).collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Treats a RESP3 map or a RESP2 flat `[key, value, ...]` array as a list of pairs.
+pub fn pairs(value: &Value) -> Vec<(String, &Value)> {
+    match value {
+        Value::Map(map) => map
+            .iter()
+            .filter_map(|(k, v)| as_string(k).map(|k| (k, v)))
+            .collect(),
+        Value::Array(items) => items
+            .chunks_exact(2)
+            .filter_map(|pair| as_string(&pair[0]).map(|k| (k, &pair[1])))
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Fixtures shared by the tests of the reply parsers.
+#[cfg(test)]
+pub(crate) mod test_support {
+    use redis::Value;
+
+    /// A bulk string, for building replies.
+    pub fn bulk(text: &str) -> Value {
+        Value::BulkString(text.as_bytes().to_vec())
+    }
+}
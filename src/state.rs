@@ -1,20 +1,87 @@
-use std::sync::{Arc, Mutex};
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex},
+};
 
-use crate::redis_client::types::{KeyMeta, KeyspaceState, RedisInfo};
+use crate::{
+    config,
+    redis_client::{
+        clients::ClientList,
+        docs::CommandDoc,
+        hotkeys::HotKeysReport,
+        info::RedisInfo,
+        latency::{LatencyReport, PingStats},
+        memory::MemoryAnalysis,
+        monitor::MonitorEntry,
+        pubsub::{ActiveChannels, PubSubMessage},
+        samples::Samples,
+        scripts::RedisFunction,
+        slowlog::SlowlogEntry,
+        types::{ConsoleEntry, KeyMeta, KeyspaceState, RefreshedKey, TransactionOutcome},
+        undo::UndoStack,
+    },
+};
 
 #[derive(Clone, Debug)]
 pub struct SharedState {
     pub info: Arc<Mutex<Option<RedisInfo>>>,
+    /// Recent `INFO` polls, for the charts of the dashboard.
+    pub samples: Arc<Mutex<Samples>>,
+    /// Round trips of the `PING` sent along with each `INFO` poll.
+    pub ping: Arc<Mutex<PingStats>>,
     pub keys: Arc<Mutex<Vec<KeyMeta>>>,
     pub keyspace_state: Arc<Mutex<KeyspaceState>>,
+    pub console: Arc<Mutex<Vec<ConsoleEntry>>>,
+    pub command_docs: Arc<Mutex<Vec<CommandDoc>>>,
+    pub undo: UndoStack,
+    pub functions: Arc<Mutex<Vec<RedisFunction>>>,
+    pub script_result: Arc<Mutex<Option<ConsoleEntry>>>,
+    pub transaction: Arc<Mutex<Option<TransactionOutcome>>>,
+    /// A key fetched on its own, to be shown whether it is on the loaded page or not.
+    pub fetched_key: Arc<Mutex<Option<KeyMeta>>>,
+    pub clients: Arc<Mutex<Option<ClientList>>>,
+    pub slowlog: Arc<Mutex<Option<Vec<SlowlogEntry>>>>,
+    pub latency: Arc<Mutex<Option<LatencyReport>>>,
+    /// The latest progress of the memory analysis.
+    pub memory: Arc<Mutex<Option<MemoryAnalysis>>>,
+    /// The latest ranking of the hot keys sampling.
+    pub hot_keys: Arc<Mutex<Option<HotKeysReport>>>,
+    /// Entries streamed by `MONITOR` that the monitor pane has not taken yet.
+    pub monitor: Arc<Mutex<VecDeque<MonitorEntry>>>,
+    /// Messages received on the Pub/Sub connection that the pane has not taken yet.
+    pub pubsub: Arc<Mutex<VecDeque<PubSubMessage>>>,
+    pub channels: Arc<Mutex<Option<ActiveChannels>>>,
+    /// Keys reported changed by keyspace notifications, not refreshed yet.
+    pub changed_keys: Arc<Mutex<Vec<String>>>,
+    /// The changed keys fetched again, `None` for the ones that are gone.
+    pub refreshed_keys: Arc<Mutex<Vec<RefreshedKey>>>,
 }
 
 impl Default for SharedState {
     fn default() -> Self {
         Self {
             info: Arc::new(Mutex::new(None)),
+            samples: Arc::new(Mutex::new(Samples::default())),
+            ping: Arc::new(Mutex::new(PingStats::default())),
             keys: Arc::new(Mutex::new(Vec::new())),
             keyspace_state: Arc::new(Mutex::new(KeyspaceState::default())),
+            console: Arc::new(Mutex::new(Vec::new())),
+            command_docs: Arc::new(Mutex::new(Vec::new())),
+            undo: UndoStack::new(config::get().undo_limit),
+            functions: Arc::new(Mutex::new(Vec::new())),
+            script_result: Arc::new(Mutex::new(None)),
+            transaction: Arc::new(Mutex::new(None)),
+            fetched_key: Arc::new(Mutex::new(None)),
+            clients: Arc::new(Mutex::new(None)),
+            slowlog: Arc::new(Mutex::new(None)),
+            latency: Arc::new(Mutex::new(None)),
+            memory: Arc::new(Mutex::new(None)),
+            hot_keys: Arc::new(Mutex::new(None)),
+            monitor: Arc::new(Mutex::new(VecDeque::new())),
+            pubsub: Arc::new(Mutex::new(VecDeque::new())),
+            channels: Arc::new(Mutex::new(None)),
+            changed_keys: Arc::new(Mutex::new(Vec::new())),
+            refreshed_keys: Arc::new(Mutex::new(Vec::new())),
         }
     }
 }
@@ -49,6 +49,8 @@ pub struct Tui {
     pub terminal: ratatui::Terminal<Backend<IO>>,
     pub event_task: JoinHandle<()>,
     pub cancellation_token: CancellationToken,
+    /// Stops the event task only, so that the TUI can be left and entered again.
+    event_cancellation: CancellationToken,
     pub event_rx: UnboundedReceiver<Event>,
     pub event_tx: UnboundedSender<Event>,
     pub frame_rate: f64,
@@ -72,6 +74,7 @@ impl Tui {
             terminal,
             event_task,
             cancellation_token,
+            event_cancellation: CancellationToken::new(),
             event_rx,
             event_tx,
             frame_rate,
@@ -101,6 +104,7 @@ impl Tui {
         self
     }
 
+    #[allow(dead_code)]
     pub fn paste(mut self, paste: bool) -> Self {
         self.paste = paste;
         self
@@ -110,7 +114,8 @@ impl Tui {
         let tick_delay = std::time::Duration::from_secs_f64(1.0 / self.tick_rate);
         let render_delay = std::time::Duration::from_secs_f64(1.0 / self.frame_rate);
 
-        let tui_cancelation_token = self.cancellation_token.clone();
+        self.event_cancellation = self.cancellation_token.child_token();
+        let tui_cancelation_token = self.event_cancellation.clone();
 
         let event_tx = self.event_tx.clone();
 
@@ -173,7 +178,7 @@ impl Tui {
     }
 
     pub fn stop(&self) -> Result<()> {
-        self.cancel();
+        self.event_cancellation.cancel();
         let mut counter = 0;
         while !self.event_task.is_finished() {
             std::thread::sleep(Duration::from_millis(1));
@@ -1,2 +1,23 @@
+pub mod bookmarks;
+pub mod charts;
+pub mod clients;
+pub mod columns;
+pub mod console;
+pub mod dashboard;
+pub mod details;
+pub mod filter;
+pub mod highlight;
+pub mod hotkeys;
 pub mod info;
+pub mod inspector;
 pub mod keyspace;
+pub mod latency;
+pub mod memory;
+pub mod monitor;
+pub mod popup;
+pub mod pubsub;
+pub mod scripts;
+pub mod slowlog;
+pub mod transaction;
+pub mod tree;
+pub mod undo;
@@ -0,0 +1,312 @@
+use std::path::PathBuf;
+
+use ratatui::{
+    layout::{Constraint, Flex, Layout, Rect},
+    style::Stylize,
+    widgets::{
+        Block, BorderType, Borders, Cell, Clear, HighlightSpacing, Row, StatefulWidget, Table,
+        TableState, Widget,
+    },
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{config, utils};
+
+const HIGHLIGHT_SYMBOL: &str = " >> ";
+const BOOKMARKS_DIR: &str = "bookmarks";
+const MAX_RECENT: usize = 20;
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Bookmark {
+    Key(String),
+    /// A `SCAN MATCH` pattern the key space is narrowed to when jumping to it.
+    Pattern(String),
+}
+
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+struct Saved {
+    #[serde(default)]
+    bookmarks: Vec<Bookmark>,
+    /// Most recently viewed first.
+    #[serde(default)]
+    recent: Vec<String>,
+}
+
+/// Bookmarked keys and patterns of a connection profile, with the keys viewed last.
+pub struct Bookmarks {
+    profile: String,
+    saved: Saved,
+    table: TableState,
+}
+
+impl Bookmarks {
+    /// Loads what was saved for the profile, starting empty when nothing was.
+    pub fn load(profile: &str) -> Self {
+        let saved = std::fs::read_to_string(path(profile))
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+
+        Self {
+            profile: profile.into(),
+            saved,
+            table: TableState::default(),
+        }
+    }
+
+    pub fn save(&self) -> std::io::Result<()> {
+        let path = path(&self.profile);
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        std::fs::write(path, serde_json::to_string_pretty(&self.saved)?)
+    }
+
+    /// Adds the bookmark, or removes it when it is there already. Returns whether it was added.
+    pub fn toggle(&mut self, bookmark: Bookmark) -> bool {
+        let len = self.saved.bookmarks.len();
+        self.saved.bookmarks.retain(|saved| *saved != bookmark);
+        if self.saved.bookmarks.len() < len {
+            return false;
+        }
+
+        self.saved.bookmarks.push(bookmark);
+        true
+    }
+
+    /// Moves the key to the top of the recently viewed ones.
+    pub fn visit(&mut self, key: &str) {
+        self.saved.recent.retain(|recent| recent != key);
+        self.saved.recent.insert(0, key.into());
+        self.saved.recent.truncate(MAX_RECENT);
+    }
+
+    /// Bookmarked and recently viewed keys, without duplicates.
+    pub fn keys(&self) -> Vec<String> {
+        let mut keys: Vec<String> = self
+            .saved
+            .bookmarks
+            .iter()
+            .filter_map(|bookmark| match bookmark {
+                Bookmark::Key(key) => Some(key.clone()),
+                Bookmark::Pattern(_) => None,
+            })
+            .collect();
+        for key in &self.saved.recent {
+            if !keys.contains(key) {
+                keys.push(key.clone());
+            }
+        }
+        keys
+    }
+
+    /// Rows of the popup: the bookmarks, then the recently viewed keys.
+    fn entries(&self) -> impl Iterator<Item = (&'static str, &String)> {
+        let bookmarks = self.saved.bookmarks.iter().map(|bookmark| match bookmark {
+            Bookmark::Key(key) => ("key", key),
+            Bookmark::Pattern(pattern) => ("pattern", pattern),
+        });
+        let recent = self.saved.recent.iter().map(|key| ("recent", key));
+
+        bookmarks.chain(recent)
+    }
+
+    fn len(&self) -> usize {
+        self.saved.bookmarks.len() + self.saved.recent.len()
+    }
+
+    /// What to jump to, recently viewed keys jump like bookmarked ones.
+    pub fn selected(&self) -> Option<Bookmark> {
+        let i = self.table.selected()?;
+        match self.saved.bookmarks.get(i) {
+            Some(bookmark) => Some(bookmark.clone()),
+            None => self
+                .saved
+                .recent
+                .get(i - self.saved.bookmarks.len())
+                .cloned()
+                .map(Bookmark::Key),
+        }
+    }
+
+    /// Removes the selected bookmark, or the selected key from the recently viewed ones.
+    pub fn remove_selected(&mut self) {
+        let Some(i) = self.table.selected() else {
+            return;
+        };
+
+        let bookmarks = self.saved.bookmarks.len();
+        if i < bookmarks {
+            self.saved.bookmarks.remove(i);
+        } else if i - bookmarks < self.saved.recent.len() {
+            self.saved.recent.remove(i - bookmarks);
+        }
+        self.clamp_selection();
+    }
+
+    /// Selects the first row when the popup opens.
+    pub fn reset_selection(&mut self) {
+        self.table = TableState::default();
+        self.clamp_selection();
+    }
+
+    fn clamp_selection(&mut self) {
+        let len = self.len();
+        let selected = match self.table.selected() {
+            _ if len == 0 => None,
+            Some(i) => Some(i.min(len - 1)),
+            None => Some(0),
+        };
+        self.table.select(selected);
+    }
+
+    pub fn scroll_next(&mut self) {
+        let len = self.len();
+        if len == 0 {
+            return;
+        }
+        let next = self.table.selected().map_or(0, |i| (i + 1) % len);
+        self.table.select(Some(next));
+    }
+
+    pub fn scroll_previous(&mut self) {
+        let len = self.len();
+        if len == 0 {
+            return;
+        }
+        let last = len - 1;
+        let previous = self.table.selected().map_or(last, |i| (i + last) % len);
+        self.table.select(Some(previous));
+    }
+}
+
+fn path(profile: &str) -> PathBuf {
+    let name: String = profile
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    utils::get_data_dir()
+        .join(BOOKMARKS_DIR)
+        .join(format!("{name}.json"))
+}
+
+pub struct BookmarksWidget;
+
+impl StatefulWidget for BookmarksWidget {
+    type State = Bookmarks;
+
+    fn render(self, area: Rect, buf: &mut ratatui::prelude::Buffer, state: &mut Self::State) {
+        let colors = config::get().colors;
+
+        let [popup_area] = Layout::horizontal([Constraint::Percentage(60)])
+            .flex(Flex::Center)
+            .areas(area);
+        let [popup_area] = Layout::vertical([Constraint::Percentage(60)])
+            .flex(Flex::Center)
+            .areas(popup_area);
+
+        let block = Block::new()
+            .bg(colors.base00)
+            .fg(colors.base04)
+            .border_type(BorderType::Rounded)
+            .borders(Borders::ALL)
+            .title(format!(
+                "Bookmarks of {} (<Enter> jumps, <d> removes)",
+                state.profile
+            ));
+
+        let table_area = block.inner(popup_area);
+        Clear.render(popup_area, buf);
+        block.render(popup_area, buf);
+
+        let header = Row::new(["Kind", "Name"].map(|h| Cell::from(h.bold())))
+            .bottom_margin(1)
+            .fg(colors.base04)
+            .bg(colors.base02);
+
+        let rows = state.entries().enumerate().map(|(idx, (kind, name))| {
+            Row::new([Cell::from(kind), Cell::from(name.clone())])
+                .fg(if kind == "recent" {
+                    colors.base03
+                } else {
+                    colors.base04
+                })
+                .bg(if idx % 2 == 0 {
+                    colors.base00
+                } else {
+                    colors.base01
+                })
+        });
+
+        let table = Table::new(rows, [Constraint::Length(8), Constraint::Fill(1)])
+            .header(header)
+            .highlight_symbol(HIGHLIGHT_SYMBOL)
+            .highlight_style(colors.base05)
+            .highlight_spacing(HighlightSpacing::Always);
+
+        StatefulWidget::render(table, table_area, buf, &mut state.table);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn bookmarks() -> Bookmarks {
+        Bookmarks {
+            profile: "test".into(),
+            saved: Saved::default(),
+            table: TableState::default(),
+        }
+    }
+
+    #[test]
+    fn test_toggle_and_visit() {
+        let mut bookmarks = bookmarks();
+
+        assert!(bookmarks.toggle(Bookmark::Key("a".into())));
+        assert!(bookmarks.toggle(Bookmark::Pattern("user:*".into())));
+        assert!(!bookmarks.toggle(Bookmark::Key("a".into())));
+        assert_eq!(
+            bookmarks.saved.bookmarks,
+            [Bookmark::Pattern("user:*".into())]
+        );
+
+        bookmarks.visit("x");
+        bookmarks.visit("y");
+        bookmarks.visit("x");
+        assert_eq!(bookmarks.saved.recent, ["x", "y"]);
+
+        bookmarks.reset_selection();
+        bookmarks.scroll_next();
+        assert_eq!(bookmarks.selected(), Some(Bookmark::Key("x".into())));
+
+        bookmarks.remove_selected();
+        assert_eq!(bookmarks.saved.recent, ["y"]);
+        assert_eq!(bookmarks.selected(), Some(Bookmark::Key("y".into())));
+    }
+
+    #[test]
+    fn test_saved_format() {
+        let saved = Saved {
+            bookmarks: vec![Bookmark::Key("a".into()), Bookmark::Pattern("b*".into())],
+            recent: vec!["c".into()],
+        };
+
+        let json = serde_json::to_value(&saved).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({ "bookmarks": [{ "key": "a" }, { "pattern": "b*" }], "recent": ["c"] })
+        );
+        assert_eq!(serde_json::from_value::<Saved>(json).unwrap(), saved);
+    }
+}
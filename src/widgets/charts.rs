@@ -0,0 +1,251 @@
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Style, Stylize},
+    symbols::Marker,
+    widgets::{Axis, Block, BorderType, Borders, Chart, Dataset, GraphType, Sparkline, Widget},
+};
+
+use crate::{
+    config,
+    redis_client::samples::{Sample, Samples},
+};
+
+const MIB: f64 = 1024.0 * 1024.0;
+/// Shortest time span shown, so that the first samples do not fill the whole width.
+const MIN_WINDOW: f64 = 60.0;
+
+/// One line of a chart, with its points as (seconds before now, value).
+pub struct Series {
+    name: &'static str,
+    color: Color,
+    points: Vec<(f64, f64)>,
+}
+
+impl Series {
+    pub fn new(name: &'static str, color: Color, points: Vec<(f64, f64)>) -> Self {
+        Self {
+            name,
+            color,
+            points,
+        }
+    }
+}
+
+fn series(
+    name: &'static str,
+    color: Color,
+    samples: &Samples,
+    value: impl Fn(&Sample) -> Option<f64>,
+) -> Series {
+    let Some(now) = samples.latest().map(|sample| sample.at) else {
+        return Series {
+            name,
+            color,
+            points: Vec::new(),
+        };
+    };
+
+    let points = samples
+        .iter()
+        .filter_map(|sample| {
+            let ago = now.duration_since(sample.at).as_secs_f64();
+            value(sample).map(|value| (-ago, value))
+        })
+        .collect();
+
+    Series {
+        name,
+        color,
+        points,
+    }
+}
+
+fn panel(title: String) -> Block<'static> {
+    Block::new()
+        .border_style(config::get().colors.base03)
+        .border_type(BorderType::Rounded)
+        .borders(Borders::ALL)
+        .title(title.fg(config::get().colors.base0d))
+}
+
+/// Draws the lines over the time they span, at least a minute, up to `max` or a bit over the
+/// highest value.
+pub fn render_chart(
+    title: String,
+    lines: &[Series],
+    unit: &str,
+    max: Option<f64>,
+    area: Rect,
+    buf: &mut ratatui::prelude::Buffer,
+) {
+    let colors = config::get().colors;
+
+    let window = lines
+        .iter()
+        .flat_map(|line| line.points.first())
+        .map(|(ago, _)| -ago)
+        .fold(MIN_WINDOW, f64::max);
+    let top = max.unwrap_or_else(|| {
+        lines
+            .iter()
+            .flat_map(|line| &line.points)
+            .map(|(_, value)| *value)
+            .fold(1.0, f64::max)
+            * 1.1
+    });
+
+    let datasets = lines
+        .iter()
+        .map(|line| {
+            Dataset::default()
+                .name(line.name)
+                .marker(Marker::Braille)
+                .graph_type(GraphType::Line)
+                .style(Style::default().fg(line.color))
+                .data(&line.points)
+        })
+        .collect();
+
+    let axis_style = Style::default().fg(colors.base04);
+    Chart::new(datasets)
+        .block(panel(title))
+        .x_axis(
+            Axis::default()
+                .style(axis_style)
+                .bounds([-window, 0.0])
+                .labels([format!("-{window:.0}s"), "now".into()]),
+        )
+        .y_axis(
+            Axis::default()
+                .style(axis_style)
+                .bounds([0.0, top])
+                .labels(["0".into(), format!("{top:.1}{unit}")]),
+        )
+        .render(area, buf);
+}
+
+/// History of the `INFO` metrics polled since the app started.
+pub struct ChartsWidget<'a> {
+    samples: &'a Samples,
+}
+
+impl<'a> ChartsWidget<'a> {
+    pub fn new(samples: &'a Samples) -> Self {
+        Self { samples }
+    }
+}
+
+impl Widget for ChartsWidget<'_> {
+    fn render(self, area: Rect, buf: &mut ratatui::prelude::Buffer) {
+        let colors = config::get().colors;
+        let samples = self.samples;
+        let latest = samples.latest();
+
+        let [top, middle, bottom] = Layout::vertical([Constraint::Fill(1); 3]).areas(area);
+        let [ops_area, memory_area] = Layout::horizontal([Constraint::Fill(1); 2]).areas(top);
+        let [hits_area, keys_area] = Layout::horizontal([Constraint::Fill(1); 2]).areas(middle);
+        let [clients_area, net_area] = Layout::horizontal([Constraint::Fill(1); 2]).areas(bottom);
+
+        render_chart(
+            format!("Ops/sec: {}", latest.map_or(0, |s| s.ops_per_sec)),
+            &[series("ops/sec", colors.base0d, samples, |s| {
+                Some(s.ops_per_sec as f64)
+            })],
+            "",
+            None,
+            ops_area,
+            buf,
+        );
+
+        let max_memory = latest.map_or(0, |s| s.max_memory);
+        let mut memory = vec![series("used", colors.base0b, samples, |s| {
+            Some(s.used_memory as f64 / MIB)
+        })];
+        if max_memory > 0 {
+            memory.push(series("maxmemory", colors.base08, samples, |s| {
+                (s.max_memory > 0).then(|| s.max_memory as f64 / MIB)
+            }));
+        }
+        render_chart(
+            format!(
+                "Memory: {:.1} MiB of {}",
+                latest.map_or(0.0, |s| s.used_memory as f64 / MIB),
+                if max_memory > 0 {
+                    format!("{:.1} MiB", max_memory as f64 / MIB)
+                } else {
+                    "unlimited".into()
+                }
+            ),
+            &memory,
+            "M",
+            None,
+            memory_area,
+            buf,
+        );
+
+        render_chart(
+            format!(
+                "Hit ratio: {}",
+                latest
+                    .and_then(|s| s.hit_ratio)
+                    .map_or_else(|| "-".into(), |ratio| format!("{:.1}%", ratio * 100.0))
+            ),
+            &[series("hits", colors.base0a, samples, |s| {
+                s.hit_ratio.map(|ratio| ratio * 100.0)
+            })],
+            "%",
+            Some(100.0),
+            hits_area,
+            buf,
+        );
+
+        render_chart(
+            format!(
+                "Evicted/expired per sec: {:.1} / {:.1}",
+                latest.map_or(0.0, |s| s.evicted_per_sec),
+                latest.map_or(0.0, |s| s.expired_per_sec)
+            ),
+            &[
+                series("evicted", colors.base08, samples, |s| {
+                    Some(s.evicted_per_sec)
+                }),
+                series("expired", colors.base09, samples, |s| {
+                    Some(s.expired_per_sec)
+                }),
+            ],
+            "",
+            None,
+            keys_area,
+            buf,
+        );
+
+        let clients: Vec<u64> = samples.iter().map(|s| s.clients).collect();
+        // The newest samples on the right, as many as fit
+        let width = usize::from(clients_area.width.saturating_sub(2));
+        let clients = &clients[clients.len().saturating_sub(width)..];
+        Sparkline::default()
+            .block(panel(format!(
+                "Clients: {}",
+                latest.map_or(0, |s| s.clients)
+            )))
+            .style(Style::default().fg(colors.base0e))
+            .data(clients)
+            .render(clients_area, buf);
+
+        render_chart(
+            format!(
+                "Network in/out: {:.1} / {:.1} KB/s",
+                latest.map_or(0.0, |s| s.input_kbps),
+                latest.map_or(0.0, |s| s.output_kbps)
+            ),
+            &[
+                series("in", colors.base0c, samples, |s| Some(s.input_kbps)),
+                series("out", colors.base0e, samples, |s| Some(s.output_kbps)),
+            ],
+            "K",
+            None,
+            net_area,
+            buf,
+        );
+    }
+}
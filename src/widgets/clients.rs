@@ -0,0 +1,324 @@
+use std::cmp::Ordering;
+
+use byte_unit::{Byte, UnitType};
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    style::Stylize,
+    text::Span,
+    widgets::{
+        Block, BorderType, Borders, Cell, HighlightSpacing, Paragraph, Row, StatefulWidget, Table,
+        TableState, Widget,
+    },
+};
+
+use crate::{
+    config,
+    redis_client::clients::{ClientInfo, ClientList},
+    widgets::columns::SortOrder,
+};
+
+const HIGHLIGHT_SYMBOL: &str = " >> ";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ClientColumn {
+    Id,
+    Addr,
+    Name,
+    Db,
+    Age,
+    Idle,
+    Cmd,
+    Memory,
+    Flags,
+}
+
+const COLUMNS: [(ClientColumn, &str, Constraint); 9] = [
+    (ClientColumn::Id, "Id", Constraint::Length(8)),
+    (ClientColumn::Addr, "Addr", Constraint::Fill(2)),
+    (ClientColumn::Name, "Name", Constraint::Fill(2)),
+    (ClientColumn::Db, "Db", Constraint::Length(4)),
+    (ClientColumn::Age, "Age(s)", Constraint::Length(9)),
+    (ClientColumn::Idle, "Idle(s)", Constraint::Length(9)),
+    (ClientColumn::Cmd, "Cmd", Constraint::Fill(2)),
+    (ClientColumn::Memory, "Memory", Constraint::Length(11)),
+    (ClientColumn::Flags, "Flags", Constraint::Length(7)),
+];
+
+fn compare(column: ClientColumn, a: &ClientInfo, b: &ClientInfo) -> Ordering {
+    match column {
+        ClientColumn::Id => a.id.cmp(&b.id),
+        ClientColumn::Addr => a.addr.cmp(&b.addr),
+        ClientColumn::Name => a.name.cmp(&b.name),
+        ClientColumn::Db => a.db.cmp(&b.db),
+        ClientColumn::Age => a.age.cmp(&b.age),
+        ClientColumn::Idle => a.idle.cmp(&b.idle),
+        ClientColumn::Cmd => a.cmd.cmp(&b.cmd),
+        ClientColumn::Memory => a.memory.cmp(&b.memory),
+        ClientColumn::Flags => a.flags.cmp(&b.flags),
+    }
+}
+
+fn cell(column: ClientColumn, client: &ClientInfo) -> String {
+    match column {
+        ClientColumn::Id => client.id.to_string(),
+        ClientColumn::Addr => client.addr.clone(),
+        ClientColumn::Name => client.name.clone(),
+        ClientColumn::Db => client.db.to_string(),
+        ClientColumn::Age => client.age.to_string(),
+        ClientColumn::Idle => client.idle.to_string(),
+        ClientColumn::Cmd => client.cmd.clone(),
+        ClientColumn::Memory => format!(
+            "{:.1}",
+            Byte::from_u64(client.memory).get_appropriate_unit(UnitType::Binary)
+        ),
+        ClientColumn::Flags => client.flags.clone(),
+    }
+}
+
+/// The connections from `CLIENT LIST`, filtered and sorted.
+pub struct Clients {
+    list: ClientList,
+    filter: String,
+    /// Indexes of the clients passing the filter, in display order.
+    visible: Vec<usize>,
+    table: TableState,
+    /// Column under the column cursor.
+    current: usize,
+    sort: Option<(ClientColumn, SortOrder)>,
+}
+
+impl Clients {
+    pub fn new() -> Self {
+        Self {
+            list: ClientList::default(),
+            filter: String::new(),
+            visible: Vec::new(),
+            table: TableState::default(),
+            current: 0,
+            sort: None,
+        }
+    }
+
+    pub fn set_list(&mut self, list: ClientList) {
+        let selected = self.selected().map(|client| client.id);
+        self.list = list;
+        self.apply(selected);
+    }
+
+    pub fn filter(&self) -> &str {
+        &self.filter
+    }
+
+    pub fn set_filter(&mut self, filter: String) {
+        let selected = self.selected().map(|client| client.id);
+        self.filter = filter;
+        self.apply(selected);
+    }
+
+    pub fn next_column(&mut self) {
+        self.current = (self.current + 1) % COLUMNS.len();
+    }
+
+    pub fn previous_column(&mut self) {
+        self.current = (self.current + COLUMNS.len() - 1) % COLUMNS.len();
+    }
+
+    /// Ascending, descending, then unsorted, like the key table.
+    pub fn toggle_sort(&mut self) {
+        let column = COLUMNS[self.current].0;
+        self.sort = match self.sort {
+            Some((sorted, SortOrder::Ascending)) if sorted == column => {
+                Some((column, SortOrder::Descending))
+            }
+            Some((sorted, SortOrder::Descending)) if sorted == column => None,
+            _ => Some((column, SortOrder::Ascending)),
+        };
+
+        let selected = self.selected().map(|client| client.id);
+        self.apply(selected);
+    }
+
+    /// Filters and sorts the clients again, keeping the selected one selected.
+    fn apply(&mut self, selected: Option<u64>) {
+        let clients = &self.list.clients;
+        self.visible = (0..clients.len())
+            .filter(|idx| clients[*idx].matches(&self.filter))
+            .collect();
+
+        if let Some((column, order)) = self.sort {
+            self.visible.sort_by(|a, b| {
+                let ordering = compare(column, &clients[*a], &clients[*b]);
+                match order {
+                    SortOrder::Ascending => ordering,
+                    SortOrder::Descending => ordering.reverse(),
+                }
+            });
+        }
+
+        let index = selected
+            .and_then(|id| self.visible.iter().position(|idx| clients[*idx].id == id))
+            .or_else(|| (!self.visible.is_empty()).then_some(0));
+        self.table.select(index);
+    }
+
+    pub fn selected(&self) -> Option<&ClientInfo> {
+        self.table
+            .selected()
+            .and_then(|i| self.visible.get(i))
+            .and_then(|idx| self.list.clients.get(*idx))
+    }
+
+    pub fn scroll_next(&mut self) {
+        if self.visible.is_empty() {
+            return;
+        }
+        let next = self
+            .table
+            .selected()
+            .map_or(0, |i| (i + 1) % self.visible.len());
+        self.table.select(Some(next));
+    }
+
+    pub fn scroll_previous(&mut self) {
+        if self.visible.is_empty() {
+            return;
+        }
+        let last = self.visible.len() - 1;
+        let previous = self
+            .table
+            .selected()
+            .map_or(last, |i| (i + last) % self.visible.len());
+        self.table.select(Some(previous));
+    }
+}
+
+pub struct ClientsWidget;
+
+impl StatefulWidget for ClientsWidget {
+    type State = Clients;
+
+    fn render(self, area: Rect, buf: &mut ratatui::prelude::Buffer, state: &mut Self::State) {
+        let colors = config::get().colors;
+
+        let mut title = format!(
+            "Clients ({} of {})",
+            state.visible.len(),
+            state.list.clients.len()
+        );
+        if !state.filter.is_empty() {
+            title.push_str(&format!(" matching {:?}", state.filter));
+        }
+        title.push_str(" <f> filter, <[/]> column, <o> sort, <d> kill, <r> refresh");
+
+        let block = Block::new()
+            .bg(colors.base00)
+            .fg(colors.base04)
+            .border_type(BorderType::Rounded)
+            .borders(Borders::ALL)
+            .title(title);
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        let [table_area, own_area] =
+            Layout::vertical([Constraint::Fill(1), Constraint::Length(1)]).areas(inner);
+
+        let header = Row::new(COLUMNS.iter().enumerate().map(|(idx, (column, name, _))| {
+            let indicator = match state.sort {
+                Some((sorted, SortOrder::Ascending)) if sorted == *column => " ▲",
+                Some((sorted, SortOrder::Descending)) if sorted == *column => " ▼",
+                _ => "",
+            };
+
+            let mut title = Span::raw(format!("{name}{indicator}")).bold();
+            if idx == state.current {
+                title = title.underlined().fg(colors.base0d);
+            }
+            Cell::from(title)
+        }))
+        .bottom_margin(1)
+        .fg(colors.base04)
+        .bg(colors.base02);
+
+        let own_id = state.list.own.as_ref().map(|own| own.id);
+        let rows = state.visible.iter().enumerate().map(|(i, idx)| {
+            let client = &state.list.clients[*idx];
+            let row = Row::new(COLUMNS.iter().map(|(column, _, _)| cell(*column, client)));
+
+            let row = if Some(client.id) == own_id {
+                row.fg(colors.base0b)
+            } else {
+                row.fg(colors.base04)
+            };
+            row.bg(if i % 2 == 0 {
+                colors.base00
+            } else {
+                colors.base01
+            })
+        });
+
+        let table = Table::new(rows, COLUMNS.map(|(_, _, width)| width))
+            .header(header)
+            .highlight_symbol(HIGHLIGHT_SYMBOL)
+            .highlight_style(colors.base05)
+            .highlight_spacing(HighlightSpacing::Always);
+        StatefulWidget::render(table, table_area, buf, &mut state.table);
+
+        let own = match state.list.own {
+            Some(ref own) => format!(
+                "This connection: id={} addr={} db={} age={}s",
+                own.id, own.addr, own.db, own.age
+            ),
+            None => "This connection: CLIENT INFO needs Redis 6.2".into(),
+        };
+        Paragraph::new(own).fg(colors.base0b).render(own_area, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn client(id: u64, addr: &str, idle: u64) -> ClientInfo {
+        ClientInfo {
+            id,
+            addr: addr.into(),
+            idle,
+            ..ClientInfo::default()
+        }
+    }
+
+    #[test]
+    fn test_filter_and_sort() {
+        let mut clients = Clients::new();
+        clients.set_list(ClientList {
+            clients: vec![
+                client(1, "10.0.0.1:1", 5),
+                client(2, "10.0.0.2:1", 50),
+                client(3, "127.0.0.1:1", 1),
+            ],
+            own: None,
+        });
+
+        // Idle, descending
+        clients.current = 5;
+        clients.toggle_sort();
+        clients.toggle_sort();
+        let ids = |clients: &Clients| -> Vec<u64> {
+            clients
+                .visible
+                .iter()
+                .map(|idx| clients.list.clients[*idx].id)
+                .collect()
+        };
+        assert_eq!(ids(&clients), [2, 1, 3]);
+
+        // The selection follows the client, not the row
+        assert_eq!(clients.selected().map(|c| c.id), Some(1));
+
+        clients.set_filter("10.0".into());
+        assert_eq!(ids(&clients), [2, 1]);
+        assert_eq!(clients.selected().map(|c| c.id), Some(1));
+    }
+}
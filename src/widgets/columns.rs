@@ -0,0 +1,311 @@
+use std::cmp::Ordering;
+
+use byte_unit::{Byte, UnitType};
+use ratatui::{
+    layout::Constraint,
+    style::Stylize,
+    text::{Line, Span},
+    widgets::{Cell, Row},
+};
+
+use crate::{
+    config::{self, ColumnConfig, KeyColumn},
+    redis_client::{client::ObjectFields, types::KeyMeta},
+};
+
+const MIN_WIDTH: u16 = 5;
+const MAX_WIDTH: u16 = 80;
+const RESIZE_STEP: u16 = 5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortOrder {
+    Ascending,
+    Descending,
+}
+
+/// Columns of the key table with the one under the column cursor and the sort applied to the page.
+pub struct KeyColumns {
+    columns: Vec<ColumnConfig>,
+    current: usize,
+    sort: Option<(KeyColumn, SortOrder)>,
+}
+
+impl KeyColumns {
+    pub fn new(columns: Vec<ColumnConfig>) -> Self {
+        Self {
+            columns,
+            current: 0,
+            sort: None,
+        }
+    }
+
+    /// The `OBJECT` details the shown columns need.
+    pub fn object_fields(&self) -> ObjectFields {
+        let shown = |column| self.columns.iter().any(|c| c.column == column);
+        ObjectFields {
+            encoding: shown(KeyColumn::Encoding),
+            idle: shown(KeyColumn::Idle),
+            freq: shown(KeyColumn::Freq),
+        }
+    }
+
+    pub fn next(&mut self) {
+        self.current = (self.current + 1) % self.columns.len().max(1);
+    }
+
+    pub fn previous(&mut self) {
+        let len = self.columns.len().max(1);
+        self.current = (self.current + len - 1) % len;
+    }
+
+    /// Widens the current column, as far as the other columns leave room for.
+    pub fn grow(&mut self) {
+        let others: u16 = self
+            .columns
+            .iter()
+            .enumerate()
+            .filter(|(idx, _)| *idx != self.current)
+            .map(|(_, c)| c.width)
+            .sum();
+        if let Some(column) = self.columns.get_mut(self.current) {
+            let room = 100u16.saturating_sub(others).max(column.width);
+            column.width = (column.width + RESIZE_STEP).min(MAX_WIDTH).min(room);
+        }
+    }
+
+    pub fn shrink(&mut self) {
+        if let Some(column) = self.columns.get_mut(self.current) {
+            column.width = column.width.saturating_sub(RESIZE_STEP).max(MIN_WIDTH);
+        }
+    }
+
+    /// Sorts by the current column ascending, then descending, then back to the server order.
+    pub fn toggle_sort(&mut self) {
+        let Some(column) = self.columns.get(self.current).map(|c| c.column) else {
+            return;
+        };
+
+        self.sort = match self.sort {
+            Some((sorted, SortOrder::Ascending)) if sorted == column => {
+                Some((column, SortOrder::Descending))
+            }
+            Some((sorted, SortOrder::Descending)) if sorted == column => None,
+            _ => Some((column, SortOrder::Ascending)),
+        };
+    }
+
+    /// Sorts rows of the key table, leaves them in the order SCAN returned them when unsorted.
+    pub fn sort<'a, T>(&self, rows: &mut [T], meta: impl Fn(&T) -> &'a KeyMeta) {
+        let Some((column, order)) = self.sort else {
+            return;
+        };
+
+        rows.sort_by(|a, b| {
+            let ordering = compare(column, meta(a), meta(b));
+            match order {
+                SortOrder::Ascending => ordering,
+                SortOrder::Descending => ordering.reverse(),
+            }
+        });
+    }
+
+    pub fn widths(&self) -> Vec<Constraint> {
+        self.columns
+            .iter()
+            .map(|c| Constraint::Percentage(c.width))
+            .collect()
+    }
+
+    pub fn header(&self) -> Row<'static> {
+        let colors = config::get().colors;
+
+        Row::new(self.columns.iter().enumerate().map(|(idx, c)| {
+            let indicator = match self.sort {
+                Some((column, SortOrder::Ascending)) if column == c.column => " ▲",
+                Some((column, SortOrder::Descending)) if column == c.column => " ▼",
+                _ => "",
+            };
+
+            let mut title = Span::raw(format!("{}{indicator}", title(c.column))).bold();
+            if idx == self.current {
+                title = title.underlined().fg(colors.base0d);
+            }
+
+            Cell::from(title)
+        }))
+    }
+
+    pub fn cells(&self, meta: &KeyMeta, key: Line<'static>) -> Vec<Cell<'static>> {
+        self.columns
+            .iter()
+            .map(|c| match c.column {
+                KeyColumn::Key => Cell::from(key.clone()),
+                column => cell(column, meta),
+            })
+            .collect()
+    }
+}
+
+fn title(column: KeyColumn) -> &'static str {
+    match column {
+        KeyColumn::Key => "Key",
+        KeyColumn::Type => "Type",
+        KeyColumn::Ttl => "TTL(s)",
+        KeyColumn::Memory => "Size",
+        KeyColumn::Encoding => "Encoding",
+        KeyColumn::Length => "Length",
+        KeyColumn::Idle => "Idle(s)",
+        KeyColumn::Freq => "Freq",
+    }
+}
+
+fn optional<T: ToString>(value: Option<T>) -> String {
+    value.map_or_else(|| "-".into(), |v| v.to_string())
+}
+
+fn cell(column: KeyColumn, meta: &KeyMeta) -> Cell<'static> {
+    match column {
+        KeyColumn::Key => Cell::from(meta.key.clone()),
+        KeyColumn::Type => Cell::from(meta.r_type),
+        KeyColumn::Ttl => Cell::from(meta.ttl.to_string()),
+        KeyColumn::Memory => Cell::from(format!(
+            "{:.2}",
+            unsafe { Byte::from_u128_unsafe(meta.size) }.get_appropriate_unit(UnitType::Binary)
+        )),
+        KeyColumn::Encoding => Cell::from(optional(meta.encoding.clone())),
+        KeyColumn::Length => Cell::from(optional(meta.value.len())),
+        KeyColumn::Idle => Cell::from(optional(meta.idle)),
+        KeyColumn::Freq => Cell::from(optional(meta.freq)),
+    }
+}
+
+fn compare(column: KeyColumn, a: &KeyMeta, b: &KeyMeta) -> Ordering {
+    // Keys without expiration (-1) last when sorting by what expires first
+    let ttl = |meta: &KeyMeta| {
+        if meta.ttl < 0 {
+            isize::MAX
+        } else {
+            meta.ttl
+        }
+    };
+
+    match column {
+        KeyColumn::Key => a.key.cmp(&b.key),
+        KeyColumn::Type => title_of(a).cmp(&title_of(b)),
+        KeyColumn::Ttl => ttl(a).cmp(&ttl(b)),
+        KeyColumn::Memory => a.size.cmp(&b.size),
+        KeyColumn::Encoding => a.encoding.cmp(&b.encoding),
+        KeyColumn::Length => a.value.len().cmp(&b.value.len()),
+        KeyColumn::Idle => a.idle.cmp(&b.idle),
+        KeyColumn::Freq => a.freq.cmp(&b.freq),
+    }
+}
+
+fn title_of(meta: &KeyMeta) -> String {
+    format!("{:?}", meta.r_type)
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::redis_client::types::{KeyValue, RedisType};
+
+    fn meta(key: &str, ttl: isize, size: u128) -> KeyMeta {
+        KeyMeta {
+            key: key.into(),
+            r_type: RedisType::String,
+            size,
+            ttl,
+            value: KeyValue::Unknown,
+            encoding: None,
+            idle: None,
+            freq: None,
+        }
+    }
+
+    #[test]
+    fn test_sort_cycle() {
+        let mut columns = KeyColumns::new(vec![
+            ColumnConfig {
+                column: KeyColumn::Key,
+                width: 50,
+            },
+            ColumnConfig {
+                column: KeyColumn::Ttl,
+                width: 50,
+            },
+        ]);
+        let keys = [meta("a", -1, 1), meta("b", 30, 2), meta("c", 10, 3)];
+        let sorted = |columns: &KeyColumns| {
+            let mut rows = vec![0, 1, 2];
+            columns.sort(&mut rows, |idx| &keys[*idx]);
+            rows.iter()
+                .map(|idx| keys[*idx].key.clone())
+                .collect::<String>()
+        };
+
+        columns.next();
+        columns.toggle_sort();
+        assert_eq!(sorted(&columns), "cba");
+
+        columns.toggle_sort();
+        assert_eq!(sorted(&columns), "abc");
+
+        columns.toggle_sort();
+        assert_eq!(columns.sort, None);
+
+        columns.shrink();
+        columns.shrink();
+        assert_eq!(columns.columns[1].width, 40);
+    }
+
+    #[test]
+    fn test_grow_within_total() {
+        let mut columns = KeyColumns::new(vec![
+            ColumnConfig {
+                column: KeyColumn::Key,
+                width: 60,
+            },
+            ColumnConfig {
+                column: KeyColumn::Ttl,
+                width: 37,
+            },
+        ]);
+
+        columns.grow();
+        assert_eq!(columns.columns[0].width, 63);
+        columns.grow();
+        assert_eq!(columns.columns[0].width, 63);
+
+        columns.next();
+        columns.shrink();
+        columns.previous();
+        columns.grow();
+        assert_eq!(columns.columns[0].width, 68);
+    }
+
+    #[test]
+    fn test_object_fields() {
+        let columns = KeyColumns::new(vec![
+            ColumnConfig {
+                column: KeyColumn::Key,
+                width: 50,
+            },
+            ColumnConfig {
+                column: KeyColumn::Idle,
+                width: 50,
+            },
+        ]);
+
+        assert_eq!(
+            columns.object_fields(),
+            ObjectFields {
+                encoding: false,
+                idle: true,
+                freq: false,
+            }
+        );
+    }
+}
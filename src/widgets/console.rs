@@ -0,0 +1,662 @@
+#[cfg(unix)]
+use std::os::unix::fs::OpenOptionsExt;
+use std::{
+    fs::OpenOptions,
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+use crossterm::event::KeyEvent;
+use ratatui::{
+    layout::{Constraint, Layout},
+    style::Stylize,
+    text::{Line, Text},
+    widgets::{Block, BorderType, Borders, Paragraph, StatefulWidget, Widget},
+};
+use tui_textarea::{CursorMove, TextArea};
+
+use crate::{
+    config,
+    redis_client::{docs::CommandDoc, reply::Reply, types::ConsoleEntry},
+    utils,
+};
+
+const HISTORY_FILE: &str = "console_history";
+const MAX_HISTORY: usize = 1000;
+
+/// Commands that turn the connection into a push stream and would stall the shared manager,
+/// or change its state under every other view, which a reconnect then silently undoes. A
+/// `WATCH` left on the shared connection would also fail the transactions the app sends on it.
+const UNSUPPORTED_COMMANDS: &[&str] = &[
+    "AUTH",
+    "HELLO",
+    "MONITOR",
+    "PSUBSCRIBE",
+    "PSYNC",
+    "QUIT",
+    "RESET",
+    "SELECT",
+    "SSUBSCRIBE",
+    "SUBSCRIBE",
+    "SYNC",
+    "UNWATCH",
+    "WATCH",
+];
+
+/// `CLIENT` subcommands changing how the shared connection is replied to.
+const UNSUPPORTED_CLIENT_SUBCOMMANDS: &[&str] = &["REPLY", "TRACKING"];
+
+/// Commands that hold the shared connection, and every view with it, until they are served.
+const BLOCKING_COMMANDS: &[&str] = &[
+    "BLMOVE",
+    "BLMPOP",
+    "BLPOP",
+    "BRPOP",
+    "BRPOPLPUSH",
+    "BZMPOP",
+    "BZPOPMAX",
+    "BZPOPMIN",
+    "WAIT",
+    "WAITAOF",
+];
+
+/// Why the console refuses the command, the first argument being its upper case name.
+fn unsupported(name: &str, args: &[Vec<u8>]) -> Option<String> {
+    // Options come before the keys of `STREAMS`, one of which may well be called `block`
+    let blocks = || {
+        args.iter()
+            .take_while(|arg| !arg.eq_ignore_ascii_case(b"STREAMS"))
+            .any(|arg| arg.eq_ignore_ascii_case(b"BLOCK"))
+    };
+
+    if UNSUPPORTED_COMMANDS.contains(&name) {
+        Some(format!("{name} is not supported in the console"))
+    } else if let Some(sub) = args
+        .get(1)
+        .map(|sub| String::from_utf8_lossy(sub).to_uppercase())
+        .filter(|sub| name == "CLIENT" && UNSUPPORTED_CLIENT_SUBCOMMANDS.contains(&sub.as_str()))
+    {
+        Some(format!("CLIENT {sub} is not supported in the console"))
+    } else if BLOCKING_COMMANDS.contains(&name)
+        || (matches!(name, "XREAD" | "XREADGROUP") && blocks())
+    {
+        Some(format!("{name} would block the connection the app shares"))
+    } else {
+        None
+    }
+}
+
+/// Whether the command carries a password, which is then kept out of the history file the way
+/// redis-cli does.
+fn is_sensitive(args: &[Vec<u8>]) -> bool {
+    let Some((name, rest)) = args.split_first() else {
+        return false;
+    };
+    let has = |word: &[u8]| rest.iter().any(|arg| arg.eq_ignore_ascii_case(word));
+    let sub = |word: &[u8]| {
+        rest.first()
+            .is_some_and(|arg| arg.eq_ignore_ascii_case(word))
+    };
+
+    match String::from_utf8_lossy(name).to_uppercase().as_str() {
+        "AUTH" => true,
+        "HELLO" => has(b"AUTH"),
+        "MIGRATE" => has(b"AUTH") || has(b"AUTH2"),
+        "ACL" => sub(b"SETUSER"),
+        "CONFIG" => {
+            sub(b"SET")
+                && rest[1..].iter().step_by(2).any(|param| {
+                    let param = String::from_utf8_lossy(param).to_lowercase();
+                    param.contains("pass") || param.contains("auth")
+                })
+        }
+        _ => false,
+    }
+}
+
+pub struct Console {
+    input: TextArea<'static>,
+    entries: Vec<ConsoleEntry>,
+    history: Vec<String>,
+    history_path: PathBuf,
+    history_index: Option<usize>,
+    docs: Vec<CommandDoc>,
+    hint: Option<String>,
+    scroll: usize,
+}
+
+impl Console {
+    pub fn new() -> Self {
+        Self::with_history(history_path())
+    }
+
+    fn with_history(history_path: PathBuf) -> Self {
+        Self {
+            input: Self::text_area(),
+            entries: Vec::new(),
+            history: load_history(&history_path),
+            history_path,
+            history_index: None,
+            docs: Vec::new(),
+            hint: None,
+            scroll: 0,
+        }
+    }
+
+    fn text_area() -> TextArea<'static> {
+        let mut text_area = TextArea::default();
+        text_area
+            .set_placeholder_text("Enter a command, <Tab> completes, <Up>/<Down> browse history");
+        text_area.set_cursor_line_style(Default::default());
+        text_area.set_block(
+            Block::default()
+                .border_style(config::get().colors.base04)
+                .border_type(BorderType::Rounded)
+                .borders(Borders::ALL)
+                .title("Command"),
+        );
+        text_area
+    }
+
+    pub fn has_docs(&self) -> bool {
+        !self.docs.is_empty()
+    }
+
+    pub fn set_docs(&mut self, docs: Vec<CommandDoc>) {
+        self.docs = docs;
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) {
+        self.input.input(key);
+        self.hint = None;
+    }
+
+    fn line(&self) -> String {
+        self.input.lines().join(" ")
+    }
+
+    fn set_line(&mut self, line: &str) {
+        self.input = Self::text_area();
+        self.input.insert_str(line);
+        self.input.move_cursor(CursorMove::End);
+    }
+
+    /// Takes the current input, records it in the history and returns the parsed arguments.
+    ///
+    /// Parse errors and unsupported commands are reported in place and never reach the server.
+    pub fn submit(&mut self) -> Option<Vec<Vec<u8>>> {
+        let line = self.line();
+        let line = line.trim();
+        self.set_line("");
+        self.history_index = None;
+        self.hint = None;
+        self.scroll = 0;
+
+        if line.is_empty() {
+            return None;
+        }
+
+        let parsed = split_args(line);
+        let sensitive = match parsed {
+            Ok(ref args) => is_sensitive(args),
+            Err(_) => is_sensitive(
+                &line
+                    .split_whitespace()
+                    .map(|word| word.as_bytes().to_vec())
+                    .collect::<Vec<_>>(),
+            ),
+        };
+        self.push_history(line, !sensitive);
+
+        if line.eq_ignore_ascii_case("clear") {
+            self.entries.clear();
+            return None;
+        }
+
+        let args = match parsed {
+            Ok(args) => args,
+            Err(err) => {
+                self.push_entry(ConsoleEntry {
+                    command: line.into(),
+                    reply: Reply::Error(err),
+                });
+                return None;
+            }
+        };
+
+        let name = String::from_utf8_lossy(&args[0]).to_uppercase();
+        if let Some(reason) = unsupported(&name, &args) {
+            self.push_entry(ConsoleEntry {
+                command: line.into(),
+                reply: Reply::Error(reason),
+            });
+            return None;
+        }
+
+        Some(args)
+    }
+
+    pub fn push_entry(&mut self, entry: ConsoleEntry) {
+        self.entries.push(entry);
+    }
+
+    /// Lines not persisted are still browsable until the app exits.
+    fn push_history(&mut self, line: &str, persist: bool) {
+        if self.history.last().map(String::as_str) == Some(line) {
+            return;
+        }
+
+        self.history.push(line.into());
+        if self.history.len() > MAX_HISTORY {
+            self.history.remove(0);
+        }
+
+        if !persist {
+            return;
+        }
+        if let Err(err) = append_history(&self.history_path, line) {
+            log::error!("Failed to save console history: {err:?}");
+        }
+    }
+
+    pub fn history_previous(&mut self) {
+        if self.history.is_empty() {
+            return;
+        }
+
+        let index = self
+            .history_index
+            .map_or(self.history.len() - 1, |i| i.saturating_sub(1));
+        self.history_index = Some(index);
+        let line = self.history[index].clone();
+        self.set_line(&line);
+    }
+
+    pub fn history_next(&mut self) {
+        let Some(index) = self.history_index else {
+            return;
+        };
+
+        if index + 1 >= self.history.len() {
+            self.history_index = None;
+            self.set_line("");
+        } else {
+            self.history_index = Some(index + 1);
+            let line = self.history[index + 1].clone();
+            self.set_line(&line);
+        }
+    }
+
+    pub fn scroll_up(&mut self) {
+        self.scroll = self.scroll.saturating_add(5);
+    }
+
+    pub fn scroll_down(&mut self) {
+        self.scroll = self.scroll.saturating_sub(5);
+    }
+
+    fn find_doc(&self, name: &str) -> Option<&CommandDoc> {
+        self.docs
+            .iter()
+            .find(|doc| doc.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Completes the word under the cursor: command names first, then subcommands
+    /// and keyword tokens of the command from `COMMAND DOCS`.
+    pub fn complete(&mut self) {
+        let line = self.line();
+        let mut words: Vec<&str> = line.split_whitespace().collect();
+        let current = if line.ends_with(' ') || line.is_empty() {
+            ""
+        } else {
+            words.pop().unwrap_or_default()
+        };
+
+        let candidates: Vec<String> = match words.as_slice() {
+            [] => self.docs.iter().map(|doc| doc.name.clone()).collect(),
+            [name, rest @ ..] => match self.find_doc(name) {
+                Some(doc) if rest.is_empty() && !doc.subcommands.is_empty() => {
+                    doc.subcommands.iter().map(|sub| sub.name.clone()).collect()
+                }
+                Some(doc) => {
+                    let doc = rest
+                        .first()
+                        .and_then(|sub| {
+                            doc.subcommands
+                                .iter()
+                                .find(|d| d.name.eq_ignore_ascii_case(sub))
+                        })
+                        .unwrap_or(doc);
+                    doc.tokens()
+                }
+                None => Vec::new(),
+            },
+        };
+
+        let matches: Vec<&String> = candidates
+            .iter()
+            .filter(|c| c.to_uppercase().starts_with(&current.to_uppercase()))
+            .collect();
+
+        let completion = match matches.as_slice() {
+            [] => {
+                self.hint = Some("No completions".into());
+                return;
+            }
+            [single] => format!("{single} "),
+            many => {
+                self.hint = Some(
+                    many.iter()
+                        .map(|s| s.as_str())
+                        .collect::<Vec<_>>()
+                        .join(" "),
+                );
+                common_prefix(many)
+            }
+        };
+
+        if completion.trim().len() < current.len() {
+            return;
+        }
+
+        words.push(&completion);
+        let line = words.join(" ");
+        self.set_line(&line);
+    }
+
+    /// Usage of the command being typed, shown under the output like `redis-cli` hints.
+    fn usage(&self) -> Option<String> {
+        let line = self.line();
+        let mut words = line.split_whitespace();
+        let doc = self.find_doc(words.next()?)?;
+
+        let doc = match words.next() {
+            Some(sub) if !doc.subcommands.is_empty() => doc
+                .subcommands
+                .iter()
+                .find(|d| d.name.eq_ignore_ascii_case(sub))
+                .map(|sub| (format!("{} {}", doc.name, sub.name), sub)),
+            _ => Some((doc.name.clone(), doc)),
+        };
+
+        doc.map(|(name, doc)| format!("{name} {}  -- {}", doc.syntax(), doc.summary))
+    }
+}
+
+fn history_path() -> PathBuf {
+    utils::get_data_dir().join(HISTORY_FILE)
+}
+
+fn load_history(path: &Path) -> Vec<String> {
+    let Ok(content) = std::fs::read_to_string(path) else {
+        return Vec::new();
+    };
+
+    let lines: Vec<String> = content.lines().map(Into::into).collect();
+    let skip = lines.len().saturating_sub(MAX_HISTORY);
+    lines.into_iter().skip(skip).collect()
+}
+
+/// Appends to the history file, created readable by the user only.
+fn append_history(path: &Path, line: &str) -> std::io::Result<()> {
+    let mut options = OpenOptions::new();
+    options.create(true).append(true);
+    #[cfg(unix)]
+    options.mode(0o600);
+
+    writeln!(options.open(path)?, "{line}")
+}
+
+fn common_prefix(words: &[&String]) -> String {
+    let Some(first) = words.first() else {
+        return String::new();
+    };
+
+    let mut len = first.len();
+    for word in &words[1..] {
+        len = first
+            .chars()
+            .zip(word.chars())
+            .take_while(|(a, b)| a.eq_ignore_ascii_case(b))
+            .count()
+            .min(len);
+    }
+
+    first.chars().take(len).collect()
+}
+
+fn push_char(arg: &mut Vec<u8>, c: char) {
+    arg.extend_from_slice(c.encode_utf8(&mut [0; 4]).as_bytes());
+}
+
+/// Splits a command line into arguments the way `redis-cli` does: whitespace separated,
+/// double quotes support escapes (`\n`, `\t`, `\"`, `\xHH`), single quotes only `\'`.
+/// Arguments are bytes, `\xHH` stands for the byte itself and not for a character.
+pub fn split_args(line: &str) -> Result<Vec<Vec<u8>>, String> {
+    let mut args = Vec::new();
+    let mut chars = line.chars().peekable();
+
+    loop {
+        while chars.peek().is_some_and(|c| c.is_whitespace()) {
+            chars.next();
+        }
+
+        let Some(&first) = chars.peek() else {
+            break;
+        };
+
+        let mut arg = Vec::new();
+        match first {
+            '"' => {
+                chars.next();
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => match chars.next() {
+                            Some('n') => arg.push(b'\n'),
+                            Some('r') => arg.push(b'\r'),
+                            Some('t') => arg.push(b'\t'),
+                            Some('b') => arg.push(0x8),
+                            Some('a') => arg.push(0x7),
+                            Some('x') => {
+                                let hex: String = chars.by_ref().take(2).collect();
+                                let byte = u8::from_str_radix(&hex, 16)
+                                    .map_err(|_| format!("Invalid escape \\x{hex}"))?;
+                                arg.push(byte);
+                            }
+                            Some(c) => push_char(&mut arg, c),
+                            None => return Err("Unbalanced quotes".into()),
+                        },
+                        Some(c) => push_char(&mut arg, c),
+                        None => return Err("Unbalanced quotes".into()),
+                    }
+                }
+            }
+            '\'' => {
+                chars.next();
+                loop {
+                    match chars.next() {
+                        Some('\'') => break,
+                        Some('\\') if chars.peek() == Some(&'\'') => {
+                            chars.next();
+                            arg.push(b'\'');
+                        }
+                        Some(c) => push_char(&mut arg, c),
+                        None => return Err("Unbalanced quotes".into()),
+                    }
+                }
+            }
+            _ => {
+                while let Some(c) = chars.next_if(|c| !c.is_whitespace()) {
+                    push_char(&mut arg, c);
+                }
+            }
+        }
+
+        if chars.peek().is_some_and(|c| !c.is_whitespace()) {
+            return Err("Closing quote must be followed by a space".into());
+        }
+
+        args.push(arg);
+    }
+
+    Ok(args)
+}
+
+/// [`split_args`] for the inputs that only take text.
+pub fn split_text_args(line: &str) -> Result<Vec<String>, String> {
+    split_args(line)?
+        .into_iter()
+        .map(|arg| String::from_utf8(arg).map_err(|_| "Arguments must be valid UTF-8".into()))
+        .collect()
+}
+
+pub struct ConsoleWidget;
+
+impl StatefulWidget for ConsoleWidget {
+    type State = Console;
+
+    fn render(
+        self,
+        area: ratatui::prelude::Rect,
+        buf: &mut ratatui::prelude::Buffer,
+        state: &mut Self::State,
+    ) {
+        let colors = config::get().colors;
+
+        let [output_area, hint_area, input_area] = Layout::vertical([
+            Constraint::Fill(1),
+            Constraint::Length(1),
+            Constraint::Length(3),
+        ])
+        .areas(area);
+
+        let block = Block::new()
+            .bg(colors.base00)
+            .fg(colors.base04)
+            .border_type(BorderType::Rounded)
+            .borders(Borders::all())
+            .title("Console");
+
+        let inner = block.inner(output_area);
+        block.render(output_area, buf);
+
+        let mut lines: Vec<Line> = Vec::new();
+        for entry in &state.entries {
+            lines.push(
+                Line::from(format!("> {}", entry.command))
+                    .fg(colors.base0d)
+                    .bold(),
+            );
+            lines.extend(entry.reply.lines());
+        }
+
+        let max_scroll = lines.len().saturating_sub(inner.height as usize);
+        state.scroll = state.scroll.min(max_scroll);
+        let offset = (max_scroll - state.scroll) as u16;
+
+        Paragraph::new(Text::from(lines))
+            .scroll((offset, 0))
+            .render(inner, buf);
+
+        let hint = state
+            .hint
+            .clone()
+            .or_else(|| state.usage())
+            .unwrap_or_default();
+        Paragraph::new(hint)
+            .fg(colors.base03)
+            .render(hint_area, buf);
+
+        state.input.render(input_area, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_split_args() {
+        assert_eq!(
+            split_args("SET  key \"hello world\"").unwrap(),
+            [&b"SET"[..], b"key", b"hello world"]
+        );
+        assert_eq!(
+            split_args(r#"SET k "a\"b\n\x41""#).unwrap(),
+            [&b"SET"[..], b"k", b"a\"b\nA"]
+        );
+        assert_eq!(split_args(r"GET 'it\'s'").unwrap(), [&b"GET"[..], b"it's"]);
+        assert_eq!(split_args("   ").unwrap(), Vec::<Vec<u8>>::new());
+    }
+
+    #[test]
+    fn test_split_args_bytes() {
+        assert_eq!(
+            split_args(r#"SET k "\xff\x00é""#).unwrap(),
+            [&b"SET"[..], b"k", &[0xff, 0x00, 0xc3, 0xa9]]
+        );
+        assert!(split_text_args(r#"SET k "\xff""#).is_err());
+        assert_eq!(split_text_args("SET k é").unwrap(), ["SET", "k", "é"]);
+    }
+
+    #[test]
+    fn test_unsupported() {
+        let check = |line: &str| {
+            let args = split_args(line).unwrap();
+            unsupported(&String::from_utf8_lossy(&args[0]).to_uppercase(), &args)
+        };
+
+        assert!(check("SUBSCRIBE news").is_some());
+        assert!(check("select 2").is_some());
+        assert!(check("CLIENT reply off").is_some());
+        assert!(check("client tracking on").is_some());
+        assert!(check("HELLO 3").is_some());
+        assert!(check("AUTH default secret").is_some());
+        assert!(check("WATCH key").is_some());
+        assert!(check("unwatch").is_some());
+        assert!(check("BLPOP queue 0").is_some());
+        assert!(check("XREAD COUNT 1 block 0 STREAMS s $").is_some());
+        assert_eq!(check("XREAD COUNT 1 STREAMS block 0"), None);
+        assert_eq!(check("CLIENT LIST"), None);
+        assert_eq!(check("GET key"), None);
+    }
+
+    #[test]
+    fn test_sensitive_lines_are_not_persisted() {
+        let path = std::env::temp_dir().join(format!(
+            "{}-history-{}",
+            env!("CARGO_PKG_NAME"),
+            std::process::id()
+        ));
+        let _ = std::fs::remove_file(&path);
+
+        let mut console = Console::with_history(path.clone());
+        for line in [
+            "AUTH default secret",
+            "hello 3 auth default secret",
+            "ACL SETUSER app on >secret",
+            "CONFIG SET maxmemory 1mb requirepass secret",
+            "MIGRATE host 6379 \"\" 0 5000 AUTH secret KEYS a",
+            "GET key",
+            "CONFIG SET maxmemory 1mb",
+        ] {
+            console.set_line(line);
+            console.submit();
+        }
+        let saved = std::fs::read_to_string(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+
+        assert_eq!(saved, "GET key\nCONFIG SET maxmemory 1mb\n");
+        assert_eq!(console.history.len(), 7);
+    }
+
+    #[test]
+    fn test_split_args_errors() {
+        assert!(split_args("GET \"key").is_err());
+        assert!(split_args("GET 'key").is_err());
+        assert!(split_args("GET \"a\"b").is_err());
+    }
+}
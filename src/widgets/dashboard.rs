@@ -0,0 +1,318 @@
+use std::sync::{Arc, Mutex};
+
+use byte_unit::{Byte, UnitType};
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    style::Stylize,
+    widgets::{Block, BorderType, Borders, Cell, Paragraph, Row, StatefulWidget, Table, Widget},
+};
+
+use crate::{
+    config,
+    redis_client::{info::RedisInfo, samples::Samples},
+    widgets::charts::ChartsWidget,
+};
+
+fn bytes(size: u64) -> String {
+    format!(
+        "{:.2}",
+        Byte::from_u64(size).get_appropriate_unit(UnitType::Binary)
+    )
+}
+
+fn uptime(secs: u64) -> String {
+    format!(
+        "{}d {}h {}m",
+        secs / 86400,
+        secs % 86400 / 3600,
+        secs % 3600 / 60
+    )
+}
+
+fn yes_no(value: bool) -> String {
+    if value { "yes" } else { "no" }.into()
+}
+
+type Panel = (&'static str, Vec<(String, String)>);
+
+fn rows(fields: &[(&str, String)]) -> Vec<(String, String)> {
+    fields
+        .iter()
+        .map(|(name, value)| (name.to_string(), value.clone()))
+        .collect()
+}
+
+/// The sections of `INFO`, each one a list of labelled values.
+fn panels(info: &RedisInfo) -> [Vec<Panel>; 3] {
+    let server = &info.server;
+    let clients = &info.clients;
+    let memory = &info.memory;
+    let persistence = &info.persistence;
+    let stats = &info.stats;
+    let replication = &info.replication;
+    let cpu = &info.cpu;
+
+    let max_memory = if memory.max_memory == 0 {
+        "unlimited".into()
+    } else {
+        memory.max_memory_human.clone()
+    };
+    let hit_ratio = stats
+        .hit_ratio()
+        .map_or_else(|| "-".into(), |ratio| format!("{:.1}%", ratio * 100.0));
+
+    let mut replication_rows = rows(&[
+        ("Role", replication.role.clone()),
+        ("Replicas", replication.connected_replicas.to_string()),
+        ("Offset", replication.master_repl_offset.to_string()),
+    ]);
+    if !replication.master_host.is_empty() {
+        replication_rows.extend(rows(&[
+            (
+                "Master",
+                format!("{}:{}", replication.master_host, replication.master_port),
+            ),
+            ("Link", replication.master_link_status.clone()),
+        ]));
+    }
+    replication_rows.extend(
+        replication
+            .replicas
+            .iter()
+            .map(|replica| (String::new(), replica.clone())),
+    );
+
+    [
+        vec![
+            (
+                "Server",
+                rows(&[
+                    ("Version", server.version.clone()),
+                    ("Mode", server.mode.clone()),
+                    ("OS", format!("{} ({} bit)", server.os, server.arch_bits)),
+                    ("PID", server.process_id.to_string()),
+                    ("Port", server.tcp_port.to_string()),
+                    ("Uptime", uptime(server.uptime_in_seconds)),
+                    ("Config", server.config_file.clone()),
+                ]),
+            ),
+            (
+                "Clients",
+                rows(&[
+                    (
+                        "Connected",
+                        format!("{}/{}", clients.connected, clients.max_clients),
+                    ),
+                    ("Blocked", clients.blocked.to_string()),
+                    ("Tracking", clients.tracking.to_string()),
+                ]),
+            ),
+            (
+                "Memory",
+                rows(&[
+                    ("Used", memory.used_human.clone()),
+                    ("RSS", bytes(memory.used_rss)),
+                    ("Peak", memory.used_peak_human.clone()),
+                    ("Lua", bytes(memory.used_lua)),
+                    ("Max", max_memory),
+                    ("Policy", memory.max_memory_policy.clone()),
+                    ("System", memory.total_system_human.clone()),
+                    (
+                        "Fragmentation",
+                        format!("{:.2}", memory.fragmentation_ratio),
+                    ),
+                ]),
+            ),
+            (
+                "CPU",
+                rows(&[
+                    ("System", format!("{:.2}s", cpu.used_cpu_sys)),
+                    ("User", format!("{:.2}s", cpu.used_cpu_user)),
+                    ("Children sys", format!("{:.2}s", cpu.used_cpu_sys_children)),
+                    (
+                        "Children user",
+                        format!("{:.2}s", cpu.used_cpu_user_children),
+                    ),
+                ]),
+            ),
+        ],
+        vec![
+            (
+                "Stats",
+                rows(&[
+                    ("Ops/sec", stats.instantaneous_ops_per_sec.to_string()),
+                    ("Commands", stats.total_commands_processed.to_string()),
+                    ("Connections", stats.total_connections_received.to_string()),
+                    ("Rejected", stats.rejected_connections.to_string()),
+                    ("Hit ratio", hit_ratio),
+                    ("Expired", stats.expired_keys.to_string()),
+                    ("Evicted", stats.evicted_keys.to_string()),
+                    (
+                        "Net in",
+                        format!(
+                            "{} ({:.2} KB/s)",
+                            bytes(stats.total_net_input_bytes),
+                            stats.instantaneous_input_kbps
+                        ),
+                    ),
+                    (
+                        "Net out",
+                        format!(
+                            "{} ({:.2} KB/s)",
+                            bytes(stats.total_net_output_bytes),
+                            stats.instantaneous_output_kbps
+                        ),
+                    ),
+                    (
+                        "Pub/Sub",
+                        format!(
+                            "{} channels, {} patterns",
+                            stats.pubsub_channels, stats.pubsub_patterns
+                        ),
+                    ),
+                    ("Error replies", stats.total_error_replies.to_string()),
+                ]),
+            ),
+            (
+                "Persistence",
+                rows(&[
+                    ("Loading", yes_no(persistence.loading)),
+                    (
+                        "Unsaved changes",
+                        persistence.rdb_changes_since_last_save.to_string(),
+                    ),
+                    ("BGSAVE running", yes_no(persistence.rdb_bgsave_in_progress)),
+                    ("Last save", persistence.rdb_last_save_time.to_string()),
+                    ("Last BGSAVE", persistence.rdb_last_bgsave_status.clone()),
+                    ("AOF", yes_no(persistence.aof_enabled)),
+                    ("AOF rewrite", yes_no(persistence.aof_rewrite_in_progress)),
+                    ("AOF last write", persistence.aof_last_write_status.clone()),
+                ]),
+            ),
+            ("Replication", replication_rows),
+        ],
+        vec![
+            (
+                "Keyspace",
+                info.keyspace
+                    .iter()
+                    .map(|db| {
+                        (
+                            format!("db{}", db.db),
+                            format!(
+                                "{} keys, {} expiring, avg TTL {}ms",
+                                db.keys, db.expires, db.avg_ttl
+                            ),
+                        )
+                    })
+                    .collect(),
+            ),
+            (
+                "Modules",
+                info.modules
+                    .iter()
+                    .map(|module| (module.name.clone(), module.version.clone()))
+                    .collect(),
+            ),
+            (
+                "Errorstats",
+                info.errors
+                    .iter()
+                    .map(|(error, count)| (error.clone(), count.to_string()))
+                    .collect(),
+            ),
+        ],
+    ]
+}
+
+/// Every section of `INFO`, refreshed with the footer.
+pub struct Dashboard {
+    info: Arc<Mutex<Option<RedisInfo>>>,
+    samples: Arc<Mutex<Samples>>,
+    /// The history of the metrics is shown instead of the sections.
+    charts: bool,
+}
+
+impl Dashboard {
+    pub fn new(info: Arc<Mutex<Option<RedisInfo>>>, samples: Arc<Mutex<Samples>>) -> Self {
+        Self {
+            info,
+            samples,
+            charts: false,
+        }
+    }
+
+    pub fn toggle_charts(&mut self) {
+        self.charts = !self.charts;
+    }
+}
+
+pub struct DashboardWidget;
+
+impl StatefulWidget for DashboardWidget {
+    type State = Dashboard;
+
+    fn render(self, area: Rect, buf: &mut ratatui::prelude::Buffer, state: &mut Self::State) {
+        let colors = config::get().colors;
+
+        let block = Block::new()
+            .bg(colors.base00)
+            .fg(colors.base04)
+            .border_type(BorderType::Rounded)
+            .borders(Borders::ALL)
+            .title(if state.charts {
+                "Dashboard: charts (<Tab> sections, <Esc> back)"
+            } else {
+                "Dashboard: sections (<Tab> charts, <Esc> back)"
+            });
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        if state.charts {
+            let samples = state.samples.lock().unwrap();
+            if samples.is_empty() {
+                Paragraph::new("Waiting for INFO...").render(inner, buf);
+            } else {
+                ChartsWidget::new(&samples).render(inner, buf);
+            }
+            return;
+        }
+
+        let Some(info) = state.info.lock().unwrap().clone() else {
+            Paragraph::new("Waiting for INFO...").render(inner, buf);
+            return;
+        };
+
+        let columns = panels(&info);
+        let column_areas = Layout::horizontal([Constraint::Fill(1); 3]).split(inner);
+
+        for (panels, column_area) in columns.into_iter().zip(column_areas.iter()) {
+            let heights = panels
+                .iter()
+                .map(|(_, rows)| Constraint::Length(rows.len().max(1) as u16 + 2));
+            let areas = Layout::vertical(heights).split(*column_area);
+
+            for ((title, rows), panel_area) in panels.into_iter().zip(areas.iter()) {
+                let block = Block::new()
+                    .border_style(colors.base03)
+                    .border_type(BorderType::Rounded)
+                    .borders(Borders::ALL)
+                    .title(title.fg(colors.base0d));
+
+                let label_width = rows.iter().map(|(label, _)| label.len()).max();
+                let rows = rows.into_iter().map(|(label, value)| {
+                    Row::new([
+                        Cell::from(label).fg(colors.base04),
+                        Cell::from(value).fg(colors.base05),
+                    ])
+                });
+                let widths = [
+                    Constraint::Length(label_width.unwrap_or_default() as u16),
+                    Constraint::Fill(1),
+                ];
+
+                Widget::render(Table::new(rows, widths).block(block), *panel_area, buf);
+            }
+        }
+    }
+}
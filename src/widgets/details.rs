@@ -0,0 +1,512 @@
+use std::collections::HashMap;
+
+use byte_unit::{Byte, UnitType};
+use crossterm::event::KeyEvent;
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    style::Stylize,
+    text::Line,
+    widgets::{
+        Block, BorderType, Borders, Cell, HighlightSpacing, Paragraph, Row, Scrollbar,
+        ScrollbarOrientation, ScrollbarState, StatefulWidget, Table, TableState, Widget, Wrap,
+    },
+};
+use tui_textarea::TextArea;
+
+use crate::{
+    config,
+    redis_client::types::{KeyMeta, KeyValue, RedisType},
+};
+
+const HIGHLIGHT_SYMBOL: &str = " >> ";
+/// Rows taken by the table header with its margins.
+const HEADER_HEIGHT: u16 = 3;
+/// Columns taken by the highlight symbol and the scrollbar.
+const CHROME_WIDTH: usize = HIGHLIGHT_SYMBOL.len() + 1;
+/// Keys whose scroll position is remembered, past which they are all forgotten.
+const MAX_TABLES: usize = 1000;
+
+/// Column titles and rows for a value, sets and hashes sorted so that the scroll position
+/// stays meaningful across refreshes. Strings are wrapped to `width`.
+fn value_rows(value: &KeyValue, width: usize) -> (Vec<&'static str>, Vec<Vec<String>>) {
+    match value {
+        KeyValue::String(value) => (
+            vec!["Value"],
+            value
+                .lines()
+                .flat_map(|line| wrap(line, width))
+                .map(|line| vec![line])
+                .collect(),
+        ),
+        KeyValue::List(items) => (
+            vec!["Item"],
+            items.iter().map(|item| vec![item.clone()]).collect(),
+        ),
+        KeyValue::Set(members) => {
+            let mut members: Vec<Vec<String>> = members.iter().map(|m| vec![m.clone()]).collect();
+            members.sort();
+            (vec!["Member"], members)
+        }
+        KeyValue::Hash(fields) => {
+            let mut fields: Vec<Vec<String>> = fields
+                .iter()
+                .map(|(field, value)| vec![field.clone(), value.clone()])
+                .collect();
+            fields.sort();
+            (vec!["Field", "Value"], fields)
+        }
+        KeyValue::Zset(members) => (
+            vec!["Member", "Score"],
+            members
+                .iter()
+                .map(|(member, score)| vec![member.clone(), score.to_string()])
+                .collect(),
+        ),
+        KeyValue::Json(value) => (
+            vec!["Value"],
+            serde_json::to_string_pretty(value)
+                .unwrap_or_default()
+                .lines()
+                .map(|line| vec![line.to_owned()])
+                .collect(),
+        ),
+        KeyValue::Unknown => (Vec::new(), Vec::new()),
+    }
+}
+
+/// Splits a line into chunks of at most `width` chars, an empty line stays one row.
+fn wrap(line: &str, width: usize) -> Vec<String> {
+    let chars: Vec<char> = line.chars().collect();
+    if chars.is_empty() || width == 0 {
+        return vec![line.to_owned()];
+    }
+    chars.chunks(width).map(|c| c.iter().collect()).collect()
+}
+
+/// Value of the selected key, scrolled and searched independently of the key list. Every key
+/// remembers its own position, up to [`MAX_TABLES`] of them.
+pub struct Details {
+    key: Option<String>,
+    r_type: Option<RedisType>,
+    /// The value and width the rows were built from, frames showing the same keep them.
+    shown: Option<(KeyValue, usize)>,
+    header: Vec<&'static str>,
+    rows: Vec<Vec<String>>,
+    tables: HashMap<String, TableState>,
+    search: Option<TextArea<'static>>,
+    query: String,
+    matches: Vec<usize>,
+    /// Where the rows were drawn last, for mouse clicks.
+    area: Rect,
+}
+
+impl Details {
+    pub fn new() -> Self {
+        Self {
+            key: None,
+            r_type: None,
+            shown: None,
+            header: Vec::new(),
+            rows: Vec::new(),
+            tables: HashMap::new(),
+            search: None,
+            query: String::new(),
+            matches: Vec::new(),
+            area: Rect::default(),
+        }
+    }
+
+    /// Shows `meta`, going back to where it was left when the key was shown before. The rows
+    /// are only built again when the key, its value or the width changed.
+    fn update(&mut self, meta: Option<&KeyMeta>, width: usize) {
+        let key = meta.map(|meta| &meta.key);
+        let unchanged = self.key.as_ref() == key
+            && match (meta, &self.shown) {
+                (Some(meta), Some((value, shown_width))) => {
+                    *shown_width == width && *value == meta.value
+                }
+                (None, None) => true,
+                _ => false,
+            };
+        if unchanged {
+            return;
+        }
+
+        if let Some(key) = key {
+            if self.tables.len() >= MAX_TABLES && !self.tables.contains_key(key) {
+                self.tables.clear();
+            }
+        }
+
+        self.key = key.cloned();
+        self.r_type = meta.map(|meta| meta.r_type);
+        self.shown = meta.map(|meta| (meta.value.clone(), width));
+        (self.header, self.rows) = meta
+            .map(|meta| value_rows(&meta.value, width))
+            .unwrap_or_default();
+        self.find_matches();
+
+        // The value may have shrunk since the key was shown last
+        let len = self.rows.len();
+        let table = self.table();
+        if table.selected().is_some_and(|i| i >= len) {
+            table.select(len.checked_sub(1));
+        }
+    }
+
+    fn table(&mut self) -> &mut TableState {
+        self.tables
+            .entry(self.key.clone().unwrap_or_default())
+            .or_default()
+    }
+
+    /// The selected hash field, list element or member with a label for it. `None` for
+    /// strings, their rows are just the wrapped lines of the whole value.
+    pub fn selected_element(&mut self) -> Option<(String, String)> {
+        let index = self.table().selected()?;
+        let row = self.rows.get(index)?;
+
+        match self.r_type? {
+            RedisType::Hash => Some((format!("field {}", row[0]), row[1].clone())),
+            RedisType::List => Some((format!("[{index}]"), row[0].clone())),
+            RedisType::Set | RedisType::Zset => Some(("member".into(), row[0].clone())),
+            _ => None,
+        }
+    }
+
+    pub fn scroll_next(&mut self) {
+        let len = self.rows.len();
+        if len == 0 {
+            return;
+        }
+        let table = self.table();
+        let next = table.selected().map_or(0, |i| (i + 1) % len);
+        table.select(Some(next));
+    }
+
+    pub fn scroll_previous(&mut self) {
+        let len = self.rows.len();
+        if len == 0 {
+            return;
+        }
+        let table = self.table();
+        let previous = table.selected().map_or(len - 1, |i| (i + len - 1) % len);
+        table.select(Some(previous));
+    }
+
+    /// Selects the row at `y` rows below the top of the value, returns whether it hit one.
+    pub fn click(&mut self, y: u16) -> bool {
+        let Some(row) = y.checked_sub(self.area.y + HEADER_HEIGHT).map(usize::from) else {
+            return false;
+        };
+
+        let len = self.rows.len();
+        let table = self.table();
+        let index = table.offset() + row;
+        if index < len {
+            table.select(Some(index));
+        }
+        index < len
+    }
+
+    pub fn is_searching(&self) -> bool {
+        self.search.is_some()
+    }
+
+    pub fn start_search(&mut self) {
+        let mut text_area = TextArea::from([self.query.as_str()]);
+        text_area.move_cursor(tui_textarea::CursorMove::End);
+        text_area.set_block(
+            Block::default()
+                .border_style(config::get().colors.base04)
+                .border_type(BorderType::Rounded)
+                .borders(Borders::ALL)
+                .title("Search (<Enter> keep, <Esc> cancel)"),
+        );
+        self.search = Some(text_area);
+    }
+
+    /// Searches as the query is typed, jumping to the first match from the cursor on.
+    pub fn handle_search_key(&mut self, key: KeyEvent) {
+        let Some(ref mut text_area) = self.search else {
+            return;
+        };
+        if !text_area.input(key) {
+            return;
+        }
+
+        self.query = text_area.lines().join("");
+        self.find_matches();
+
+        let from = self.table().selected().unwrap_or_default();
+        if let Some(&first) = self
+            .matches
+            .iter()
+            .find(|&&i| i >= from)
+            .or(self.matches.first())
+        {
+            self.table().select(Some(first));
+        }
+    }
+
+    pub fn confirm_search(&mut self) {
+        self.search = None;
+    }
+
+    pub fn cancel_search(&mut self) {
+        self.search = None;
+        self.query.clear();
+        self.matches.clear();
+    }
+
+    fn find_matches(&mut self) {
+        let query = self.query.to_lowercase();
+        self.matches = if query.is_empty() {
+            Vec::new()
+        } else {
+            self.rows
+                .iter()
+                .enumerate()
+                .filter(|(_, row)| row.iter().any(|c| c.to_lowercase().contains(&query)))
+                .map(|(idx, _)| idx)
+                .collect()
+        };
+    }
+
+    pub fn next_match(&mut self) {
+        let current = self.table().selected();
+        let next = self
+            .matches
+            .iter()
+            .find(|&&i| current.map_or(true, |c| i > c))
+            .or(self.matches.first())
+            .copied();
+
+        if next.is_some() {
+            self.table().select(next);
+        }
+    }
+
+    pub fn previous_match(&mut self) {
+        let current = self.table().selected();
+        let previous = self
+            .matches
+            .iter()
+            .rev()
+            .find(|&&i| current.map_or(true, |c| i < c))
+            .or(self.matches.last())
+            .copied();
+
+        if previous.is_some() {
+            self.table().select(previous);
+        }
+    }
+
+    fn title(&mut self) -> String {
+        if self.query.is_empty() {
+            return "Key Details".into();
+        }
+
+        let current = self.table().selected();
+        let position = current
+            .and_then(|c| self.matches.iter().position(|&i| i == c))
+            .map_or_else(|| "-".into(), |p| (p + 1).to_string());
+
+        format!(
+            "Key Details /{} ({position}/{})",
+            self.query,
+            self.matches.len()
+        )
+    }
+}
+
+pub struct DetailsWidget<'a> {
+    meta: Option<&'a KeyMeta>,
+    focused: bool,
+}
+
+impl<'a> DetailsWidget<'a> {
+    pub fn new(meta: Option<&'a KeyMeta>, focused: bool) -> Self {
+        Self { meta, focused }
+    }
+}
+
+impl StatefulWidget for DetailsWidget<'_> {
+    type State = Details;
+
+    fn render(self, area: Rect, buf: &mut ratatui::prelude::Buffer, state: &mut Self::State) {
+        let colors = config::get().colors;
+
+        let Some(meta) = self.meta else {
+            state.update(None, 0);
+            return;
+        };
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(if self.focused {
+                colors.base0d
+            } else {
+                colors.base04
+            });
+
+        let inner = block.inner(area);
+
+        let [meta_area, view_area, search_area] = Layout::vertical([
+            Constraint::Max(6),
+            Constraint::Fill(1),
+            Constraint::Length(if state.is_searching() { 3 } else { 0 }),
+        ])
+        .areas(inner);
+
+        let width = usize::from(view_area.width).saturating_sub(CHROME_WIDTH);
+        state.update(Some(meta), width);
+        state.area = view_area;
+
+        block.title(state.title()).render(area, buf);
+
+        let key_info = format!(
+            "Key: {}\nType: {:?}\nTTL: {}\nSize: {}",
+            meta.key,
+            meta.r_type,
+            meta.ttl,
+            unsafe { Byte::from_u128_unsafe(meta.size) }.get_appropriate_unit(UnitType::Binary)
+        );
+
+        Paragraph::new(key_info)
+            .wrap(Wrap { trim: true })
+            .render(meta_area, buf);
+
+        if let Some(ref text_area) = state.search {
+            text_area.render(search_area, buf);
+        }
+
+        if state.header.is_empty() {
+            return;
+        }
+
+        let header = Row::new(
+            state
+                .header
+                .iter()
+                .map(|h| Cell::from(Line::from(*h).bold())),
+        )
+        .top_margin(1)
+        .bottom_margin(1)
+        .fg(colors.base04)
+        .bg(colors.base02);
+
+        let widths = vec![Constraint::Fill(1); state.header.len()];
+        let rows = state.rows.iter().enumerate().map(|(idx, row)| {
+            let cells = row.iter().cloned().map(Cell::from);
+            let row = Row::new(cells).bg(colors.base00);
+
+            if state.matches.contains(&idx) {
+                row.fg(colors.base0a)
+            } else {
+                row.fg(colors.base04)
+            }
+        });
+
+        let table = Table::new(rows, widths)
+            .header(header)
+            .highlight_symbol(HIGHLIGHT_SYMBOL)
+            .highlight_style(colors.base05)
+            .highlight_spacing(HighlightSpacing::Always);
+
+        let len = state.rows.len();
+        let table_state = state.table();
+        StatefulWidget::render(table, view_area, buf, table_state);
+
+        let position = table_state.selected().unwrap_or(table_state.offset());
+        let mut scrollbar = ScrollbarState::new(len).position(position);
+        let [_, scrollbar_area] =
+            Layout::vertical([Constraint::Length(HEADER_HEIGHT), Constraint::Fill(1)])
+                .areas(view_area);
+
+        StatefulWidget::render(
+            Scrollbar::new(ScrollbarOrientation::VerticalRight)
+                .begin_symbol(None)
+                .end_symbol(None),
+            scrollbar_area,
+            buf,
+            &mut scrollbar,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_value_rows() {
+        let (header, rows) = value_rows(&KeyValue::String("abcdef\n\nxy".into()), 4);
+        assert_eq!(header, ["Value"]);
+        assert_eq!(rows, [["abcd"], ["ef"], [""], ["xy"]]);
+
+        let hash = KeyValue::Hash(HashMap::from([
+            ("b".to_owned(), "2".to_owned()),
+            ("a".to_owned(), "1".to_owned()),
+        ]));
+        assert_eq!(value_rows(&hash, 10).1, [["a", "1"], ["b", "2"]]);
+    }
+
+    #[test]
+    fn test_update_only_on_change() {
+        let meta = KeyMeta {
+            key: "greeting".into(),
+            r_type: RedisType::String,
+            size: 0,
+            ttl: -1,
+            value: KeyValue::String("hello".into()),
+            encoding: None,
+            idle: None,
+            freq: None,
+        };
+
+        let mut details = Details::new();
+        details.update(Some(&meta), 10);
+        details.rows.push(vec!["kept".into()]);
+        details.update(Some(&meta), 10);
+        assert_eq!(details.rows, [["hello"], ["kept"]]);
+
+        details.update(Some(&meta), 20);
+        assert_eq!(details.rows, [["hello"]]);
+
+        for i in 0..MAX_TABLES {
+            details.tables.insert(i.to_string(), TableState::default());
+        }
+        let other = KeyMeta {
+            key: "other".into(),
+            ..meta
+        };
+        details.update(Some(&other), 20);
+        assert_eq!(details.tables.len(), 1);
+    }
+
+    #[test]
+    fn test_search() {
+        let mut details = Details::new();
+        details.rows = ["apple", "banana", "cherry", "pineapple"]
+            .map(|s| vec![s.to_owned()])
+            .to_vec();
+        details.query = "APP".into();
+        details.find_matches();
+        assert_eq!(details.matches, [0, 3]);
+
+        details.next_match();
+        assert_eq!(details.table().selected(), Some(0));
+        details.next_match();
+        assert_eq!(details.table().selected(), Some(3));
+        details.next_match();
+        assert_eq!(details.table().selected(), Some(0));
+        details.previous_match();
+        assert_eq!(details.table().selected(), Some(3));
+    }
+}
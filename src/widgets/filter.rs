@@ -0,0 +1,334 @@
+use regex::Regex;
+use strum::Display;
+
+/// How the live filter interprets what is typed.
+#[derive(Debug, Default, Display, Clone, Copy, PartialEq, Eq)]
+pub enum MatchMode {
+    /// Characters in order, not necessarily next to each other, ignoring case.
+    #[default]
+    Fuzzy,
+    Substring,
+    /// Redis `MATCH` syntax: `*`, `?`, `[a-z]` and `\` escapes.
+    Glob,
+    Regex,
+}
+
+impl MatchMode {
+    pub fn next(self) -> Self {
+        match self {
+            Self::Fuzzy => Self::Substring,
+            Self::Substring => Self::Glob,
+            Self::Glob => Self::Regex,
+            Self::Regex => Self::Fuzzy,
+        }
+    }
+}
+
+/// Query of the live key filter, matched locally against loaded keys and turned into
+/// a `SCAN MATCH` pattern for the server.
+#[derive(Debug, Clone, Default)]
+pub struct KeyFilter {
+    mode: MatchMode,
+    query: String,
+    regex: Option<Regex>,
+    error: Option<String>,
+}
+
+impl KeyFilter {
+    pub fn new(mode: MatchMode, query: impl Into<String>) -> Self {
+        let mut filter = Self {
+            mode,
+            query: query.into(),
+            regex: None,
+            error: None,
+        };
+        filter.compile();
+        filter
+    }
+
+    fn compile(&mut self) {
+        self.regex = None;
+        self.error = None;
+
+        if self.mode == MatchMode::Regex && !self.query.is_empty() {
+            match Regex::new(&self.query) {
+                Ok(regex) => self.regex = Some(regex),
+                Err(err) => self.error = Some(err.to_string()),
+            }
+        }
+    }
+
+    pub fn mode(&self) -> MatchMode {
+        self.mode
+    }
+
+    pub fn query(&self) -> &str {
+        &self.query
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.query.is_empty()
+    }
+
+    /// Why the query can not be used, e.g. an invalid regex.
+    pub fn error(&self) -> Option<&str> {
+        self.error.as_deref()
+    }
+
+    /// Char positions of `key` to highlight, `None` when the key does not match.
+    pub fn matches(&self, key: &str) -> Option<Vec<usize>> {
+        if self.query.is_empty() {
+            return Some(Vec::new());
+        }
+
+        match self.mode {
+            MatchMode::Fuzzy => fuzzy_match(&self.query, key),
+            MatchMode::Substring => substring_match(&self.query, key),
+            MatchMode::Glob => {
+                let pattern: Vec<char> = self.query.chars().collect();
+                let key: Vec<char> = key.chars().collect();
+                let mut hits = Vec::new();
+                glob_match(&pattern, &key, 0, &mut hits).then_some(hits)
+            }
+            MatchMode::Regex => {
+                let found = self.regex.as_ref()?.find(key)?;
+                Some(char_range(key, found.start(), found.end()))
+            }
+        }
+    }
+
+    /// Pattern for `SCAN MATCH` selecting a superset of the matching keys, `None` to scan everything.
+    pub fn scan_pattern(&self) -> Option<String> {
+        if self.query.is_empty() {
+            return None;
+        }
+
+        match self.mode {
+            MatchMode::Fuzzy => {
+                let parts: Vec<String> = self.query.chars().map(ignore_case).collect();
+                Some(format!("*{}*", parts.join("*")))
+            }
+            MatchMode::Substring => {
+                let part: String = self.query.chars().map(ignore_case).collect();
+                Some(format!("*{part}*"))
+            }
+            MatchMode::Glob => Some(self.query.clone()),
+            MatchMode::Regex => {
+                let prefix = regex_literal_prefix(&self.query)?;
+                Some(format!("{}*", glob_escape(&prefix)))
+            }
+        }
+    }
+}
+
+/// Escapes the glob metacharacters of a literal for `SCAN MATCH`.
+pub fn glob_escape(literal: &str) -> String {
+    let mut escaped = String::with_capacity(literal.len());
+    for c in literal.chars() {
+        if matches!(c, '*' | '?' | '[' | ']' | '\\') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// `a` -> `[aA]`, so that the server side match ignores case like the local one.
+fn ignore_case(c: char) -> String {
+    let (lower, upper) = (c.to_ascii_lowercase(), c.to_ascii_uppercase());
+    if lower == upper {
+        glob_escape(&c.to_string())
+    } else {
+        format!("[{lower}{upper}]")
+    }
+}
+
+fn fuzzy_match(query: &str, key: &str) -> Option<Vec<usize>> {
+    let mut query = query.chars().map(|c| c.to_ascii_lowercase()).peekable();
+    let mut hits = Vec::new();
+
+    for (idx, c) in key.chars().enumerate() {
+        match query.peek() {
+            Some(&q) if q == c.to_ascii_lowercase() => {
+                hits.push(idx);
+                query.next();
+            }
+            Some(_) => {}
+            None => break,
+        }
+    }
+
+    query.peek().is_none().then_some(hits)
+}
+
+fn substring_match(query: &str, key: &str) -> Option<Vec<usize>> {
+    let key_lower = key.to_ascii_lowercase();
+    let start = key_lower.find(&query.to_ascii_lowercase())?;
+    Some(char_range(key, start, start + query.len()))
+}
+
+/// Char positions covered by a byte range of `s`.
+fn char_range(s: &str, start: usize, end: usize) -> Vec<usize> {
+    s.char_indices()
+        .enumerate()
+        .filter(|(_, (byte, _))| (start..end).contains(byte))
+        .map(|(idx, _)| idx)
+        .collect()
+}
+
+/// Redis `stringmatchlen` semantics, recording the positions matched by literal characters.
+fn glob_match(pattern: &[char], key: &[char], pos: usize, hits: &mut Vec<usize>) -> bool {
+    let Some(&first) = pattern.first() else {
+        return key.is_empty();
+    };
+
+    if first == '*' {
+        let rest = &pattern[1..];
+        for skip in 0..=key.len() {
+            let mark = hits.len();
+            if glob_match(rest, &key[skip..], pos + skip, hits) {
+                return true;
+            }
+            hits.truncate(mark);
+        }
+        return false;
+    }
+
+    let Some(&c) = key.first() else {
+        return false;
+    };
+
+    let (matched, consumed, literal) = match first {
+        '?' => (true, 1, false),
+        '[' => {
+            let (matched, consumed) = class_match(&pattern[1..], c);
+            (matched, consumed + 1, false)
+        }
+        '\\' if pattern.len() > 1 => (pattern[1] == c, 2, true),
+        _ => (first == c, 1, true),
+    };
+
+    if !matched {
+        return false;
+    }
+
+    if literal {
+        hits.push(pos);
+    }
+    glob_match(&pattern[consumed..], &key[1..], pos + 1, hits)
+}
+
+/// Matches `c` against a `[...]` class starting right after the bracket, returns the number
+/// of pattern chars consumed including the closing bracket.
+fn class_match(pattern: &[char], c: char) -> (bool, usize) {
+    let negate = pattern.first() == Some(&'^');
+    let mut i = usize::from(negate);
+    let mut matched = false;
+
+    while i < pattern.len() && pattern[i] != ']' {
+        if pattern[i] == '\\' && i + 1 < pattern.len() {
+            matched |= pattern[i + 1] == c;
+            i += 2;
+        } else if i + 2 < pattern.len() && pattern[i + 1] == '-' && pattern[i + 2] != ']' {
+            let (start, end) = (
+                pattern[i].min(pattern[i + 2]),
+                pattern[i].max(pattern[i + 2]),
+            );
+            matched |= (start..=end).contains(&c);
+            i += 3;
+        } else {
+            matched |= pattern[i] == c;
+            i += 1;
+        }
+    }
+
+    // An unterminated class runs to the end of the pattern, like in Redis
+    let consumed = (i + 1).min(pattern.len());
+    (matched != negate, consumed)
+}
+
+/// Whether the regex has a `|` outside of a character class, an alternative that may not
+/// start with the prefix of the first one.
+fn has_alternation(regex: &str) -> bool {
+    let mut chars = regex.chars();
+    let mut in_class = false;
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                chars.next();
+            }
+            '[' => in_class = true,
+            ']' => in_class = false,
+            '|' if !in_class => return true,
+            _ => {}
+        }
+    }
+    false
+}
+
+/// The literal text a `^`-anchored regex starts with, if any.
+fn regex_literal_prefix(regex: &str) -> Option<String> {
+    if has_alternation(regex) {
+        return None;
+    }
+    let rest = regex.strip_prefix('^')?;
+    let mut prefix: String = rest
+        .chars()
+        .take_while(|c| !".^$*+?()[]{}|\\".contains(*c))
+        .collect();
+
+    // `ab?` or `ab*` make the last literal optional
+    if rest[prefix.len()..].starts_with(['?', '*', '{']) {
+        prefix.pop();
+    }
+
+    (!prefix.is_empty()).then_some(prefix)
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_matches() {
+        let fuzzy = KeyFilter::new(MatchMode::Fuzzy, "usp");
+        assert_eq!(fuzzy.matches("app:user:profile"), Some(vec![4, 5, 9]));
+        assert_eq!(fuzzy.matches("session"), None);
+
+        let substring = KeyFilter::new(MatchMode::Substring, "USER");
+        assert_eq!(substring.matches("app:user:1"), Some(vec![4, 5, 6, 7]));
+
+        let glob = KeyFilter::new(MatchMode::Glob, "a*:[0-9]?");
+        assert_eq!(glob.matches("app:12"), Some(vec![0, 3]));
+        assert_eq!(glob.matches("app:x2"), None);
+        assert!(KeyFilter::new(MatchMode::Glob, "h[^e]llo")
+            .matches("hallo")
+            .is_some());
+
+        let regex = KeyFilter::new(MatchMode::Regex, r"user:\d+$");
+        assert_eq!(regex.matches("ü:user:42"), Some(vec![2, 3, 4, 5, 6, 7, 8]));
+        assert!(KeyFilter::new(MatchMode::Regex, "(").error().is_some());
+    }
+
+    #[test]
+    fn test_scan_pattern() {
+        let pattern = |mode, query| KeyFilter::new(mode, query).scan_pattern();
+
+        assert_eq!(pattern(MatchMode::Fuzzy, "a1"), Some("*[aA]*1*".into()));
+        assert_eq!(
+            pattern(MatchMode::Substring, "a*"),
+            Some("*[aA]\\**".into())
+        );
+        assert_eq!(pattern(MatchMode::Glob, "a*"), Some("a*".into()));
+        assert_eq!(pattern(MatchMode::Regex, "^app:us?"), Some("app:u*".into()));
+        assert_eq!(pattern(MatchMode::Regex, "user$"), None);
+        assert_eq!(pattern(MatchMode::Regex, "^app|session"), None);
+        assert_eq!(
+            pattern(MatchMode::Regex, r"^app[|]\|x"),
+            Some("app*".into())
+        );
+        assert_eq!(pattern(MatchMode::Fuzzy, ""), None);
+    }
+}
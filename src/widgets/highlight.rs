@@ -0,0 +1,265 @@
+use ratatui::{
+    style::{Color, Style},
+    text::Span,
+};
+
+use crate::config;
+
+/// Format of a value, guessed from its content.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Syntax {
+    Json,
+    Xml,
+    Yaml,
+    Plain,
+}
+
+impl Syntax {
+    pub fn detect(text: &str) -> Self {
+        let trimmed = text.trim_start();
+
+        if trimmed.starts_with(['{', '['])
+            && serde_json::from_str::<serde_json::Value>(text).is_ok()
+        {
+            Self::Json
+        } else if trimmed.starts_with('<') && trimmed.trim_end().ends_with('>') {
+            Self::Xml
+        } else if is_yaml(trimmed) {
+            Self::Yaml
+        } else {
+            Self::Plain
+        }
+    }
+
+    /// Splits a line into styled pieces, each line is highlighted on its own.
+    pub fn highlight(self, line: &str) -> Vec<Span<'static>> {
+        match self {
+            Self::Json => json_line(line),
+            Self::Xml => xml_line(line),
+            Self::Yaml => yaml_line(line),
+            Self::Plain => vec![Span::raw(line.to_owned())],
+        }
+    }
+}
+
+/// A document marker, or `key: value` / `- item` lines from the start on.
+fn is_yaml(text: &str) -> bool {
+    let mut lines = text
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'));
+
+    let Some(first) = lines.next() else {
+        return false;
+    };
+
+    let entry = |line: &str| {
+        line.starts_with("- ")
+            || line.split_once(':').is_some_and(|(key, rest)| {
+                !key.is_empty()
+                    && !key.contains(char::is_whitespace)
+                    && (rest.is_empty() || rest.starts_with(' '))
+            })
+    };
+
+    first == "---" || (entry(first) && text.contains('\n') && lines.take(3).all(entry))
+}
+
+fn styled(text: &str, color: Color) -> Span<'static> {
+    Span::styled(text.to_owned(), Style::default().fg(color))
+}
+
+fn json_line(line: &str) -> Vec<Span<'static>> {
+    let colors = config::get().colors;
+    let chars: Vec<char> = line.chars().collect();
+    let mut spans = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let start = i;
+        let c = chars[i];
+
+        let color = if c == '"' {
+            i += 1;
+            while i < chars.len() && chars[i] != '"' {
+                i += if chars[i] == '\\' { 2 } else { 1 };
+            }
+            i = (i + 1).min(chars.len());
+
+            // Object keys are followed by a colon
+            let rest: String = chars[i..].iter().collect();
+            if rest.trim_start().starts_with(':') {
+                colors.base0d
+            } else {
+                colors.base0b
+            }
+        } else if c == '-' || c.is_ascii_digit() {
+            while i < chars.len() && (chars[i].is_ascii_alphanumeric() || "+-.".contains(chars[i]))
+            {
+                i += 1;
+            }
+            colors.base09
+        } else if c.is_ascii_alphabetic() {
+            while i < chars.len() && chars[i].is_ascii_alphabetic() {
+                i += 1;
+            }
+            colors.base0e
+        } else {
+            while i < chars.len() && !"\"-".contains(chars[i]) && !chars[i].is_ascii_alphanumeric()
+            {
+                i += 1;
+            }
+            colors.base04
+        };
+
+        spans.push(styled(&chars[start..i].iter().collect::<String>(), color));
+    }
+
+    spans
+}
+
+fn xml_line(line: &str) -> Vec<Span<'static>> {
+    let colors = config::get().colors;
+    let mut spans = Vec::new();
+    let mut rest = line;
+
+    while !rest.is_empty() {
+        let Some(open) = rest.find('<') else {
+            spans.push(Span::raw(rest.to_owned()));
+            break;
+        };
+        if open > 0 {
+            spans.push(Span::raw(rest[..open].to_owned()));
+        }
+        rest = &rest[open..];
+
+        if rest.starts_with("<!--") {
+            let end = rest.find("-->").map_or(rest.len(), |end| end + 3);
+            spans.push(styled(&rest[..end], colors.base03));
+            rest = &rest[end..];
+            continue;
+        }
+
+        let end = rest.find('>').map_or(rest.len(), |end| end + 1);
+        spans.extend(xml_tag(&rest[..end]));
+        rest = &rest[end..];
+    }
+
+    spans
+}
+
+/// `<name attr="value">` with the name, the attribute names and the values apart.
+fn xml_tag(tag: &str) -> Vec<Span<'static>> {
+    let colors = config::get().colors;
+
+    let skip = if tag.starts_with("</") { 2 } else { 1 };
+    let name_end = tag[skip..]
+        .find(|c: char| c.is_whitespace() || c == '>' || c == '/')
+        .map_or(tag.len(), |end| end + skip);
+
+    let rest = &tag[name_end..];
+    let closing = if rest.ends_with("/>") {
+        "/>"
+    } else if rest.ends_with('>') {
+        ">"
+    } else {
+        ""
+    };
+
+    let mut spans = vec![styled(&tag[..name_end], colors.base0d)];
+    let mut body = &rest[..rest.len() - closing.len()];
+
+    while !body.is_empty() {
+        let (end, color) = match body.strip_prefix('"') {
+            Some(quoted) => (
+                quoted.find('"').map_or(body.len(), |end| end + 2),
+                colors.base0b,
+            ),
+            None => (body.find('"').unwrap_or(body.len()), colors.base0a),
+        };
+        spans.push(styled(&body[..end], color));
+        body = &body[end..];
+    }
+
+    if !closing.is_empty() {
+        spans.push(styled(closing, colors.base0d));
+    }
+    spans
+}
+
+fn yaml_line(line: &str) -> Vec<Span<'static>> {
+    let colors = config::get().colors;
+    let trimmed = line.trim_start();
+    let indent = &line[..line.len() - trimmed.len()];
+
+    if trimmed.starts_with('#') || trimmed == "---" {
+        return vec![Span::raw(indent.to_owned()), styled(trimmed, colors.base03)];
+    }
+
+    let mut spans = vec![Span::raw(indent.to_owned())];
+    let mut rest = trimmed;
+
+    if let Some(item) = rest.strip_prefix("- ") {
+        spans.push(styled("- ", colors.base0a));
+        rest = item;
+    }
+
+    if let Some(colon) = rest
+        .find(": ")
+        .or_else(|| rest.strip_suffix(':').map(str::len))
+    {
+        spans.push(styled(&rest[..colon], colors.base0d));
+        spans.push(styled(":", colors.base04));
+        rest = &rest[colon + 1..];
+    }
+
+    let value = rest.trim();
+    let color = if value.parse::<f64>().is_ok() {
+        colors.base09
+    } else if matches!(value, "true" | "false" | "null" | "~") {
+        colors.base0e
+    } else if value.starts_with(['"', '\'']) {
+        colors.base0b
+    } else {
+        colors.base05
+    };
+    spans.push(styled(rest, color));
+
+    spans
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn texts(spans: &[Span]) -> Vec<String> {
+        spans.iter().map(|span| span.content.to_string()).collect()
+    }
+
+    #[test]
+    fn test_detect() {
+        assert_eq!(Syntax::detect(r#" {"a": [1, 2]}"#), Syntax::Json);
+        assert_eq!(Syntax::detect("{not json"), Syntax::Plain);
+        assert_eq!(Syntax::detect("<a><b/></a>"), Syntax::Xml);
+        assert_eq!(Syntax::detect("name: app\nport: 80\n"), Syntax::Yaml);
+        assert_eq!(Syntax::detect("Error: disk full"), Syntax::Plain);
+    }
+
+    #[test]
+    fn test_highlight() {
+        assert_eq!(
+            texts(&Syntax::Json.highlight(r#"  "id": -1.5, "ok": true"#)),
+            ["  ", "\"id\"", ": ", "-1.5", ", ", "\"ok\"", ": ", "true"]
+        );
+        assert_eq!(
+            texts(&Syntax::Xml.highlight(r#"<a href="x">t</a>"#)),
+            ["<a", " href=", "\"x\"", ">", "t", "</a", ">"]
+        );
+        assert_eq!(
+            texts(&Syntax::Yaml.highlight("  - port: 80")),
+            ["  ", "- ", "port", ":", " 80"]
+        );
+    }
+}
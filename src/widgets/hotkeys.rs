@@ -0,0 +1,356 @@
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    style::Stylize,
+    widgets::{
+        Block, BorderType, Borders, HighlightSpacing, Paragraph, Row, StatefulWidget, Table,
+        TableState, Tabs, Widget,
+    },
+};
+use serde_json::{json, Value};
+
+use crate::{
+    config,
+    redis_client::{
+        hotkeys::{HotKeysReport, HotKeysSource, MONITOR_SECONDS},
+        memory::prefix,
+    },
+};
+
+const HIGHLIGHT_SYMBOL: &str = " >> ";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum View {
+    Keys,
+    Prefixes,
+}
+
+/// What drilling down into the row under the cursor leads to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DrillDown {
+    Key(String),
+    /// The hot keys of the prefix, shown in place.
+    Prefix(String),
+}
+
+fn prefix_label(prefix: &str) -> &str {
+    if prefix.is_empty() {
+        "(no prefix)"
+    } else {
+        prefix
+    }
+}
+
+/// The keys and prefixes ranked by accesses, hottest first.
+pub struct HotKeys {
+    report: HotKeysReport,
+    /// Whether sampling is on its way.
+    running: bool,
+    view: View,
+    /// Only the keys of this prefix are listed, after drilling down into it.
+    prefix: Option<String>,
+    table: TableState,
+}
+
+impl HotKeys {
+    pub fn new() -> Self {
+        Self {
+            report: HotKeysReport::default(),
+            running: false,
+            view: View::Keys,
+            prefix: None,
+            table: TableState::default(),
+        }
+    }
+
+    /// Whether sampling was ever started.
+    pub fn is_started(&self) -> bool {
+        self.running || self.report.sampled > 0 || self.report.finished
+    }
+
+    /// Forgets the ranking, for a new sampling.
+    pub fn start(&mut self) {
+        self.running = true;
+        self.report = HotKeysReport::default();
+        self.table.select(None);
+    }
+
+    pub fn stop(&mut self) {
+        self.running = false;
+    }
+
+    pub fn set_report(&mut self, report: HotKeysReport) {
+        // Progress of a stopped sampling still on its way is dropped
+        if !self.running {
+            return;
+        }
+        self.running = !report.finished;
+        self.report = report;
+
+        if self.table.selected().is_none() && self.len() > 0 {
+            self.table.select(Some(0));
+        }
+    }
+
+    fn keys(&self) -> impl Iterator<Item = &(String, u64)> {
+        let delimiter = &config::get().key_delimiter;
+        self.report
+            .keys
+            .iter()
+            .filter(move |(key, _)| match self.prefix {
+                Some(ref selected) => prefix(key, delimiter) == selected,
+                None => true,
+            })
+    }
+
+    fn len(&self) -> usize {
+        match self.view {
+            View::Keys => self.keys().count(),
+            View::Prefixes => self.report.prefixes.len(),
+        }
+    }
+
+    /// Switches between the keys and the prefixes, showing every key again.
+    pub fn next_view(&mut self) {
+        self.view = match self.view {
+            View::Keys => View::Prefixes,
+            View::Prefixes => View::Keys,
+        };
+        self.prefix = None;
+        self.table.select((self.len() > 0).then_some(0));
+    }
+
+    /// The key under the cursor, or the prefix, whose keys are then listed.
+    pub fn drill_down(&mut self) -> Option<DrillDown> {
+        let selected = self.table.selected()?;
+        match self.view {
+            View::Keys => self
+                .keys()
+                .nth(selected)
+                .map(|(key, _)| DrillDown::Key(key.clone())),
+            View::Prefixes => {
+                let prefix = self.report.prefixes.get(selected)?.prefix.clone();
+                self.view = View::Keys;
+                self.prefix = Some(prefix.clone());
+                self.table.select((self.len() > 0).then_some(0));
+                Some(DrillDown::Prefix(prefix))
+            }
+        }
+    }
+
+    fn hits_header(&self) -> &'static str {
+        match self.report.source {
+            HotKeysSource::Lfu => "Frequency",
+            HotKeysSource::Monitor => "Accesses",
+        }
+    }
+
+    /// The name, headers and rows of the current table, for exporting it.
+    pub fn export(&self) -> (&'static str, Vec<&'static str>, Vec<Vec<Value>>) {
+        match self.view {
+            View::Keys => (
+                "hot-keys",
+                vec!["Key", self.hits_header()],
+                self.keys()
+                    .map(|(key, hits)| vec![json!(key), json!(hits)])
+                    .collect(),
+            ),
+            View::Prefixes => (
+                "hot-prefixes",
+                vec!["Prefix", "Keys", self.hits_header()],
+                self.report
+                    .prefixes
+                    .iter()
+                    .map(|p| vec![json!(p.prefix), json!(p.keys), json!(p.hits)])
+                    .collect(),
+            ),
+        }
+    }
+
+    pub fn scroll_next(&mut self) {
+        let len = self.len();
+        if len == 0 {
+            return;
+        }
+        let next = self.table.selected().map_or(0, |i| (i + 1) % len);
+        self.table.select(Some(next));
+    }
+
+    pub fn scroll_previous(&mut self) {
+        let len = self.len();
+        if len == 0 {
+            return;
+        }
+        let previous = self
+            .table
+            .selected()
+            .map_or(len - 1, |i| (i + len - 1) % len);
+        self.table.select(Some(previous));
+    }
+}
+
+pub struct HotKeysWidget;
+
+impl StatefulWidget for HotKeysWidget {
+    type State = HotKeys;
+
+    fn render(self, area: Rect, buf: &mut ratatui::prelude::Buffer, state: &mut Self::State) {
+        let colors = config::get().colors;
+
+        let block = Block::new()
+            .bg(colors.base00)
+            .fg(colors.base04)
+            .border_type(BorderType::Rounded)
+            .borders(Borders::ALL)
+            .title(
+                "Hot keys <Tab> keys/prefixes, <Enter> drill down, <e/E> export CSV/JSON, \
+                 <r> restart, <x> stop",
+            );
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        let [status_area, tabs_area, table_area] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Length(2),
+            Constraint::Fill(1),
+        ])
+        .areas(inner);
+
+        let report = &state.report;
+        let progress = match (state.running, report.finished) {
+            (true, _) => "running",
+            (false, true) => "done",
+            (false, false) if report.sampled > 0 => "stopped",
+            (false, false) => "not started",
+        };
+        let status = match report.source {
+            HotKeysSource::Lfu => format!(
+                "{progress}: OBJECT FREQ of {} keys, logarithmic LFU counters",
+                report.sampled
+            ),
+            HotKeysSource::Monitor => format!(
+                "{progress}: {} commands seen on MONITOR in {MONITOR_SECONDS}s, \
+                 the eviction policy is not LFU",
+                report.sampled
+            ),
+        };
+        Paragraph::new(status)
+            .fg(colors.base05)
+            .render(status_area, buf);
+
+        let keys_title = match state.prefix {
+            Some(ref prefix) => format!("Keys of {}", prefix_label(prefix)),
+            None => "Keys".into(),
+        };
+        Tabs::new([keys_title, "Prefixes".into()])
+            .select(usize::from(state.view == View::Prefixes))
+            .highlight_style(colors.base0d)
+            .fg(colors.base04)
+            .block(
+                Block::new()
+                    .borders(Borders::BOTTOM)
+                    .border_style(colors.base03),
+            )
+            .render(tabs_area, buf);
+
+        let (header, rows, widths): (Row, Vec<Row>, Vec<Constraint>) = match state.view {
+            View::Keys => (
+                Row::new(["#", "Key", state.hits_header()]),
+                state
+                    .keys()
+                    .enumerate()
+                    .map(|(i, (key, hits))| {
+                        Row::new([(i + 1).to_string(), key.clone(), hits.to_string()])
+                    })
+                    .collect(),
+                vec![
+                    Constraint::Length(6),
+                    Constraint::Fill(1),
+                    Constraint::Length(10),
+                ],
+            ),
+            View::Prefixes => (
+                Row::new(["#", "Prefix", "Keys", state.hits_header()]),
+                report
+                    .prefixes
+                    .iter()
+                    .enumerate()
+                    .map(|(i, p)| {
+                        Row::new([
+                            (i + 1).to_string(),
+                            prefix_label(&p.prefix).to_string(),
+                            p.keys.to_string(),
+                            p.hits.to_string(),
+                        ])
+                    })
+                    .collect(),
+                vec![
+                    Constraint::Length(6),
+                    Constraint::Fill(1),
+                    Constraint::Length(10),
+                    Constraint::Length(10),
+                ],
+            ),
+        };
+        let rows = rows.into_iter().enumerate().map(|(i, row)| {
+            row.fg(colors.base04).bg(if i % 2 == 0 {
+                colors.base00
+            } else {
+                colors.base01
+            })
+        });
+
+        let table = Table::new(rows, widths)
+            .header(header.bold().fg(colors.base04).bg(colors.base02))
+            .highlight_symbol(HIGHLIGHT_SYMBOL)
+            .highlight_style(colors.base05)
+            .highlight_spacing(HighlightSpacing::Always);
+        StatefulWidget::render(table, table_area, buf, &mut state.table);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_drill_down() {
+        let counts = HashMap::from([
+            ("user:1".to_string(), 5),
+            ("user:2".to_string(), 3),
+            ("counter".to_string(), 7),
+        ]);
+        let mut report = HotKeysReport::rank(HotKeysSource::Monitor, 15, &counts, ":");
+        report.finished = true;
+
+        let mut hot_keys = HotKeys::new();
+        hot_keys.start();
+        hot_keys.set_report(report);
+        assert!(!hot_keys.running);
+        assert_eq!(
+            hot_keys.drill_down(),
+            Some(DrillDown::Key("counter".into()))
+        );
+
+        hot_keys.next_view();
+        assert_eq!(
+            hot_keys.drill_down(),
+            Some(DrillDown::Prefix("user:".into()))
+        );
+        let (name, _, rows) = hot_keys.export();
+        assert_eq!(name, "hot-keys");
+        assert_eq!(
+            rows,
+            [
+                vec![json!("user:1"), json!(5)],
+                vec![json!("user:2"), json!(3)]
+            ]
+        );
+
+        hot_keys.scroll_next();
+        assert_eq!(hot_keys.drill_down(), Some(DrillDown::Key("user:2".into())));
+    }
+}
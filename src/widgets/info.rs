@@ -2,15 +2,67 @@ use std::sync::{Arc, Mutex};
 
 use ratatui::{prelude::*, widgets::*};
 
-use crate::{config, redis_client::types::RedisInfo};
+use crate::{
+    config::{self, Profile},
+    redis_client::{info::RedisInfo, latency::PingStats},
+    widgets::latency::round_trip,
+};
 
 pub struct Info {
     info: Arc<Mutex<Option<RedisInfo>>>,
+    profile: Profile,
+    ping: Arc<Mutex<PingStats>>,
 }
 
 impl Info {
     pub fn new(info: Arc<Mutex<Option<RedisInfo>>>) -> Self {
-        Self { info }
+        Self {
+            info,
+            profile: Profile::default(),
+            ping: Arc::default(),
+        }
+    }
+
+    #[must_use]
+    pub fn profile(mut self, profile: Profile) -> Self {
+        self.profile = profile;
+        self
+    }
+
+    #[must_use]
+    pub fn ping(mut self, ping: Arc<Mutex<PingStats>>) -> Self {
+        self.ping = ping;
+        self
+    }
+
+    fn round_trip(&self) -> String {
+        let ping = self.ping.lock().unwrap();
+        match (ping.latest(), ping.average()) {
+            (Some(latest), Some(average)) => {
+                format!("ping {} (avg {})", round_trip(latest), round_trip(average))
+            }
+            _ => "ping -".into(),
+        }
+    }
+
+    fn banner(&self) -> Option<Line<'static>> {
+        let colors = config::get().colors;
+        let mut spans = Vec::new();
+
+        if self.profile.production {
+            spans.push(
+                format!(" PRODUCTION: {} ", self.profile.name)
+                    .fg(colors.base00)
+                    .bg(colors.base08)
+                    .bold(),
+            );
+        }
+
+        if self.profile.read_only {
+            spans.push(" READ-ONLY ".fg(colors.base00).bg(colors.base09).bold());
+        }
+
+        (!spans.is_empty()).then(|| Line::from(spans).right_aligned())
     }
 }
 
@@ -20,14 +72,19 @@ impl StatefulWidget for InfoWidget {
     type State = Info;
 
     fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
-        Block::new()
+        let mut block = Block::new()
             .fg(config::get().colors.base04)
             .bg(config::get().colors.base00)
             .borders(Borders::ALL)
             .title("Info")
             .title_alignment(Alignment::Left)
-            .border_type(BorderType::Rounded)
-            .render(area, buf);
+            .border_type(BorderType::Rounded);
+
+        if let Some(banner) = state.banner() {
+            block = block.title(banner);
+        }
+
+        block.render(area, buf);
 
         let common_info = { state.info.lock().unwrap().as_ref().map(Clone::clone) };
 
@@ -35,7 +92,7 @@ impl StatefulWidget for InfoWidget {
             .flex(layout::Flex::Center)
             .areas(area);
 
-        let [version_area, _, cpu_area] =
+        let [version_area, ping_area, cpu_area] =
             Layout::horizontal([Constraint::Min(0), Constraint::Fill(1), Constraint::Min(0)])
                 .flex(layout::Flex::Center)
                 .horizontal_margin(3)
@@ -78,5 +135,9 @@ impl StatefulWidget for InfoWidget {
         Paragraph::new(clients)
             .alignment(Alignment::Center)
             .render(clients_area, buf);
+
+        Paragraph::new(state.round_trip())
+            .alignment(Alignment::Center)
+            .render(ping_area, buf);
     }
 }
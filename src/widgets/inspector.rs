@@ -0,0 +1,235 @@
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    style::Stylize,
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Paragraph, StatefulWidget, Widget},
+};
+
+use crate::{
+    config,
+    redis_client::{export, types::KeyValue},
+    widgets::highlight::Syntax,
+};
+
+const HORIZONTAL_STEP: usize = 8;
+
+/// The whole value as text, collections as pretty printed JSON.
+pub fn value_text(value: &KeyValue) -> String {
+    match value {
+        KeyValue::String(value) => value.clone(),
+        value => serde_json::to_string_pretty(&export::value_to_json(value)).unwrap_or_default(),
+    }
+}
+
+/// Up to `width` chars of the styled line starting at char `start`.
+fn slice_spans(spans: &[Span<'static>], start: usize, width: usize) -> Vec<Span<'static>> {
+    let mut skip = start;
+    let mut take = width;
+    let mut sliced = Vec::new();
+
+    for span in spans {
+        if take == 0 {
+            break;
+        }
+
+        let len = span.content.chars().count();
+        if skip >= len {
+            skip -= len;
+            continue;
+        }
+
+        let content: String = span.content.chars().skip(skip).take(take).collect();
+        take -= content.chars().count();
+        skip = 0;
+        sliced.push(Span::styled(content, span.style));
+    }
+
+    sliced
+}
+
+/// A value opened on the whole screen, for text that does not fit the details pane.
+pub struct Inspector {
+    title: String,
+    text: String,
+    syntax: Syntax,
+    lines: Vec<Vec<Span<'static>>>,
+    wrap: bool,
+    /// First visible row, counted in wrapped rows when wrapping.
+    offset: usize,
+    /// First visible char of every line when not wrapping.
+    column: usize,
+    yanked: bool,
+}
+
+impl Inspector {
+    pub fn new() -> Self {
+        Self {
+            title: String::new(),
+            text: String::new(),
+            syntax: Syntax::Plain,
+            lines: Vec::new(),
+            wrap: true,
+            offset: 0,
+            column: 0,
+            yanked: false,
+        }
+    }
+
+    /// Shows `text`, JSON on a single line is pretty printed.
+    pub fn open(&mut self, title: String, text: String) {
+        self.syntax = Syntax::detect(&text);
+        self.text = match serde_json::from_str::<serde_json::Value>(&text) {
+            Ok(json) if self.syntax == Syntax::Json && !text.contains('\n') => {
+                serde_json::to_string_pretty(&json).unwrap_or(text)
+            }
+            _ => text,
+        };
+        self.lines = self
+            .text
+            .lines()
+            .map(|line| self.syntax.highlight(line))
+            .collect();
+        self.title = title;
+        self.offset = 0;
+        self.column = 0;
+        self.yanked = false;
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn toggle_wrap(&mut self) {
+        self.wrap = !self.wrap;
+        self.offset = 0;
+        self.column = 0;
+    }
+
+    pub fn set_yanked(&mut self) {
+        self.yanked = true;
+    }
+
+    pub fn scroll_down(&mut self) {
+        self.offset += 1;
+    }
+
+    pub fn scroll_up(&mut self) {
+        self.offset = self.offset.saturating_sub(1);
+    }
+
+    pub fn scroll_right(&mut self) {
+        if !self.wrap {
+            self.column += HORIZONTAL_STEP;
+        }
+    }
+
+    pub fn scroll_left(&mut self) {
+        self.column = self.column.saturating_sub(HORIZONTAL_STEP);
+    }
+
+    /// Line number and text of every row, continuation rows of a wrapped line have no number.
+    fn rows(&self, width: usize) -> Vec<(Option<usize>, Vec<Span<'static>>)> {
+        let mut rows = Vec::new();
+
+        for (idx, spans) in self.lines.iter().enumerate() {
+            if !self.wrap {
+                rows.push((Some(idx + 1), slice_spans(spans, self.column, width)));
+                continue;
+            }
+
+            let len: usize = spans.iter().map(|span| span.content.chars().count()).sum();
+            for start in (0..len.max(1)).step_by(width.max(1)) {
+                let number = (start == 0).then_some(idx + 1);
+                rows.push((number, slice_spans(spans, start, width)));
+            }
+        }
+
+        rows
+    }
+}
+
+pub struct InspectorWidget;
+
+impl StatefulWidget for InspectorWidget {
+    type State = Inspector;
+
+    fn render(self, area: Rect, buf: &mut ratatui::prelude::Buffer, state: &mut Self::State) {
+        let colors = config::get().colors;
+
+        let mut title = format!(
+            "Inspect: {} ({:?}, wrap {}) <w> wrap, <h/l> scroll, <y> yank, <Esc> back",
+            state.title,
+            state.syntax,
+            if state.wrap { "on" } else { "off" },
+        );
+        if state.yanked {
+            title.push_str(" [yanked]");
+        }
+
+        let block = Block::new()
+            .bg(colors.base00)
+            .fg(colors.base05)
+            .border_style(colors.base0d)
+            .border_type(BorderType::Rounded)
+            .borders(Borders::ALL)
+            .title(title);
+
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        let digits = state.lines.len().max(1).to_string().len();
+        let [gutter_area, text_area] =
+            Layout::horizontal([Constraint::Length(digits as u16 + 1), Constraint::Fill(1)])
+                .areas(inner);
+
+        let rows = state.rows(usize::from(text_area.width));
+        let height = usize::from(text_area.height);
+        state.offset = state.offset.min(rows.len().saturating_sub(height));
+
+        let visible = rows.into_iter().skip(state.offset).take(height);
+        let (numbers, lines): (Vec<Line>, Vec<Line>) = visible
+            .map(|(number, spans)| {
+                let number = number.map_or_else(String::new, |n| format!("{n:>digits$}"));
+                (Line::from(number).fg(colors.base03), Line::from(spans))
+            })
+            .unzip();
+
+        Paragraph::new(numbers).render(gutter_area, buf);
+        Paragraph::new(lines).render(text_area, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_slice_spans() {
+        let spans = vec![Span::raw("abc"), Span::raw("défg")];
+        let text = |spans: Vec<Span>| {
+            spans
+                .iter()
+                .map(|s| s.content.to_string())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(text(slice_spans(&spans, 2, 3)), ["c", "dé"]);
+        assert_eq!(text(slice_spans(&spans, 5, 10)), ["fg"]);
+        assert!(slice_spans(&spans, 9, 3).is_empty());
+    }
+
+    #[test]
+    fn test_wrapped_rows() {
+        let mut inspector = Inspector::new();
+        inspector.open("key".into(), "abcdef\n\nxy".into());
+
+        let numbers: Vec<Option<usize>> = inspector
+            .rows(4)
+            .into_iter()
+            .map(|(number, _)| number)
+            .collect();
+        assert_eq!(numbers, [Some(1), None, Some(2), Some(3)]);
+    }
+}
@@ -1,18 +1,29 @@
-use byte_unit::{Byte, UnitType};
-use crossterm::event::KeyEvent;
+use std::{
+    collections::{BTreeSet, HashMap},
+    time::{Duration, Instant},
+};
+
+use crossterm::event::{KeyCode, KeyEvent, MouseButton, MouseEvent, MouseEventKind};
 use ratatui::{
-    layout::{Alignment, Constraint, Layout, Margin},
+    layout::{Alignment, Constraint, Layout, Position, Rect},
     style::Stylize,
+    text::{Line, Span},
     widgets::{
-        Block, BorderType, Borders, Cell, Clear, HighlightSpacing, Paragraph, Row, StatefulWidget,
-        Table, TableState, Widget, Wrap,
+        Block, BorderType, Borders, HighlightSpacing, Paragraph, Row, StatefulWidget, Table,
+        TableState, Widget, Wrap,
     },
 };
 use tui_textarea::TextArea;
 
 use crate::{
     config,
-    redis_client::types::{KeyMeta, KeyValue, RedisType},
+    redis_client::types::{KeyMeta, RefreshedKey},
+    widgets::{
+        columns::KeyColumns,
+        details::{Details, DetailsWidget},
+        filter::KeyFilter,
+        tree::{KeyTreeView, KeyTreeWidget},
+    },
 };
 
 enum KeySpacePopupMode {
@@ -24,6 +35,28 @@ enum KeySpaceMode {
     Popup(KeySpacePopupMode),
 }
 
+const DEFAULT_SPLIT: u16 = 35;
+const MIN_SPLIT: u16 = 15;
+const MAX_SPLIT: u16 = 85;
+/// How long the row of a key changed on the server stays highlighted.
+const FLASH: Duration = Duration::from_millis(1500);
+/// Rows taken by a table header with its margins.
+const HEADER_HEIGHT: u16 = 3;
+
+/// Where the parts of the key space were drawn last, for mouse hit testing.
+#[derive(Default, Clone, Copy)]
+struct KeySpaceAreas {
+    area: Rect,
+    list: Rect,
+    details: Rect,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum KeySpaceView {
+    Table,
+    Tree,
+}
+
 pub struct KeySpace {
     table: TableState,
     keys: Vec<KeyMeta>,
@@ -31,17 +64,52 @@ pub struct KeySpace {
     pattern: Option<String>,
     mode: KeySpaceMode,
     text_area: Option<TextArea<'static>>,
+    view: KeySpaceView,
+    tree: KeyTreeView,
+    filter: KeyFilter,
+    /// Filter to go back to when the popup is dismissed.
+    previous_filter: KeyFilter,
+    /// Indexes of the keys passing the filter, with the char positions to highlight.
+    visible: Vec<(usize, Vec<usize>)>,
+    columns: KeyColumns,
+    /// Keys marked for bulk operations, by name so that they survive paging and filtering.
+    selection: BTreeSet<String>,
+    /// Key a range selection was started from, looked up again when the range is marked.
+    anchor: Option<String>,
+    /// Width of the key list in percent of the key space.
+    split: u16,
+    /// The split between the key list and the details is being dragged.
+    dragging: bool,
+    details: Details,
+    areas: KeySpaceAreas,
+    /// Keys changed on the server, with when, to highlight their rows for a moment.
+    flashes: HashMap<String, Instant>,
 }
 
 impl KeySpace {
     pub fn new(keys: Vec<KeyMeta>) -> Self {
+        let visible = (0..keys.len()).map(|i| (i, Vec::new())).collect();
+
         Self {
             keys,
+            visible,
+            filter: KeyFilter::default(),
+            columns: KeyColumns::new(config::get().key_columns.clone()),
+            previous_filter: KeyFilter::default(),
             table: TableState::default(),
             cursor: None,
             pattern: None,
             mode: KeySpaceMode::Normal,
             text_area: None,
+            view: KeySpaceView::Table,
+            tree: KeyTreeView::new(config::get().key_delimiter.clone()),
+            selection: BTreeSet::new(),
+            anchor: None,
+            split: DEFAULT_SPLIT,
+            dragging: false,
+            details: Details::new(),
+            areas: KeySpaceAreas::default(),
+            flashes: HashMap::new(),
         }
     }
 
@@ -49,44 +117,112 @@ impl KeySpace {
         matches!(self.mode, KeySpaceMode::Popup(_))
     }
 
-    pub fn handle_key(&mut self, key: KeyEvent) {
-        if let Some(ref mut text_area) = self.text_area {
-            text_area.input(key);
+    /// Types into the filter popup, `<Tab>` switches the match mode. Returns whether the
+    /// filter changed so that the server side scan can be scheduled.
+    pub fn handle_key(&mut self, key: KeyEvent) -> bool {
+        let Some(ref mut text_area) = self.text_area else {
+            return false;
+        };
+
+        let mut mode = self.filter.mode();
+        if key.code == KeyCode::Tab {
+            mode = mode.next();
+        } else if !text_area.input(key) {
+            return false;
         }
+
+        let query = text_area.lines().join("");
+        self.set_filter(KeyFilter::new(mode, query));
+        true
     }
 
     pub fn enter_filter_pattern(&mut self) {
         self.mode = KeySpaceMode::Popup(KeySpacePopupMode::FilterPattern);
-        let mut text_area = TextArea::default();
-        text_area.set_placeholder_text("Enter pattern");
+        self.previous_filter = self.filter.clone();
+
+        let mut text_area = TextArea::from([self.filter.query()]);
+        text_area.move_cursor(tui_textarea::CursorMove::End);
+        text_area.set_placeholder_text("Type to filter loaded keys");
+        self.text_area = Some(text_area);
+        self.update_filter_block();
+    }
+
+    fn update_filter_block(&mut self) {
+        let Some(ref mut text_area) = self.text_area else {
+            return;
+        };
+
+        let (title, color) = match self.filter.error() {
+            Some(err) => (
+                format!("{}: {err}", self.filter.mode()),
+                config::get().colors.base08,
+            ),
+            None => (
+                format!("Filter: {} (<Tab> switches mode)", self.filter.mode()),
+                config::get().colors.base04,
+            ),
+        };
+
         text_area.set_block(
             Block::default()
-                .border_style(config::get().colors.base04)
+                .border_style(color)
                 .border_type(BorderType::Rounded)
                 .borders(Borders::ALL)
-                .title("Pattern"),
+                .title(title),
         );
+    }
 
-        self.text_area = Some(text_area);
+    fn set_filter(&mut self, filter: KeyFilter) {
+        self.filter = filter;
+        self.apply_filter();
+        self.update_filter_block();
     }
 
-    pub fn confirm_filter_pattern(&mut self) -> Option<String> {
-        let pattern = if let Some(text_area) = self.text_area.take() {
-            let line = text_area.lines()[0].clone();
+    fn apply_filter(&mut self) {
+        let selected = self.selected_key().map(|meta| meta.key.clone());
 
-            if line == "" {
-                self.pattern = None;
-            } else {
-                self.pattern = Some(line.clone());
-            }
+        self.visible = self
+            .keys
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, meta)| self.filter.matches(&meta.key).map(|hits| (idx, hits)))
+            .collect();
+        self.columns
+            .sort(&mut self.visible, |(idx, _)| &self.keys[*idx]);
+
+        let index = selected
+            .and_then(|key| {
+                self.visible
+                    .iter()
+                    .position(|(idx, _)| self.keys[*idx].key == key)
+            })
+            .or_else(|| (!self.visible.is_empty()).then_some(0))
+            .filter(|_| self.table.selected().is_some());
+        self.table.select(index);
+    }
 
-            Some(line)
-        } else {
-            None
-        };
+    /// `SCAN MATCH` pattern the current filter narrows the keyspace to.
+    pub fn filter_scan_pattern(&self) -> Option<String> {
+        self.filter.scan_pattern()
+    }
 
+    /// Closes the popup keeping the filter, returns its scan pattern.
+    pub fn confirm_filter_pattern(&mut self) -> Option<String> {
+        self.text_area = None;
+        self.exit_popup();
+        self.filter_scan_pattern()
+    }
+
+    /// Closes the popup and goes back to the filter it was opened with.
+    pub fn cancel_filter_pattern(&mut self) -> Option<String> {
+        self.text_area = None;
         self.exit_popup();
-        pattern
+        self.set_filter(self.previous_filter.clone());
+        self.filter_scan_pattern()
+    }
+
+    pub fn clear_filter(&mut self) {
+        self.set_filter(KeyFilter::new(self.filter.mode(), ""));
     }
 
     pub fn exit_popup(&mut self) {
@@ -102,19 +238,206 @@ impl KeySpace {
         self.pattern = pattern;
     }
 
+    pub fn selected_key(&self) -> Option<&KeyMeta> {
+        match self.view {
+            KeySpaceView::Table => self
+                .table
+                .selected()
+                .and_then(|i| self.visible.get(i))
+                .and_then(|(idx, _)| self.keys.get(*idx)),
+            KeySpaceView::Tree => self.tree.selected_key(),
+        }
+    }
+
+    /// Marks or unmarks the key under the cursor and moves on to the next one.
+    pub fn toggle_selection(&mut self) {
+        let Some(key) = self.selected_key().map(|meta| meta.key.clone()) else {
+            return;
+        };
+
+        if !self.selection.remove(&key) {
+            self.selection.insert(key);
+        }
+        self.scroll_next();
+    }
+
+    /// The first call drops an anchor on the key under the cursor, the second one marks every
+    /// row between the anchor and the cursor. An anchor no longer shown, after paging or
+    /// filtering, is dropped on the current key instead.
+    pub fn range_select(&mut self) {
+        if self.is_tree() {
+            return;
+        }
+        let Some(current) = self.table.selected() else {
+            return;
+        };
+        let Some(key) = self.selected_key().map(|meta| meta.key.clone()) else {
+            return;
+        };
+
+        let anchor = self.anchor.take().and_then(|anchor| {
+            self.visible
+                .iter()
+                .position(|(idx, _)| self.keys[*idx].key == anchor)
+        });
+        match anchor {
+            None => self.anchor = Some(key),
+            Some(anchor) => {
+                let (start, end) = (anchor.min(current), anchor.max(current));
+                for (idx, _) in self.visible.iter().take(end + 1).skip(start) {
+                    self.selection.insert(self.keys[*idx].key.clone());
+                }
+            }
+        }
+    }
+
+    /// Marks every key of the loaded page that passes the filter.
+    pub fn select_page(&mut self) {
+        for (idx, _) in &self.visible {
+            self.selection.insert(self.keys[*idx].key.clone());
+        }
+    }
+
+    /// Flips the marks of the keys on the loaded page that pass the filter.
+    pub fn invert_selection(&mut self) {
+        for (idx, _) in &self.visible {
+            let key = &self.keys[*idx].key;
+            if !self.selection.remove(key) {
+                self.selection.insert(key.clone());
+            }
+        }
+    }
+
+    pub fn clear_selection(&mut self) {
+        self.selection.clear();
+        self.anchor = None;
+    }
+
+    /// Keys a bulk operation applies to: the marked ones, or the one under the cursor.
+    pub fn targets(&self) -> Vec<String> {
+        if self.selection.is_empty() {
+            self.selected_key()
+                .map(|meta| vec![meta.key.clone()])
+                .unwrap_or_default()
+        } else {
+            self.selection.iter().cloned().collect()
+        }
+    }
+
     pub fn set_keys(&mut self, keys: Vec<KeyMeta>) {
+        self.tree.insert_keys(keys.iter().cloned());
         _ = std::mem::replace(&mut self.keys, keys);
+        self.apply_filter();
+    }
+
+    /// Updates the loaded keys that were fetched again in place, dropping the ones that are
+    /// gone, so keys shown on their own stay and the selection stays on the same key.
+    pub fn refresh_keys(&mut self, refreshed: Vec<RefreshedKey>) {
+        for (key, meta) in refreshed {
+            match meta {
+                Some(meta) => {
+                    if let Some(loaded) = self.keys.iter_mut().find(|loaded| loaded.key == key) {
+                        *loaded = meta.clone();
+                        self.tree.insert_keys([meta]);
+                    }
+                }
+                None => {
+                    self.keys.retain(|loaded| loaded.key != key);
+                    self.tree.remove_key(&key);
+                }
+            }
+        }
+        self.apply_filter();
+    }
+
+    pub fn flash(&mut self, keys: &[String]) {
+        let now = Instant::now();
+        self.flashes.retain(|_, at| now.duration_since(*at) < FLASH);
+        for key in keys {
+            self.flashes.insert(key.clone(), now);
+        }
+    }
+
+    fn is_flashing(&self, key: &str) -> bool {
+        self.flashes.get(key).is_some_and(|at| at.elapsed() < FLASH)
+    }
+
+    /// Names of the keys on the loaded page.
+    pub fn loaded_keys(&self) -> impl Iterator<Item = &String> {
+        self.keys.iter().map(|meta| &meta.key)
+    }
+
+    /// Puts a key fetched on its own into the list, even when it is not on the loaded page,
+    /// and selects it. The filter is cleared when it would hide the key.
+    pub fn show_key(&mut self, meta: KeyMeta) {
+        let key = meta.key.clone();
+        match self.keys.iter_mut().find(|loaded| loaded.key == key) {
+            Some(loaded) => *loaded = meta,
+            None => self.keys.insert(0, meta),
+        }
+
+        self.view = KeySpaceView::Table;
+        if self.filter.matches(&key).is_none() {
+            self.filter = KeyFilter::new(self.filter.mode(), "");
+        }
+        self.apply_filter();
+
+        let index = self
+            .visible
+            .iter()
+            .position(|(idx, _)| self.keys[*idx].key == key);
+        self.scroll_to(index.unwrap_or_default());
+    }
+
+    pub fn columns_mut(&mut self) -> &mut KeyColumns {
+        &mut self.columns
+    }
+
+    /// Sorts the loaded page by the column under the column cursor.
+    pub fn toggle_sort(&mut self) {
+        self.columns.toggle_sort();
+        self.apply_filter();
+    }
+
+    pub fn is_tree(&self) -> bool {
+        self.view == KeySpaceView::Tree
+    }
+
+    pub fn toggle_view(&mut self) {
+        self.view = match self.view {
+            KeySpaceView::Table => KeySpaceView::Tree,
+            KeySpaceView::Tree => KeySpaceView::Table,
+        };
+    }
+
+    /// Forgets the keys accumulated by the tree, e.g. when they may have changed on the server.
+    pub fn clear_tree(&mut self) {
+        self.tree.clear();
+    }
+
+    /// See [`KeyTreeView::toggle_selected`].
+    pub fn toggle_folder(&mut self) -> Option<Option<String>> {
+        self.tree.toggle_selected()
     }
 
     pub fn scroll_next(&mut self) {
-        let wrap_index = self.keys.len().max(1);
+        if self.is_tree() {
+            self.tree.scroll_next();
+            return;
+        }
+
+        let wrap_index = self.visible.len().max(1);
         let next = self.table.selected().map_or(0, |i| (i + 1) % wrap_index);
         self.scroll_to(next);
     }
 
     pub fn scroll_previous(&mut self) {
-        let last: usize = self.keys.len().saturating_sub(1);
-        let wrap_index = self.keys.len().max(1);
+        if self.is_tree() {
+            self.tree.scroll_previous();
+            return;
+        }
+        let last: usize = self.visible.len().saturating_sub(1);
+        let wrap_index = self.visible.len().max(1);
         let previous = self
             .table
             .selected()
@@ -122,8 +445,89 @@ impl KeySpace {
         self.scroll_to(previous);
     }
 
+    pub fn details_mut(&mut self) -> &mut Details {
+        &mut self.details
+    }
+
+    /// Clicks select rows and drag the split, the wheel scrolls the list or the details.
+    /// Returns which part a row was clicked in.
+    pub fn handle_mouse(&mut self, mouse: MouseEvent) -> Option<MouseTarget> {
+        let position = Position::new(mouse.column, mouse.row);
+        let areas = self.areas;
+
+        match mouse.kind {
+            MouseEventKind::Down(MouseButton::Left) if self.is_on_split(mouse.column) => {
+                self.dragging = true;
+            }
+            MouseEventKind::Down(MouseButton::Left) if areas.list.contains(position) => {
+                let again = self.click_row(mouse.row - areas.list.y);
+                return Some(MouseTarget::List { again });
+            }
+            MouseEventKind::Down(MouseButton::Left) if areas.details.contains(position) => {
+                return self
+                    .details
+                    .click(mouse.row)
+                    .then_some(MouseTarget::Details);
+            }
+            MouseEventKind::Drag(MouseButton::Left) if self.dragging => {
+                self.resize_split(mouse.column);
+            }
+            MouseEventKind::Up(MouseButton::Left) => self.dragging = false,
+            MouseEventKind::ScrollDown if areas.list.contains(position) => self.scroll_next(),
+            MouseEventKind::ScrollUp if areas.list.contains(position) => self.scroll_previous(),
+            MouseEventKind::ScrollDown if areas.details.contains(position) => {
+                self.details.scroll_next();
+            }
+            MouseEventKind::ScrollUp if areas.details.contains(position) => {
+                self.details.scroll_previous();
+            }
+            _ => {}
+        }
+
+        None
+    }
+
+    /// The borders of the key list and the details meet at the split.
+    fn is_on_split(&self, column: u16) -> bool {
+        let split = self.areas.details.x;
+        self.areas.details.width > 0 && (column == split || column + 1 == split)
+    }
+
+    fn resize_split(&mut self, column: u16) {
+        let area = self.areas.area;
+        if area.width == 0 {
+            return;
+        }
+
+        let offset = u32::from(column.saturating_sub(area.x));
+        let percent = offset * 100 / u32::from(area.width);
+        self.split = u16::try_from(percent)
+            .unwrap_or(MAX_SPLIT)
+            .clamp(MIN_SPLIT, MAX_SPLIT);
+    }
+
+    /// Selects the row at `y` rows below the top of the list.
+    fn click_row(&mut self, y: u16) -> bool {
+        let Some(row) = y.checked_sub(HEADER_HEIGHT).map(usize::from) else {
+            return false;
+        };
+
+        if self.is_tree() {
+            return self.tree.click_row(row);
+        }
+
+        let index = self.table.offset() + row;
+        if index >= self.visible.len() {
+            return false;
+        }
+
+        let again = self.table.selected() == Some(index);
+        self.scroll_to(index);
+        again
+    }
+
     fn scroll_to(&mut self, index: usize) {
-        if self.keys.is_empty() {
+        if self.visible.is_empty() {
             self.table.select(None)
         } else {
             self.table.select(Some(index));
@@ -131,7 +535,23 @@ impl KeySpace {
     }
 }
 
-pub struct KeySpaceWidget;
+/// What a mouse click landed on.
+pub enum MouseTarget {
+    /// A key, `again` when it was selected already.
+    List { again: bool },
+    /// A row of the value.
+    Details,
+}
+
+pub struct KeySpaceWidget {
+    details_focused: bool,
+}
+
+impl KeySpaceWidget {
+    pub fn new(details_focused: bool) -> Self {
+        Self { details_focused }
+    }
+}
 
 impl KeySpaceWidget {
     fn render_confirm_popup(
@@ -160,157 +580,65 @@ impl KeySpaceWidget {
             text_area.render(popup_area, buf);
         }
     }
+}
 
-    fn render_key_view(
-        &self,
-        state: &mut KeySpace,
-        area: ratatui::prelude::Rect,
-        buf: &mut ratatui::prelude::Buffer,
-    ) {
-        let Some(selected_index) = state.table.selected() else {
-            return;
-        };
+const HIGHLIGHT_SYMBOL: &str = " >> ";
+const SELECTED_MARKER: &str = "✓ ";
 
-        let Some(key) = state.keys.get(selected_index) else {
-            return;
-        };
+fn keys_title(state: &KeySpace) -> String {
+    let mut title = match state.view {
+        KeySpaceView::Table => "Keys".to_owned(),
+        KeySpaceView::Tree => format!("Keys (tree, {} loaded)", state.tree.len()),
+    };
 
-        let key_details_block = Block::default()
-            .title("Key Details")
-            .borders(Borders::ALL)
-            .border_type(BorderType::Rounded);
+    if !state.selection.is_empty() {
+        title.push_str(&format!(" [{} selected]", state.selection.len()));
+    }
+    if let Some(ref anchor) = state.anchor {
+        title.push_str(&format!(" [range from {anchor}, <v> to mark]"));
+    }
+    title
+}
 
-        let key_details_area = key_details_block.inner(area);
-        key_details_block.render(area, buf);
+/// Renders the key with the chars matched by the filter emphasized.
+fn highlight(key: &str, hits: &[usize]) -> Line<'static> {
+    if hits.is_empty() {
+        return Line::raw(key.to_owned());
+    }
 
-        let [key_meta_area, view_area] =
-            Layout::vertical([Constraint::Max(6), Constraint::Fill(1)])
-                .flex(ratatui::layout::Flex::Center)
-                .areas(key_details_area);
-
-        let key_info = format!(
-            "Key: {}\nType: {:?}\nTTL: {}\nSize: {}",
-            key.key,
-            key.r_type,
-            key.ttl,
-            unsafe { Byte::from_u128_unsafe(key.size) }.get_appropriate_unit(UnitType::Binary)
-        );
+    let spans = key.chars().enumerate().map(|(idx, c)| {
+        if hits.contains(&idx) {
+            Span::raw(c.to_string())
+                .fg(config::get().colors.base0a)
+                .bold()
+        } else {
+            Span::raw(c.to_string())
+        }
+    });
 
-        Paragraph::new(key_info)
-            .wrap(Wrap { trim: true })
-            .render(key_meta_area, buf);
+    Line::from(spans.collect::<Vec<_>>())
+}
 
-        match key.value {
-            KeyValue::String(ref value) => {
-                Paragraph::new(format!("Value: {}", value))
-                    .wrap(Wrap { trim: true })
-                    .render(view_area, buf);
-            }
-            KeyValue::List(ref value) => {
-                let mut table_state = TableState::default();
-
-                let widths = [Constraint::Percentage(100)];
-                let header: Row<'_> = Row::new(["Item"].map(|h| Cell::from(h.bold())))
-                    .top_margin(1)
-                    .bottom_margin(1)
-                    .fg(config::get().colors.base04)
-                    .bg(config::get().colors.base02);
-
-                let rows = value.iter().cloned().map(|(value)| {
-                    Row::new([Cell::from(value)])
-                        .fg(config::get().colors.base04)
-                        .bg(config::get().colors.base00)
-                });
-                let table: Table<'_> = Table::new(rows, widths)
-                    .header(header)
-                    .flex(ratatui::layout::Flex::Center)
-                    .highlight_symbol(HIGHLIGHT_SYMBOL)
-                    .highlight_style(config::get().colors.base05)
-                    .highlight_spacing(HighlightSpacing::Always);
-
-                StatefulWidget::render(table, view_area, buf, &mut table_state);
-            }
-            KeyValue::Hash(ref value) => {
-                let mut table_state = TableState::default();
-
-                let widths = [Constraint::Percentage(50), Constraint::Percentage(50)];
-                let header: Row<'_> = Row::new(["Field", "Value"].map(|h| Cell::from(h.bold())))
-                    .top_margin(1)
-                    .bottom_margin(1)
-                    .fg(config::get().colors.base04)
-                    .bg(config::get().colors.base02);
-
-                let rows = value.iter().map(|(field, value)| {
-                    Row::new([Cell::from(field.clone()), Cell::from(value.clone())])
-                        .fg(config::get().colors.base04)
-                        .bg(config::get().colors.base00)
-                });
-                let table: Table<'_> = Table::new(rows, widths)
-                    .header(header)
-                    .widths(widths)
-                    .flex(ratatui::layout::Flex::Center)
-                    .highlight_symbol(HIGHLIGHT_SYMBOL)
-                    .highlight_style(config::get().colors.base05)
-                    .highlight_spacing(HighlightSpacing::Always);
-
-                StatefulWidget::render(table, view_area, buf, &mut table_state);
-            }
-            KeyValue::Set(ref value) => {
-                let mut table_state = TableState::default();
-
-                let widths = [Constraint::Percentage(100)];
-                let header: Row<'_> = Row::new(["Member"].map(|h| Cell::from(h.bold())))
-                    .top_margin(1)
-                    .bottom_margin(1)
-                    .fg(config::get().colors.base04)
-                    .bg(config::get().colors.base02);
-
-                let rows = value.iter().cloned().map(|member| {
-                    Row::new([Cell::from(member)])
-                        .fg(config::get().colors.base04)
-                        .bg(config::get().colors.base00)
-                });
-                let table: Table<'_> = Table::new(rows, widths)
-                    .header(header)
-                    .flex(ratatui::layout::Flex::Center)
-                    .highlight_symbol(HIGHLIGHT_SYMBOL)
-                    .highlight_style(config::get().colors.base05)
-                    .highlight_spacing(HighlightSpacing::Always);
-
-                StatefulWidget::render(table, view_area, buf, &mut table_state);
-            }
-            KeyValue::Zset(ref value) => {
-                let mut table_state = TableState::default();
-
-                let widths = [Constraint::Percentage(50), Constraint::Percentage(50)];
-                let header: Row<'_> = Row::new(["Member", "Score"].map(|h| Cell::from(h.bold())))
-                    .top_margin(1)
-                    .bottom_margin(1)
-                    .fg(config::get().colors.base04)
-                    .bg(config::get().colors.base02);
-
-                let rows = value.iter().map(|(member, score)| {
-                    Row::new([Cell::from(member.clone()), Cell::from(score.to_string())])
-                        .fg(config::get().colors.base04)
-                        .bg(config::get().colors.base00)
-                });
-                let table: Table<'_> = Table::new(rows, widths)
-                    .header(header)
-                    .widths(widths)
-                    .flex(ratatui::layout::Flex::Center)
-                    .highlight_symbol(HIGHLIGHT_SYMBOL)
-                    .highlight_style(config::get().colors.base05)
-                    .highlight_spacing(HighlightSpacing::Always);
-
-                StatefulWidget::render(table, view_area, buf, &mut table_state);
-            }
-            _ => {}
-        }
+impl KeySpaceWidget {
+    fn render_details(&self, state: &mut KeySpace, area: Rect, buf: &mut ratatui::prelude::Buffer) {
+        let meta = match state.view {
+            KeySpaceView::Table => state
+                .table
+                .selected()
+                .and_then(|i| state.visible.get(i))
+                .and_then(|(idx, _)| state.keys.get(*idx)),
+            KeySpaceView::Tree => state.tree.selected_key(),
+        };
+
+        StatefulWidget::render(
+            DetailsWidget::new(meta, self.details_focused),
+            area,
+            buf,
+            &mut state.details,
+        );
     }
 }
 
-const HIGHLIGHT_SYMBOL: &str = " >> ";
-
 impl StatefulWidget for KeySpaceWidget {
     type State = KeySpace;
 
@@ -321,7 +649,7 @@ impl StatefulWidget for KeySpaceWidget {
         state: &mut Self::State,
     ) {
         let [t_area, view_area] =
-            Layout::horizontal([Constraint::Percentage(35), Constraint::Fill(1)])
+            Layout::horizontal([Constraint::Percentage(state.split), Constraint::Fill(1)])
                 .flex(ratatui::layout::Flex::Center)
                 .areas(area);
 
@@ -330,7 +658,7 @@ impl StatefulWidget for KeySpaceWidget {
             .fg(config::get().colors.base04)
             .border_type(ratatui::widgets::BorderType::Rounded)
             .borders(Borders::all())
-            .title("Keys");
+            .title(keys_title(state));
 
         let table_area = space_block.inner(t_area);
         space_block.render(t_area, buf);
@@ -348,6 +676,12 @@ impl StatefulWidget for KeySpaceWidget {
             .margin(1)
             .areas(table_area);
 
+        state.areas = KeySpaceAreas {
+            area,
+            list: table_area,
+            details: view_area,
+        };
+
         let filters_block = Block::new()
             .bg(config::get().colors.base00)
             .fg(config::get().colors.base04)
@@ -377,49 +711,70 @@ impl StatefulWidget for KeySpaceWidget {
             .alignment(Alignment::Right)
             .render(size_area, buf);
 
-        Paragraph::new(format!(
-            "Pattern: {}",
-            state.pattern.as_deref().unwrap_or_else(|| "*")
-        ))
-        .bold()
-        .alignment(Alignment::Left)
-        .wrap(Wrap { trim: true })
-        .render(pattern_area, buf);
-
-        let widths = [
-            Constraint::Percentage(20),
-            Constraint::Percentage(35),
-            Constraint::Percentage(20),
-            Constraint::Percentage(25),
-        ];
-        let header: Row<'_> =
-            Row::new(["Type", "Key", "TTL(s)", "Size"].map(|h| Cell::from(h.bold())))
-                .top_margin(1)
-                .bottom_margin(1)
-                .fg(config::get().colors.base04)
-                .bg(config::get().colors.base02);
+        let mut pattern = format!("Pattern: {}", state.pattern.as_deref().unwrap_or("*"));
+        if !state.filter.is_empty() {
+            pattern.push_str(&format!(
+                "  Filter: {} ({})",
+                state.filter.query(),
+                state.filter.mode()
+            ));
+        }
+
+        Paragraph::new(pattern)
+            .bold()
+            .alignment(Alignment::Left)
+            .wrap(Wrap { trim: true })
+            .render(pattern_area, buf);
+
+        if state.is_tree() {
+            StatefulWidget::render(
+                KeyTreeWidget::new(&state.selection),
+                table_area,
+                buf,
+                &mut state.tree,
+            );
+            self.render_details(state, view_area, buf);
+
+            if state.is_popup() {
+                self.render_confirm_popup(state, area, buf)
+            }
+            return;
+        }
+
+        let colors = config::get().colors;
+        let widths = state.columns.widths();
+        let header: Row<'_> = state
+            .columns
+            .header()
+            .top_margin(1)
+            .bottom_margin(1)
+            .fg(config::get().colors.base04)
+            .bg(config::get().colors.base02);
 
         let rows = state
-            .keys
-            .clone()
-            .into_iter()
+            .visible
+            .iter()
             .enumerate()
-            .map(|(idx, meta)| {
-                Row::new([
-                    Cell::from(RedisType::from(meta.r_type)),
-                    Cell::from(meta.key),
-                    Cell::from(meta.ttl.to_string()),
-                    Cell::from(format!(
-                        "{:.2}",
-                        unsafe { Byte::from_u128_unsafe(meta.size) }
-                            .get_appropriate_unit(UnitType::Binary)
-                    )),
-                ])
-                .fg(config::get().colors.base04)
-                .bg(if idx % 2 == 0 {
-                    config::get().colors.base00
+            .map(|(idx, (key_idx, hits))| {
+                let meta = &state.keys[*key_idx];
+                let selected = state.selection.contains(&meta.key);
+
+                let mut key = highlight(&meta.key, hits);
+                if selected {
+                    key.spans
+                        .insert(0, Span::raw(SELECTED_MARKER).fg(colors.base0b));
+                }
+
+                let row = Row::new(state.columns.cells(meta, key));
+                if state.is_flashing(&meta.key) {
+                    return row.fg(colors.base00).bg(colors.base0a);
+                }
+                row.fg(colors.base04).bg(if selected {
+                    colors.base02
+                } else if idx % 2 == 0 {
+                    colors.base00
                 } else {
-                    config::get().colors.base01
+                    colors.base01
                 })
             });
         let table: Table<'_> = Table::new(rows, widths)
@@ -431,10 +786,58 @@ impl StatefulWidget for KeySpaceWidget {
 
         StatefulWidget::render(table, table_area, buf, &mut state.table);
 
-        self.render_key_view(state, view_area, buf);
+        self.render_details(state, view_area, buf);
 
         if state.is_popup() {
             self.render_confirm_popup(state, area, buf)
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::{
+        redis_client::types::{KeyValue, RedisType},
+        widgets::filter::MatchMode,
+    };
+
+    fn meta(key: &str) -> KeyMeta {
+        KeyMeta {
+            key: key.into(),
+            r_type: RedisType::String,
+            size: 0,
+            ttl: -1,
+            value: KeyValue::Unknown,
+            encoding: None,
+            idle: None,
+            freq: None,
+        }
+    }
+
+    #[test]
+    fn test_range_select_follows_the_anchor_key() {
+        let mut keyspace = KeySpace::new(Vec::new());
+        keyspace.set_keys(["a:1", "b:1", "b:2", "b:3"].map(meta).to_vec());
+
+        keyspace.table.select(Some(1));
+        keyspace.range_select();
+        assert_eq!(keyspace.anchor.as_deref(), Some("b:1"));
+
+        // "b:1" moves up a row, the range still starts from it
+        keyspace.set_filter(KeyFilter::new(MatchMode::Substring, "b:"));
+        keyspace.table.select(Some(2));
+        keyspace.range_select();
+        assert_eq!(keyspace.targets(), ["b:1", "b:2", "b:3"]);
+
+        // An anchor filtered out starts a new range on the current key
+        keyspace.clear_selection();
+        keyspace.range_select();
+        keyspace.set_filter(KeyFilter::new(MatchMode::Substring, "a:"));
+        keyspace.range_select();
+        assert_eq!(keyspace.anchor.as_deref(), Some("a:1"));
+        assert_eq!(keyspace.targets(), ["a:1"]);
+    }
+}
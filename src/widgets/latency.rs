@@ -0,0 +1,302 @@
+use std::{
+    sync::{Arc, Mutex},
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    style::Stylize,
+    widgets::{
+        Block, BorderType, Borders, HighlightSpacing, Paragraph, Row, StatefulWidget, Table,
+        TableState, Widget, Wrap,
+    },
+};
+
+use crate::{
+    config,
+    redis_client::latency::{LatencyEvent, LatencyReport, PingStats},
+    widgets::{
+        charts::{render_chart, Series},
+        slowlog::{timestamp, RETRY_DELAY},
+    },
+};
+
+const HIGHLIGHT_SYMBOL: &str = " >> ";
+
+/// A round trip in milliseconds, with the precision a local server needs.
+pub fn round_trip(rtt: Duration) -> String {
+    format!("{:.2}ms", rtt.as_secs_f64() * 1000.0)
+}
+
+/// The spikes recorded by the latency monitor of the server, next to the round trips of `PING`
+/// measured by the app.
+pub struct Latency {
+    report: LatencyReport,
+    ping: Arc<Mutex<PingStats>>,
+    table: TableState,
+    /// Whether a fetch is on its way, so ticks do not queue up more of them.
+    pending: bool,
+    /// Until when ticks do not fetch, after a failed fetch.
+    retry_at: Option<Instant>,
+}
+
+impl Latency {
+    pub fn new(ping: Arc<Mutex<PingStats>>) -> Self {
+        Self {
+            report: LatencyReport::default(),
+            ping,
+            table: TableState::default(),
+            pending: false,
+            retry_at: None,
+        }
+    }
+
+    /// Marks a fetch as sent, `false` when one is still on its way or a failed one is too
+    /// recent.
+    pub fn request(&mut self) -> bool {
+        if self.retry_at.is_some_and(|at| Instant::now() < at) {
+            return false;
+        }
+        !std::mem::replace(&mut self.pending, true)
+    }
+
+    /// Clears the fetch on its way, ticks try again after [`RETRY_DELAY`].
+    pub fn fail(&mut self) {
+        self.pending = false;
+        self.retry_at = Some(Instant::now() + RETRY_DELAY);
+    }
+
+    /// Replaces the report, keeping the same event selected while it is still there.
+    pub fn set_report(&mut self, report: LatencyReport) {
+        let selected = self.selected().map(|event| event.name.clone());
+        self.pending = false;
+        self.retry_at = None;
+        self.report = report;
+
+        let events = &self.report.events;
+        let index = selected
+            .and_then(|name| events.iter().position(|event| event.name == name))
+            .or_else(|| (!events.is_empty()).then_some(0));
+        self.table.select(index);
+    }
+
+    pub fn selected(&self) -> Option<&LatencyEvent> {
+        self.table
+            .selected()
+            .and_then(|i| self.report.events.get(i))
+    }
+
+    pub fn scroll_next(&mut self) {
+        let len = self.report.events.len();
+        if len == 0 {
+            return;
+        }
+        let next = self.table.selected().map_or(0, |i| (i + 1) % len);
+        self.table.select(Some(next));
+    }
+
+    pub fn scroll_previous(&mut self) {
+        let len = self.report.events.len();
+        if len == 0 {
+            return;
+        }
+        let previous = self
+            .table
+            .selected()
+            .map_or(len - 1, |i| (i + len - 1) % len);
+        self.table.select(Some(previous));
+    }
+}
+
+fn panel(title: String) -> Block<'static> {
+    let colors = config::get().colors;
+    Block::new()
+        .border_style(colors.base03)
+        .border_type(BorderType::Rounded)
+        .borders(Borders::ALL)
+        .title(title.fg(colors.base0d))
+}
+
+pub struct LatencyWidget;
+
+impl StatefulWidget for LatencyWidget {
+    type State = Latency;
+
+    fn render(self, area: Rect, buf: &mut ratatui::prelude::Buffer, state: &mut Self::State) {
+        let colors = config::get().colors;
+
+        let threshold = match state.report.threshold {
+            Some(0) => "monitor disabled, set latency-monitor-threshold".into(),
+            Some(threshold) => format!("threshold {threshold}ms"),
+            None => "threshold unknown".into(),
+        };
+        let block = Block::new()
+            .bg(colors.base00)
+            .fg(colors.base04)
+            .border_type(BorderType::Rounded)
+            .borders(Borders::ALL)
+            .title(format!("Latency ({threshold}) <r> refresh"));
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        let [left, right] = Layout::horizontal([Constraint::Fill(1); 2]).areas(inner);
+        let [events_area, doctor_area] =
+            Layout::vertical([Constraint::Fill(1), Constraint::Fill(2)]).areas(left);
+        let [history_area, ping_area] = Layout::vertical([Constraint::Fill(1); 2]).areas(right);
+
+        let header = Row::new(["Event", "Latest spike (UTC)", "Latest", "Max"])
+            .bold()
+            .fg(colors.base04)
+            .bg(colors.base02);
+        let rows = state.report.events.iter().enumerate().map(|(i, event)| {
+            Row::new([
+                event.name.clone(),
+                timestamp(event.timestamp),
+                format!("{}ms", event.latest_ms),
+                format!("{}ms", event.max_ms),
+            ])
+            .fg(colors.base04)
+            .bg(if i % 2 == 0 {
+                colors.base00
+            } else {
+                colors.base01
+            })
+        });
+        let events = Table::new(
+            rows,
+            [
+                Constraint::Fill(1),
+                Constraint::Length(19),
+                Constraint::Length(8),
+                Constraint::Length(8),
+            ],
+        )
+        .header(header)
+        .block(panel(format!("Events ({})", state.report.events.len())))
+        .highlight_symbol(HIGHLIGHT_SYMBOL)
+        .highlight_style(colors.base05)
+        .highlight_spacing(HighlightSpacing::Always);
+        StatefulWidget::render(events, events_area, buf, &mut state.table);
+
+        Paragraph::new(state.report.doctor.as_str())
+            .fg(colors.base05)
+            .wrap(Wrap { trim: false })
+            .block(panel("LATENCY DOCTOR".into()))
+            .render(doctor_area, buf);
+
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_secs())
+            .unwrap_or_default();
+        match state.selected() {
+            Some(event) => {
+                let points = state
+                    .report
+                    .history(&event.name)
+                    .iter()
+                    .map(|(time, ms)| (-(now.saturating_sub(*time) as f64), *ms as f64))
+                    .collect();
+                render_chart(
+                    format!("History of {}", event.name),
+                    &[Series::new("ms", colors.base08, points)],
+                    "ms",
+                    None,
+                    history_area,
+                    buf,
+                );
+            }
+            None => Paragraph::new("No latency spikes recorded")
+                .block(panel("History".into()))
+                .render(history_area, buf),
+        }
+
+        let ping = state.ping.lock().unwrap();
+        let points = match ping.iter().last() {
+            Some((latest, _)) => ping
+                .iter()
+                .map(|(at, rtt)| {
+                    let ago = latest.duration_since(*at).as_secs_f64();
+                    (-ago, rtt.as_secs_f64() * 1000.0)
+                })
+                .collect(),
+            None => Vec::new(),
+        };
+        let title = match (ping.latest(), ping.average(), ping.max()) {
+            (Some(latest), Some(average), Some(max)) => format!(
+                "PING round trip: {}, average {}, max {}",
+                round_trip(latest),
+                round_trip(average),
+                round_trip(max)
+            ),
+            _ => "PING round trip".into(),
+        };
+        render_chart(
+            title,
+            &[Series::new("rtt", colors.base0d, points)],
+            "ms",
+            None,
+            ping_area,
+            buf,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn event(name: &str) -> LatencyEvent {
+        LatencyEvent {
+            name: name.into(),
+            timestamp: 0,
+            latest_ms: 1,
+            max_ms: 1,
+        }
+    }
+
+    #[test]
+    fn test_selection_follows_the_event() {
+        let mut latency = Latency::new(Arc::default());
+        assert!(latency.request());
+        assert!(!latency.request());
+
+        latency.set_report(LatencyReport {
+            events: vec![event("command"), event("fork")],
+            ..LatencyReport::default()
+        });
+        latency.scroll_next();
+        assert_eq!(latency.selected().map(|e| e.name.as_str()), Some("fork"));
+
+        latency.set_report(LatencyReport {
+            events: vec![event("aof-write"), event("command"), event("fork")],
+            ..LatencyReport::default()
+        });
+        assert!(latency.request());
+        assert_eq!(latency.selected().map(|e| e.name.as_str()), Some("fork"));
+
+        latency.scroll_next();
+        assert_eq!(
+            latency.selected().map(|e| e.name.as_str()),
+            Some("aof-write")
+        );
+    }
+
+    #[test]
+    fn test_failed_fetch() {
+        let mut latency = Latency::new(Arc::default());
+        assert!(latency.request());
+        latency.fail();
+        assert!(!latency.request());
+
+        latency.retry_at = Some(Instant::now());
+        assert!(latency.request());
+    }
+
+    #[test]
+    fn test_round_trip() {
+        assert_eq!(round_trip(Duration::from_micros(420)), "0.42ms");
+        assert_eq!(round_trip(Duration::from_millis(12)), "12.00ms");
+    }
+}
@@ -0,0 +1,459 @@
+use std::cmp::Ordering;
+
+use byte_unit::{Byte, UnitType};
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    style::Stylize,
+    text::Span,
+    widgets::{
+        Block, BorderType, Borders, Cell, HighlightSpacing, LineGauge, Row, StatefulWidget, Table,
+        TableState, Tabs, Widget,
+    },
+};
+use serde_json::{json, Value};
+
+use crate::{
+    config,
+    redis_client::memory::{KeyUsage, MemoryAnalysis},
+    widgets::columns::SortOrder,
+};
+
+const HIGHLIGHT_SYMBOL: &str = " >> ";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum View {
+    Biggest,
+    Cardinality,
+    Prefixes,
+}
+
+const VIEWS: [(View, &str); 3] = [
+    (View::Biggest, "Biggest keys per type"),
+    (View::Cardinality, "Most elements"),
+    (View::Prefixes, "By prefix"),
+];
+
+/// How the cells of a column are shown, they are exported as they are.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    Text,
+    Bytes,
+    Count,
+    Percent,
+}
+
+type Column = (&'static str, Kind, Constraint);
+
+const KEY_COLUMNS: [Column; 4] = [
+    ("Key", Kind::Text, Constraint::Fill(1)),
+    ("Type", Kind::Text, Constraint::Length(12)),
+    ("Memory", Kind::Bytes, Constraint::Length(12)),
+    ("Elements", Kind::Count, Constraint::Length(12)),
+];
+
+const PREFIX_COLUMNS: [Column; 5] = [
+    ("Prefix", Kind::Text, Constraint::Fill(1)),
+    ("Keys", Kind::Count, Constraint::Length(12)),
+    ("Memory", Kind::Bytes, Constraint::Length(12)),
+    ("Share", Kind::Percent, Constraint::Length(8)),
+    ("Average", Kind::Bytes, Constraint::Length(12)),
+];
+
+fn key_row(usage: &KeyUsage) -> Vec<Value> {
+    vec![
+        json!(usage.key),
+        json!(usage.key_type),
+        json!(usage.bytes),
+        json!(usage.cardinality),
+    ]
+}
+
+/// Numbers by value, nulls first, the rest as text.
+fn compare(a: &Value, b: &Value) -> Ordering {
+    match (a, b) {
+        (Value::Number(a), Value::Number(b)) => a
+            .as_f64()
+            .partial_cmp(&b.as_f64())
+            .unwrap_or(Ordering::Equal),
+        (Value::Null, Value::Null) => Ordering::Equal,
+        (Value::Null, _) => Ordering::Less,
+        (_, Value::Null) => Ordering::Greater,
+        (a, b) => a.to_string().cmp(&b.to_string()),
+    }
+}
+
+fn format_cell(kind: Kind, value: &Value) -> String {
+    match (kind, value) {
+        (_, Value::Null) => "-".into(),
+        (Kind::Text, Value::String(text)) if text.is_empty() => "(no prefix)".into(),
+        (Kind::Text, Value::String(text)) => text.clone(),
+        (Kind::Bytes, value) => format!(
+            "{:.1}",
+            Byte::from_u64(value.as_u64().unwrap_or_default())
+                .get_appropriate_unit(UnitType::Binary)
+        ),
+        (Kind::Percent, value) => format!("{:.1}%", value.as_f64().unwrap_or_default()),
+        (_, value) => value.to_string(),
+    }
+}
+
+/// The results of the memory analysis, as one of its tables.
+pub struct Memory {
+    analysis: Option<MemoryAnalysis>,
+    /// Whether an analysis is on its way.
+    running: bool,
+    view: View,
+    rows: Vec<Vec<Value>>,
+    /// Column under the column cursor.
+    current: usize,
+    sort: Option<(usize, SortOrder)>,
+    table: TableState,
+}
+
+impl Memory {
+    pub fn new() -> Self {
+        Self {
+            analysis: None,
+            running: false,
+            view: View::Biggest,
+            rows: Vec::new(),
+            current: 0,
+            sort: Some((2, SortOrder::Descending)),
+            table: TableState::default(),
+        }
+    }
+
+    /// Whether an analysis was ever started.
+    pub fn is_started(&self) -> bool {
+        self.running || self.analysis.is_some()
+    }
+
+    /// Forgets the results, for a new analysis.
+    pub fn start(&mut self) {
+        self.running = true;
+        self.analysis = None;
+        self.apply(None);
+    }
+
+    pub fn stop(&mut self) {
+        self.running = false;
+    }
+
+    pub fn set_analysis(&mut self, analysis: MemoryAnalysis) {
+        // Progress of a stopped analysis still on its way is dropped
+        if !self.running {
+            return;
+        }
+        self.running = !analysis.finished;
+        self.analysis = Some(analysis);
+
+        let selected = self.selected_row().map(|row| row[0].clone());
+        self.apply(selected);
+    }
+
+    fn columns(&self) -> &'static [Column] {
+        match self.view {
+            View::Prefixes => &PREFIX_COLUMNS,
+            _ => &KEY_COLUMNS,
+        }
+    }
+
+    /// Switches to the next table, sorted by its own measure.
+    pub fn next_view(&mut self) {
+        let idx = VIEWS.iter().position(|(view, _)| *view == self.view);
+        self.view = VIEWS[idx.map_or(0, |idx| (idx + 1) % VIEWS.len())].0;
+        self.current = 0;
+        self.sort = match self.view {
+            View::Cardinality => Some((3, SortOrder::Descending)),
+            _ => Some((2, SortOrder::Descending)),
+        };
+        self.apply(None);
+    }
+
+    pub fn next_column(&mut self) {
+        self.current = (self.current + 1) % self.columns().len();
+    }
+
+    pub fn previous_column(&mut self) {
+        let len = self.columns().len();
+        self.current = (self.current + len - 1) % len;
+    }
+
+    /// Ascending, descending, then in the order of the analysis, like the key table.
+    pub fn toggle_sort(&mut self) {
+        let column = self.current;
+        self.sort = match self.sort {
+            Some((sorted, SortOrder::Ascending)) if sorted == column => {
+                Some((column, SortOrder::Descending))
+            }
+            Some((sorted, SortOrder::Descending)) if sorted == column => None,
+            _ => Some((column, SortOrder::Ascending)),
+        };
+
+        let selected = self.selected_row().map(|row| row[0].clone());
+        self.apply(selected);
+    }
+
+    /// Builds the rows of the current view again, keeping the row of the same key or prefix
+    /// selected.
+    fn apply(&mut self, selected: Option<Value>) {
+        self.rows = match (&self.analysis, self.view) {
+            (None, _) => Vec::new(),
+            (Some(analysis), View::Biggest) => {
+                let mut types: Vec<&String> = analysis.biggest.keys().collect();
+                types.sort();
+                types
+                    .into_iter()
+                    .flat_map(|key_type| &analysis.biggest[key_type])
+                    .map(key_row)
+                    .collect()
+            }
+            (Some(analysis), View::Cardinality) => {
+                analysis.cardinality.iter().map(key_row).collect()
+            }
+            (Some(analysis), View::Prefixes) => {
+                let mut prefixes: Vec<_> = analysis.prefixes.values().collect();
+                prefixes.sort_by(|a, b| a.prefix.cmp(&b.prefix));
+                prefixes
+                    .into_iter()
+                    .map(|usage| {
+                        vec![
+                            json!(usage.prefix),
+                            json!(usage.keys),
+                            json!(usage.bytes),
+                            json!(usage.bytes as f64 * 100.0 / analysis.bytes.max(1) as f64),
+                            json!(usage.bytes / usage.keys.max(1)),
+                        ]
+                    })
+                    .collect()
+            }
+        };
+
+        if let Some((column, order)) = self.sort {
+            self.rows.sort_by(|a, b| {
+                let ordering = compare(&a[column], &b[column]);
+                match order {
+                    SortOrder::Ascending => ordering,
+                    SortOrder::Descending => ordering.reverse(),
+                }
+            });
+        }
+
+        let index = selected
+            .and_then(|first| self.rows.iter().position(|row| row[0] == first))
+            .or_else(|| (!self.rows.is_empty()).then_some(0));
+        self.table.select(index);
+    }
+
+    fn selected_row(&self) -> Option<&Vec<Value>> {
+        self.table.selected().and_then(|i| self.rows.get(i))
+    }
+
+    /// The name, headers and rows of the current table, for exporting it.
+    pub fn export(&self) -> (&'static str, Vec<&'static str>, Vec<Vec<Value>>) {
+        let name = match self.view {
+            View::Biggest => "memory-biggest",
+            View::Cardinality => "memory-elements",
+            View::Prefixes => "memory-prefixes",
+        };
+        let headers = self.columns().iter().map(|(name, _, _)| *name).collect();
+        (name, headers, self.rows.clone())
+    }
+
+    pub fn scroll_next(&mut self) {
+        if self.rows.is_empty() {
+            return;
+        }
+        let next = self
+            .table
+            .selected()
+            .map_or(0, |i| (i + 1) % self.rows.len());
+        self.table.select(Some(next));
+    }
+
+    pub fn scroll_previous(&mut self) {
+        if self.rows.is_empty() {
+            return;
+        }
+        let last = self.rows.len() - 1;
+        let previous = self
+            .table
+            .selected()
+            .map_or(last, |i| (i + last) % self.rows.len());
+        self.table.select(Some(previous));
+    }
+}
+
+pub struct MemoryWidget;
+
+impl StatefulWidget for MemoryWidget {
+    type State = Memory;
+
+    fn render(self, area: Rect, buf: &mut ratatui::prelude::Buffer, state: &mut Self::State) {
+        let colors = config::get().colors;
+
+        let block = Block::new()
+            .bg(colors.base00)
+            .fg(colors.base04)
+            .border_type(BorderType::Rounded)
+            .borders(Borders::ALL)
+            .title(
+                "Memory analysis <Tab> view, <[/]> column, <o> sort, <e/E> export CSV/JSON, \
+                 <r> restart, <x> stop",
+            );
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        let [progress_area, tabs_area, table_area] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Length(2),
+            Constraint::Fill(1),
+        ])
+        .areas(inner);
+
+        let (scanned, total, bytes) = state
+            .analysis
+            .as_ref()
+            .map_or((0, 0, 0), |a| (a.scanned, a.total, a.bytes));
+        let status = match (&state.analysis, state.running) {
+            (_, true) => "running",
+            (Some(analysis), false) if analysis.finished => "done",
+            (Some(_), false) => "stopped",
+            (None, false) => "not started",
+        };
+        let ratio = if total == 0 {
+            f64::from(u8::from(status == "done"))
+        } else {
+            (scanned as f64 / total as f64).min(1.0)
+        };
+        LineGauge::default()
+            .filled_style(colors.base0d)
+            .unfilled_style(colors.base03)
+            .label(format!(
+                "{status}: {scanned} of ~{total} keys, {:.1} ",
+                Byte::from_u64(bytes).get_appropriate_unit(UnitType::Binary)
+            ))
+            .ratio(ratio)
+            .render(progress_area, buf);
+
+        let selected = VIEWS.iter().position(|(view, _)| *view == state.view);
+        Tabs::new(VIEWS.map(|(_, title)| title))
+            .select(selected.unwrap_or_default())
+            .highlight_style(colors.base0d)
+            .fg(colors.base04)
+            .block(
+                Block::new()
+                    .borders(Borders::BOTTOM)
+                    .border_style(colors.base03),
+            )
+            .render(tabs_area, buf);
+
+        let columns = state.columns();
+        let header = Row::new(columns.iter().enumerate().map(|(idx, (name, _, _))| {
+            let indicator = match state.sort {
+                Some((sorted, SortOrder::Ascending)) if sorted == idx => " ▲",
+                Some((sorted, SortOrder::Descending)) if sorted == idx => " ▼",
+                _ => "",
+            };
+
+            let mut title = Span::raw(format!("{name}{indicator}")).bold();
+            if idx == state.current {
+                title = title.underlined().fg(colors.base0d);
+            }
+            Cell::from(title)
+        }))
+        .fg(colors.base04)
+        .bg(colors.base02);
+
+        let rows = state.rows.iter().enumerate().map(|(i, row)| {
+            Row::new(
+                columns
+                    .iter()
+                    .zip(row)
+                    .map(|((_, kind, _), value)| format_cell(*kind, value)),
+            )
+            .fg(colors.base04)
+            .bg(if i % 2 == 0 {
+                colors.base00
+            } else {
+                colors.base01
+            })
+        });
+
+        let table = Table::new(rows, columns.iter().map(|(_, _, width)| *width))
+            .header(header)
+            .highlight_symbol(HIGHLIGHT_SYMBOL)
+            .highlight_style(colors.base05)
+            .highlight_spacing(HighlightSpacing::Always);
+        StatefulWidget::render(table, table_area, buf, &mut state.table);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn usage(key: &str, key_type: &str, bytes: u64, cardinality: Option<u64>) -> KeyUsage {
+        KeyUsage {
+            key: key.into(),
+            key_type: key_type.into(),
+            bytes,
+            cardinality,
+        }
+    }
+
+    fn first_column(memory: &Memory) -> Vec<String> {
+        memory
+            .rows
+            .iter()
+            .map(|row| row[0].as_str().unwrap_or_default().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_views_and_sort() {
+        let mut analysis = MemoryAnalysis::default();
+        analysis.add(usage("user:1", "hash", 300, Some(2)), ":");
+        analysis.add(usage("user:2", "hash", 100, Some(9)), ":");
+        analysis.add(usage("counter", "string", 200, None), ":");
+        analysis.finished = true;
+
+        let mut memory = Memory::new();
+        memory.start();
+        memory.set_analysis(analysis);
+        assert!(!memory.running);
+        assert_eq!(first_column(&memory), ["user:1", "counter", "user:2"]);
+
+        // The selection stays on the key when sorted by name ascending
+        memory.scroll_next();
+        memory.toggle_sort();
+        assert_eq!(first_column(&memory), ["counter", "user:1", "user:2"]);
+        assert_eq!(
+            memory.selected_row().map(|row| row[0].clone()),
+            Some(json!("counter"))
+        );
+
+        memory.next_view();
+        assert_eq!(first_column(&memory), ["user:2", "user:1"]);
+
+        memory.next_view();
+        assert_eq!(first_column(&memory), ["user:", ""]);
+        assert_eq!(memory.rows[0][3], json!(400.0 * 100.0 / 600.0));
+
+        let (name, headers, rows) = memory.export();
+        assert_eq!(name, "memory-prefixes");
+        assert_eq!(headers, ["Prefix", "Keys", "Memory", "Share", "Average"]);
+        assert_eq!(rows[1][4], json!(200));
+    }
+
+    #[test]
+    fn test_stopped_analysis_is_not_updated() {
+        let mut memory = Memory::new();
+        memory.start();
+        memory.stop();
+        memory.set_analysis(MemoryAnalysis::default());
+        assert!(!memory.is_started());
+    }
+}
@@ -0,0 +1,330 @@
+use std::collections::VecDeque;
+
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    style::{Style, Stylize},
+    widgets::{
+        Block, BorderType, Borders, HighlightSpacing, Row, Sparkline, StatefulWidget, Table,
+        TableState, Widget,
+    },
+};
+
+use crate::{
+    config,
+    redis_client::monitor::{MonitorEntry, CAPACITY},
+    widgets::filter::{KeyFilter, MatchMode},
+};
+
+const HIGHLIGHT_SYMBOL: &str = " >> ";
+/// Seconds of the commands per second histogram.
+const HISTORY: usize = 300;
+
+/// Whether the glob pattern matches the command name, ignoring case, or one of the arguments
+/// such as a key.
+fn matches(entry: &MonitorEntry, pattern: &str) -> bool {
+    if pattern.is_empty() {
+        return true;
+    }
+
+    let command = KeyFilter::new(MatchMode::Glob, pattern.to_uppercase());
+    let argument = KeyFilter::new(MatchMode::Glob, pattern);
+    command.matches(&entry.command()).is_some()
+        || entry
+            .args
+            .iter()
+            .skip(1)
+            .any(|arg| argument.matches(arg).is_some())
+}
+
+/// `HH:MM:SS.ffffff`, in UTC.
+pub fn clock(time: f64) -> String {
+    let secs = time as u64 % 86400;
+    format!(
+        "{:02}:{:02}:{:02}.{:06}",
+        secs / 3600,
+        secs % 3600 / 60,
+        secs % 60,
+        (time.fract() * 1_000_000.0) as u64
+    )
+}
+
+fn command_line(args: &[String]) -> String {
+    args.iter()
+        .map(|arg| format!("{arg:?}"))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// The commands streamed by `MONITOR`, the newest at the bottom.
+pub struct Monitor {
+    entries: VecDeque<MonitorEntry>,
+    /// Entries received while paused, shown on resume.
+    held: VecDeque<MonitorEntry>,
+    paused: bool,
+    filter: String,
+    /// Commands counted per second of server time, oldest first.
+    rates: VecDeque<(u64, u64)>,
+    table: TableState,
+}
+
+impl Monitor {
+    pub fn new() -> Self {
+        Self {
+            entries: VecDeque::new(),
+            held: VecDeque::new(),
+            paused: false,
+            filter: String::new(),
+            rates: VecDeque::new(),
+            table: TableState::default(),
+        }
+    }
+
+    /// Starts over for a new stream.
+    pub fn clear(&mut self) {
+        *self = Self {
+            filter: std::mem::take(&mut self.filter),
+            ..Self::new()
+        };
+    }
+
+    pub fn push(&mut self, entries: impl IntoIterator<Item = MonitorEntry>) {
+        for entry in entries {
+            self.count(entry.time as u64);
+
+            let buffer = if self.paused {
+                &mut self.held
+            } else {
+                &mut self.entries
+            };
+            if buffer.len() == CAPACITY {
+                buffer.pop_front();
+            }
+            buffer.push_back(entry);
+        }
+    }
+
+    fn count(&mut self, second: u64) {
+        match self.rates.back_mut() {
+            Some((last, count)) if *last == second => *count += 1,
+            // Out of order by a little, counted in the latest second
+            Some((last, count)) if *last > second => *count += 1,
+            _ => {
+                if self.rates.len() == HISTORY {
+                    self.rates.pop_front();
+                }
+                self.rates.push_back((second, 1));
+            }
+        }
+    }
+
+    /// Commands per second for the last `width` seconds before the latest one, oldest first,
+    /// with zeros for the seconds without any.
+    fn histogram(&self, width: usize) -> Vec<u64> {
+        let Some((latest, _)) = self.rates.back() else {
+            return Vec::new();
+        };
+
+        let first = latest.saturating_sub(width.saturating_sub(1) as u64);
+        let mut histogram = vec![0; (latest - first) as usize + 1];
+        for (second, count) in &self.rates {
+            if *second >= first {
+                histogram[(second - first) as usize] = *count;
+            }
+        }
+        histogram
+    }
+
+    pub fn toggle_pause(&mut self) {
+        self.paused = !self.paused;
+        if self.paused {
+            return;
+        }
+
+        for entry in self.held.drain(..) {
+            if self.entries.len() == CAPACITY {
+                self.entries.pop_front();
+            }
+            self.entries.push_back(entry);
+        }
+        self.table.select(None);
+    }
+
+    pub fn filter(&self) -> &str {
+        &self.filter
+    }
+
+    pub fn set_filter(&mut self, filter: String) {
+        self.filter = filter;
+        self.table.select(None);
+    }
+
+    fn visible(&self) -> Vec<&MonitorEntry> {
+        self.entries
+            .iter()
+            .filter(|entry| matches(entry, &self.filter))
+            .collect()
+    }
+
+    /// Scrolling pauses the stream, so that the rows stay where they are.
+    pub fn scroll_next(&mut self) {
+        self.scroll(1);
+    }
+
+    pub fn scroll_previous(&mut self) {
+        self.scroll(-1);
+    }
+
+    fn scroll(&mut self, delta: isize) {
+        let len = self.visible().len();
+        if len == 0 {
+            return;
+        }
+        self.paused = true;
+
+        let current = self.table.selected().unwrap_or(len - 1);
+        let next = current.saturating_add_signed(delta).min(len - 1);
+        self.table.select(Some(next));
+    }
+}
+
+pub struct MonitorWidget;
+
+impl StatefulWidget for MonitorWidget {
+    type State = Monitor;
+
+    fn render(self, area: Rect, buf: &mut ratatui::prelude::Buffer, state: &mut Self::State) {
+        let colors = config::get().colors;
+
+        let visible = state.visible();
+        let mut title = format!("Monitor ({} of {})", visible.len(), state.entries.len());
+        if !state.filter.is_empty() {
+            title.push_str(&format!(" matching {:?}", state.filter));
+        }
+        if state.paused {
+            title.push_str(&format!(" PAUSED, {} held", state.held.len()));
+        }
+        title.push_str(" <Space> pause, <f> filter");
+
+        let block = Block::new()
+            .bg(colors.base00)
+            .fg(colors.base04)
+            .border_type(BorderType::Rounded)
+            .borders(Borders::ALL)
+            .title(title);
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        let [table_area, histogram_area] =
+            Layout::vertical([Constraint::Fill(1), Constraint::Length(6)]).areas(inner);
+
+        let header = Row::new(["Time (UTC)", "Db", "Client", "Command"])
+            .bold()
+            .bottom_margin(1)
+            .fg(colors.base04)
+            .bg(colors.base02);
+
+        let rows: Vec<Row> = visible
+            .iter()
+            .enumerate()
+            .map(|(i, entry)| {
+                Row::new([
+                    clock(entry.time),
+                    entry.db.to_string(),
+                    entry.client.clone(),
+                    command_line(&entry.args),
+                ])
+                .fg(colors.base04)
+                .bg(if i % 2 == 0 {
+                    colors.base00
+                } else {
+                    colors.base01
+                })
+            })
+            .collect();
+        let len = rows.len();
+
+        // Follow the newest entry unless paused
+        let mut table_state = state.table.clone();
+        if !state.paused || table_state.selected().is_none() {
+            table_state.select(len.checked_sub(1));
+        }
+
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(16),
+                Constraint::Length(4),
+                Constraint::Length(22),
+                Constraint::Fill(1),
+            ],
+        )
+        .header(header)
+        .highlight_symbol(HIGHLIGHT_SYMBOL)
+        .highlight_style(colors.base05)
+        .highlight_spacing(HighlightSpacing::Always);
+        StatefulWidget::render(table, table_area, buf, &mut table_state);
+        if state.paused {
+            state.table = table_state;
+        }
+
+        let width = usize::from(histogram_area.width.saturating_sub(2));
+        let histogram = state.histogram(width);
+        Sparkline::default()
+            .block(
+                Block::new()
+                    .borders(Borders::TOP)
+                    .border_style(colors.base03)
+                    .title(format!(
+                        "Commands/sec: {}",
+                        histogram.last().copied().unwrap_or_default()
+                    )),
+            )
+            .style(Style::default().fg(colors.base0d))
+            .data(&histogram)
+            .render(histogram_area, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn entry(time: f64, args: &[&str]) -> MonitorEntry {
+        MonitorEntry {
+            time,
+            db: 0,
+            client: "lua".into(),
+            args: args.iter().map(|arg| arg.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_matches() {
+        let entry = entry(1.0, &["hget", "user:1", "name"]);
+
+        assert!(matches(&entry, ""));
+        assert!(matches(&entry, "HGET"));
+        assert!(matches(&entry, "h*"));
+        assert!(matches(&entry, "user:*"));
+        assert!(!matches(&entry, "set"));
+        assert!(!matches(&entry, "order:*"));
+    }
+
+    #[test]
+    fn test_pause_and_histogram() {
+        let mut monitor = Monitor::new();
+        monitor.push(vec![entry(10.1, &["GET", "a"]), entry(10.5, &["GET", "b"])]);
+
+        monitor.toggle_pause();
+        monitor.push(vec![entry(13.0, &["SET", "a", "1"])]);
+        assert_eq!(monitor.entries.len(), 2);
+        assert_eq!(monitor.held.len(), 1);
+
+        monitor.toggle_pause();
+        assert_eq!(monitor.entries.len(), 3);
+        assert_eq!(monitor.histogram(5), [0, 2, 0, 0, 1]);
+        assert_eq!(monitor.histogram(2), [0, 1]);
+    }
+}
@@ -0,0 +1,285 @@
+use ratatui::{
+    layout::{Alignment, Constraint, Flex, Layout, Position, Rect},
+    style::{Style, Stylize},
+    text::Span,
+    widgets::{Block, BorderType, Borders, Clear, Paragraph, StatefulWidget, Widget, Wrap},
+};
+
+use crossterm::event::{KeyCode, KeyEvent};
+use tui_textarea::TextArea;
+
+use crate::{action::Action, config, mode::PopupMode};
+
+/// A modal message on top of the current mode, `Confirm` popups emit `on_confirm` when accepted.
+/// `Input` popups also take a line of text, handed to the action with [`Action::with_input`].
+#[derive(Debug, Clone)]
+pub struct Popup {
+    pub mode: PopupMode,
+    pub title: String,
+    pub message: String,
+    pub on_confirm: Option<Action>,
+    input: Option<TextArea<'static>>,
+    /// What `<Tab>` completes the input from.
+    completions: Vec<String>,
+    /// Where the accept and the dismiss buttons were drawn, for mouse clicks.
+    buttons: (Rect, Rect),
+}
+
+impl Popup {
+    pub fn error(message: impl Into<String>) -> Self {
+        Self {
+            mode: PopupMode::Error,
+            title: "Error".into(),
+            message: message.into(),
+            on_confirm: None,
+            input: None,
+            completions: Vec::new(),
+            buttons: Default::default(),
+        }
+    }
+
+    pub fn info(message: impl Into<String>) -> Self {
+        Self {
+            mode: PopupMode::Info,
+            title: "Info".into(),
+            message: message.into(),
+            on_confirm: None,
+            input: None,
+            completions: Vec::new(),
+            buttons: Default::default(),
+        }
+    }
+
+    pub fn confirm(title: impl Into<String>, message: impl Into<String>, action: Action) -> Self {
+        Self {
+            mode: PopupMode::Confirm,
+            title: title.into(),
+            message: message.into(),
+            on_confirm: Some(action),
+            input: None,
+            completions: Vec::new(),
+            buttons: Default::default(),
+        }
+    }
+
+    pub fn input(title: impl Into<String>, message: impl Into<String>, action: Action) -> Self {
+        let mut input = TextArea::default();
+        input.set_cursor_line_style(Style::default());
+
+        Self {
+            mode: PopupMode::Input,
+            title: title.into(),
+            message: message.into(),
+            on_confirm: Some(action),
+            input: Some(input),
+            completions: Vec::new(),
+            buttons: Default::default(),
+        }
+    }
+
+    #[must_use]
+    pub fn completions(mut self, completions: Vec<String>) -> Self {
+        self.completions = completions;
+        self
+    }
+
+    pub fn is_input(&self) -> bool {
+        self.input.is_some()
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) {
+        if key.code == KeyCode::Tab && !self.completions.is_empty() {
+            self.complete();
+        } else if let Some(ref mut input) = self.input {
+            input.input(key);
+        }
+    }
+
+    /// Fills in the completion when there is a single one, or as much as all of them share
+    /// and lists them in the message.
+    fn complete(&mut self) {
+        let Some(ref mut input) = self.input else {
+            return;
+        };
+
+        let line = input.lines().join("");
+        let matches: Vec<&String> = self
+            .completions
+            .iter()
+            .filter(|candidate| candidate.starts_with(&line))
+            .collect();
+
+        let completion = match matches.as_slice() {
+            [] => {
+                self.message = format!("Nothing starts with {line}");
+                return;
+            }
+            [single] => single.to_string(),
+            matches => {
+                let shown: Vec<&str> = matches.iter().take(5).map(|m| m.as_str()).collect();
+                self.message = format!("{} matches: {}", matches.len(), shown.join(", "));
+                common_prefix(matches)
+            }
+        };
+
+        let mut completed = TextArea::from([completion]);
+        completed.set_cursor_line_style(Style::default());
+        completed.move_cursor(tui_textarea::CursorMove::End);
+        *input = completed;
+    }
+
+    /// `Some(true)` for a click on the accept button, `Some(false)` on the dismiss one.
+    pub fn button_at(&self, column: u16, row: u16) -> Option<bool> {
+        let position = Position::new(column, row);
+        let (accept, dismiss) = self.buttons;
+
+        if accept.contains(position) {
+            Some(true)
+        } else if dismiss.contains(position) {
+            Some(false)
+        } else {
+            None
+        }
+    }
+
+    /// The action to emit now that the popup was accepted.
+    pub fn confirm_action(self) -> Option<Action> {
+        let action = self.on_confirm?;
+        Some(match self.input {
+            Some(input) => action.with_input(input.lines().join("")),
+            None => action,
+        })
+    }
+}
+
+fn common_prefix(words: &[&String]) -> String {
+    let Some(first) = words.first() else {
+        return String::new();
+    };
+
+    let mut len = first.len();
+    for word in &words[1..] {
+        len = first
+            .char_indices()
+            .zip(word.chars())
+            .find(|((_, a), b)| a != b)
+            .map_or(len.min(word.len()), |((idx, _), _)| idx.min(len));
+    }
+
+    first[..len].to_owned()
+}
+
+pub struct PopupWidget;
+
+impl StatefulWidget for PopupWidget {
+    type State = Popup;
+
+    fn render(self, area: Rect, buf: &mut ratatui::prelude::Buffer, state: &mut Self::State) {
+        let colors = config::get().colors;
+
+        let [popup_area] = Layout::horizontal([Constraint::Percentage(50)])
+            .flex(Flex::Center)
+            .areas(area);
+        let [popup_area] = Layout::vertical([Constraint::Length(8)])
+            .flex(Flex::Center)
+            .areas(popup_area);
+
+        let border = match state.mode {
+            PopupMode::Error => colors.base08,
+            PopupMode::Info => colors.base0d,
+            PopupMode::Confirm | PopupMode::Input => colors.base09,
+        };
+
+        let block = Block::new()
+            .bg(colors.base00)
+            .fg(colors.base05)
+            .border_style(border)
+            .border_type(BorderType::Rounded)
+            .borders(Borders::ALL)
+            .title(state.title.as_str());
+
+        let inner = block.inner(popup_area);
+        Clear.render(popup_area, buf);
+        block.render(popup_area, buf);
+
+        let [message_area, input_area, buttons_area] = Layout::vertical([
+            Constraint::Fill(1),
+            Constraint::Length(u16::from(state.input.is_some())),
+            Constraint::Length(1),
+        ])
+        .margin(1)
+        .areas(inner);
+
+        Paragraph::new(state.message.as_str())
+            .wrap(Wrap { trim: true })
+            .alignment(Alignment::Center)
+            .render(message_area, buf);
+
+        if let Some(ref mut input) = state.input {
+            input.set_style(Style::default().bg(colors.base02));
+            input.render(input_area, buf);
+        }
+
+        let accept = |label: &'static str| Span::raw(label).fg(colors.base00).bg(border);
+
+        state.buttons = match state.mode {
+            PopupMode::Confirm | PopupMode::Input => {
+                let [yes, _, no] = Layout::horizontal([
+                    Constraint::Length(13),
+                    Constraint::Length(3),
+                    Constraint::Length(10),
+                ])
+                .flex(Flex::Center)
+                .areas(buttons_area);
+
+                Paragraph::new(accept(" Yes <Enter> ")).render(yes, buf);
+                Paragraph::new(Span::raw(" No <Esc> ").fg(colors.base00).bg(colors.base03))
+                    .render(no, buf);
+                (yes, no)
+            }
+            _ => {
+                let [ok] = Layout::horizontal([Constraint::Length(12)])
+                    .flex(Flex::Center)
+                    .areas(buttons_area);
+
+                Paragraph::new(accept(" Ok <Enter> ")).render(ok, buf);
+                (ok, Rect::default())
+            }
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::KeyModifiers;
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn press(popup: &mut Popup, code: KeyCode) {
+        popup.handle_key(KeyEvent::new(code, KeyModifiers::NONE));
+    }
+
+    #[test]
+    fn test_complete() {
+        let mut popup =
+            Popup::input("Go to key", "", Action::GoToKey(String::new())).completions(vec![
+                "user:1".into(),
+                "user:10".into(),
+                "user:2".into(),
+                "tags".into(),
+            ]);
+
+        press(&mut popup, KeyCode::Char('u'));
+        press(&mut popup, KeyCode::Tab);
+        assert_eq!(popup.input.as_ref().unwrap().lines(), ["user:"]);
+        assert_eq!(popup.message, "3 matches: user:1, user:10, user:2");
+
+        press(&mut popup, KeyCode::Char('2'));
+        press(&mut popup, KeyCode::Tab);
+        assert_eq!(
+            popup.confirm_action(),
+            Some(Action::GoToKey("user:2".into()))
+        );
+    }
+}
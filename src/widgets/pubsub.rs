@@ -0,0 +1,301 @@
+use std::collections::VecDeque;
+
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    style::Stylize,
+    widgets::{
+        Block, BorderType, Borders, HighlightSpacing, Row, StatefulWidget, Table, TableState,
+        Widget,
+    },
+};
+
+use crate::{
+    config,
+    redis_client::pubsub::{
+        ActiveChannels, PubSubMessage, Subscription, SubscriptionKind, CAPACITY,
+    },
+    widgets::monitor::clock,
+};
+
+const HIGHLIGHT_SYMBOL: &str = " >> ";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Focus {
+    Messages,
+    Subscriptions,
+}
+
+/// Subscriptions of the dedicated Pub/Sub connection with the messages they received, and the
+/// channels active on the server.
+pub struct PubSub {
+    subscriptions: Vec<Subscription>,
+    messages: VecDeque<PubSubMessage>,
+    channels: ActiveChannels,
+    focus: Focus,
+    messages_table: TableState,
+    /// Whether the newest message stays selected as messages come in.
+    follow: bool,
+    subscriptions_table: TableState,
+}
+
+impl PubSub {
+    pub fn new() -> Self {
+        Self {
+            subscriptions: Vec::new(),
+            messages: VecDeque::new(),
+            channels: ActiveChannels::default(),
+            focus: Focus::Messages,
+            messages_table: TableState::default(),
+            follow: true,
+            subscriptions_table: TableState::default(),
+        }
+    }
+
+    pub fn subscriptions(&self) -> &[Subscription] {
+        &self.subscriptions
+    }
+
+    /// Adds the subscription, `false` when it is already there.
+    pub fn subscribe(&mut self, kind: SubscriptionKind, name: String) -> bool {
+        let subscription = Subscription { kind, name };
+        if self.subscriptions.contains(&subscription) {
+            return false;
+        }
+
+        self.subscriptions.push(subscription);
+        self.subscriptions_table
+            .select(Some(self.subscriptions.len() - 1));
+        true
+    }
+
+    /// Removes the selected subscription, `false` when there is none.
+    pub fn unsubscribe_selected(&mut self) -> bool {
+        let Some(index) = self
+            .subscriptions_table
+            .selected()
+            .filter(|i| *i < self.subscriptions.len())
+        else {
+            return false;
+        };
+
+        self.subscriptions.remove(index);
+        let last = self.subscriptions.len().checked_sub(1);
+        self.subscriptions_table
+            .select(last.map(|last| index.min(last)));
+        true
+    }
+
+    /// `SPUBLISH` for the channels subscribed with `SSUBSCRIBE`, `PUBLISH` otherwise.
+    pub fn publish_kind(&self, channel: &str) -> SubscriptionKind {
+        let sharded = self
+            .subscriptions
+            .iter()
+            .any(|s| s.kind == SubscriptionKind::Shard && s.name == channel);
+        if sharded {
+            SubscriptionKind::Shard
+        } else {
+            SubscriptionKind::Channel
+        }
+    }
+
+    pub fn push(&mut self, messages: impl IntoIterator<Item = PubSubMessage>) {
+        for message in messages {
+            if self.messages.len() == CAPACITY {
+                self.messages.pop_front();
+            }
+            self.messages.push_back(message);
+        }
+    }
+
+    pub fn set_channels(&mut self, channels: ActiveChannels) {
+        self.channels = channels;
+    }
+
+    pub fn next_focus(&mut self) {
+        self.focus = match self.focus {
+            Focus::Messages => Focus::Subscriptions,
+            Focus::Subscriptions => Focus::Messages,
+        };
+    }
+
+    pub fn scroll_next(&mut self) {
+        self.scroll(1);
+    }
+
+    pub fn scroll_previous(&mut self) {
+        self.scroll(-1);
+    }
+
+    fn scroll(&mut self, delta: isize) {
+        let (table, len) = match self.focus {
+            Focus::Messages => (&mut self.messages_table, self.messages.len()),
+            Focus::Subscriptions => (&mut self.subscriptions_table, self.subscriptions.len()),
+        };
+        if len == 0 {
+            return;
+        }
+
+        let current = table.selected().unwrap_or(len - 1);
+        let next = current.saturating_add_signed(delta).min(len - 1);
+        table.select(Some(next));
+
+        if self.focus == Focus::Messages {
+            self.follow = next == len - 1;
+        }
+    }
+}
+
+fn panel(title: String, focused: bool) -> Block<'static> {
+    let colors = config::get().colors;
+    Block::new()
+        .border_type(BorderType::Rounded)
+        .borders(Borders::ALL)
+        .border_style(if focused {
+            colors.base0d
+        } else {
+            colors.base03
+        })
+        .title(title)
+}
+
+pub struct PubSubWidget;
+
+impl StatefulWidget for PubSubWidget {
+    type State = PubSub;
+
+    fn render(self, area: Rect, buf: &mut ratatui::prelude::Buffer, state: &mut Self::State) {
+        let colors = config::get().colors;
+
+        Block::new().bg(colors.base00).render(area, buf);
+        let [messages_area, side_area] =
+            Layout::horizontal([Constraint::Fill(3), Constraint::Fill(1)]).areas(area);
+        let [subscriptions_area, channels_area] =
+            Layout::vertical([Constraint::Fill(1); 2]).areas(side_area);
+
+        let rows = state.messages.iter().enumerate().map(|(i, message)| {
+            let channel = match message.pattern {
+                Some(ref pattern) => format!("{} ({pattern})", message.channel),
+                None if message.sharded => format!("{} (shard)", message.channel),
+                None => message.channel.clone(),
+            };
+            Row::new([clock(message.time), channel, message.payload.clone()])
+                .fg(colors.base04)
+                .bg(if i % 2 == 0 {
+                    colors.base00
+                } else {
+                    colors.base01
+                })
+        });
+        if state.follow {
+            state
+                .messages_table
+                .select(state.messages.len().checked_sub(1));
+        }
+        let header = Row::new(["Received (UTC)", "Channel", "Message"])
+            .bold()
+            .fg(colors.base04)
+            .bg(colors.base02);
+        let messages = Table::new(
+            rows,
+            [
+                Constraint::Length(16),
+                Constraint::Fill(1),
+                Constraint::Fill(3),
+            ],
+        )
+        .header(header)
+        .block(panel(
+            format!(
+                "Messages ({}) <s/p/S> subscribe, <m> publish, <Tab> focus",
+                state.messages.len()
+            ),
+            state.focus == Focus::Messages,
+        ))
+        .highlight_symbol(HIGHLIGHT_SYMBOL)
+        .highlight_style(colors.base05)
+        .highlight_spacing(HighlightSpacing::Always);
+        StatefulWidget::render(messages, messages_area, buf, &mut state.messages_table);
+
+        let rows = state.subscriptions.iter().map(|subscription| {
+            Row::new([
+                subscription.kind.subscribe_command().to_lowercase(),
+                subscription.name.clone(),
+            ])
+            .fg(colors.base04)
+        });
+        let subscriptions = Table::new(rows, [Constraint::Length(11), Constraint::Fill(1)])
+            .block(panel(
+                format!("Subscriptions ({}) <d> remove", state.subscriptions.len()),
+                state.focus == Focus::Subscriptions,
+            ))
+            .highlight_symbol(HIGHLIGHT_SYMBOL)
+            .highlight_style(colors.base05)
+            .highlight_spacing(HighlightSpacing::Always);
+        StatefulWidget::render(
+            subscriptions,
+            subscriptions_area,
+            buf,
+            &mut state.subscriptions_table,
+        );
+
+        let channels = &state.channels;
+        let rows = channels
+            .channels
+            .iter()
+            .map(|(channel, count)| (channel, count, ""))
+            .chain(
+                channels
+                    .shard_channels
+                    .iter()
+                    .map(|(channel, count)| (channel, count, " (shard)")),
+            )
+            .map(|(channel, count, shard)| {
+                Row::new([format!("{channel}{shard}"), count.to_string()]).fg(colors.base04)
+            });
+        let header = Row::new(["Channel", "Subscribers"])
+            .bold()
+            .fg(colors.base04)
+            .bg(colors.base02);
+        let active = Table::new(rows, [Constraint::Fill(1), Constraint::Length(11)])
+            .header(header)
+            .block(panel(
+                format!(
+                    "Active channels ({}), {} patterns <r> refresh",
+                    channels.channels.len() + channels.shard_channels.len(),
+                    channels.patterns
+                ),
+                false,
+            ));
+        Widget::render(active, channels_area, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_subscriptions() {
+        let mut pubsub = PubSub::new();
+        assert!(pubsub.subscribe(SubscriptionKind::Channel, "orders".into()));
+        assert!(pubsub.subscribe(SubscriptionKind::Shard, "carts".into()));
+        assert!(!pubsub.subscribe(SubscriptionKind::Channel, "orders".into()));
+
+        assert_eq!(pubsub.publish_kind("carts"), SubscriptionKind::Shard);
+        assert_eq!(pubsub.publish_kind("orders"), SubscriptionKind::Channel);
+
+        // The newest subscription is selected
+        assert!(pubsub.unsubscribe_selected());
+        assert_eq!(
+            pubsub.subscriptions(),
+            [Subscription {
+                kind: SubscriptionKind::Channel,
+                name: "orders".into(),
+            }]
+        );
+        assert!(pubsub.unsubscribe_selected());
+        assert!(!pubsub.unsubscribe_selected());
+    }
+}
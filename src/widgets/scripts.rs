@@ -0,0 +1,375 @@
+use std::path::PathBuf;
+
+use crossterm::event::KeyEvent;
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Stylize},
+    text::{Line, Text},
+    widgets::{
+        Block, BorderType, Borders, Cell, HighlightSpacing, Paragraph, Row, StatefulWidget, Table,
+        TableState, Widget,
+    },
+};
+use tui_textarea::TextArea;
+
+use crate::{
+    config,
+    redis_client::{event::RedisEvent, reply::Reply, scripts::RedisFunction, types::ConsoleEntry},
+    widgets::console::split_text_args,
+};
+
+const HIGHLIGHT_SYMBOL: &str = " >> ";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Focus {
+    Library,
+    Editor,
+    Keys,
+    Argv,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Target {
+    Script(Option<String>),
+    Function(String),
+}
+
+pub struct Scripts {
+    table: TableState,
+    files: Vec<PathBuf>,
+    functions: Vec<RedisFunction>,
+    focus: Focus,
+    editor: TextArea<'static>,
+    keys: TextArea<'static>,
+    argv: TextArea<'static>,
+    target: Target,
+    result: Option<ConsoleEntry>,
+}
+
+impl Scripts {
+    pub fn new() -> Self {
+        let mut editor = TextArea::default();
+        editor.set_placeholder_text("-- Type a Lua script or open one from the library");
+        editor.set_line_number_style(config::get().colors.base03.into());
+
+        let mut keys = TextArea::default();
+        keys.set_placeholder_text("key1 key2 ...");
+        let mut argv = TextArea::default();
+        argv.set_placeholder_text("arg1 \"arg 2\" ...");
+
+        Self {
+            table: TableState::default(),
+            files: Vec::new(),
+            functions: Vec::new(),
+            focus: Focus::Library,
+            editor,
+            keys,
+            argv,
+            target: Target::Script(None),
+            result: None,
+        }
+    }
+
+    /// Lists the `.lua` files of the configured script directory.
+    pub fn load_files(&mut self) {
+        let dir = config::get().scripts_dir();
+        let entries = match std::fs::read_dir(&dir) {
+            Ok(entries) => entries,
+            Err(err) => {
+                log::debug!("Failed to read scripts directory {dir:?}: {err:?}");
+                self.files.clear();
+                return;
+            }
+        };
+
+        let mut files: Vec<PathBuf> = entries
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|path| path.extension().is_some_and(|ext| ext == "lua"))
+            .collect();
+        files.sort();
+        self.files = files;
+        self.clamp_selection();
+    }
+
+    pub fn set_functions(&mut self, functions: Vec<RedisFunction>) {
+        self.functions = functions;
+        self.clamp_selection();
+    }
+
+    pub fn set_result(&mut self, result: ConsoleEntry) {
+        self.result = Some(result);
+    }
+
+    fn len(&self) -> usize {
+        self.files.len() + self.functions.len()
+    }
+
+    fn clamp_selection(&mut self) {
+        let selected = match self.table.selected() {
+            _ if self.len() == 0 => None,
+            Some(i) => Some(i.min(self.len() - 1)),
+            None => Some(0),
+        };
+        self.table.select(selected);
+    }
+
+    /// Whether keys should be typed into one of the text inputs.
+    pub fn is_editing(&self) -> bool {
+        self.focus != Focus::Library
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) {
+        match self.focus {
+            Focus::Editor => {
+                if self.editor.input(key) {
+                    if let Target::Function(_) = self.target {
+                        self.target = Target::Script(None);
+                    }
+                }
+            }
+            Focus::Keys => {
+                self.keys.input(key);
+            }
+            Focus::Argv => {
+                self.argv.input(key);
+            }
+            Focus::Library => {}
+        }
+    }
+
+    pub fn next_focus(&mut self) {
+        self.focus = match self.focus {
+            Focus::Library => Focus::Editor,
+            Focus::Editor => Focus::Keys,
+            Focus::Keys => Focus::Argv,
+            Focus::Argv => Focus::Library,
+        };
+    }
+
+    pub fn focus_library(&mut self) {
+        self.focus = Focus::Library;
+    }
+
+    pub fn scroll_next(&mut self) {
+        if self.len() == 0 {
+            return;
+        }
+        let next = self.table.selected().map_or(0, |i| (i + 1) % self.len());
+        self.table.select(Some(next));
+    }
+
+    pub fn scroll_previous(&mut self) {
+        if self.len() == 0 {
+            return;
+        }
+        let last = self.len() - 1;
+        let previous = self
+            .table
+            .selected()
+            .map_or(last, |i| (i + last) % self.len());
+        self.table.select(Some(previous));
+    }
+
+    /// Opens the selected script file in the editor or picks the selected function as the target.
+    pub fn select(&mut self) {
+        let Some(index) = self.table.selected() else {
+            return;
+        };
+
+        if let Some(path) = self.files.get(index) {
+            match std::fs::read_to_string(path) {
+                Ok(source) => {
+                    let mut editor = TextArea::from(source.lines());
+                    editor.set_line_number_style(config::get().colors.base03.into());
+                    self.editor = editor;
+                    self.target = Target::Script(
+                        path.file_name()
+                            .map(|name| name.to_string_lossy().into_owned()),
+                    );
+                    self.focus = Focus::Editor;
+                }
+                Err(err) => {
+                    self.result = Some(ConsoleEntry {
+                        command: format!("open {}", path.display()),
+                        reply: Reply::Error(err.to_string()),
+                    });
+                }
+            }
+        } else if let Some(function) = self.functions.get(index - self.files.len()) {
+            self.target = Target::Function(function.name.clone());
+            self.focus = Focus::Keys;
+        }
+    }
+
+    /// Builds the event that runs the current target with the KEYS and ARGV typed in the inputs.
+    pub fn run(&mut self) -> Option<RedisEvent> {
+        let parse = |input: &TextArea<'static>| split_text_args(&input.lines().join(" "));
+
+        let (keys, args) = match (parse(&self.keys), parse(&self.argv)) {
+            (Ok(keys), Ok(args)) => (keys, args),
+            (Err(err), _) | (_, Err(err)) => {
+                self.result = Some(ConsoleEntry {
+                    command: "run".into(),
+                    reply: Reply::Error(err),
+                });
+                return None;
+            }
+        };
+
+        match self.target {
+            Target::Function(ref name) => Some(RedisEvent::CallFunction {
+                name: name.clone(),
+                keys,
+                args,
+            }),
+            Target::Script(_) => {
+                let source = self.editor.lines().join("\n");
+                if source.trim().is_empty() {
+                    return None;
+                }
+                Some(RedisEvent::RunScript { source, keys, args })
+            }
+        }
+    }
+}
+
+pub struct ScriptsWidget;
+
+impl ScriptsWidget {
+    fn block(title: String, focused: bool) -> Block<'static> {
+        let colors = config::get().colors;
+        Block::new()
+            .bg(colors.base00)
+            .fg(colors.base04)
+            .border_style(if focused {
+                colors.base0d
+            } else {
+                colors.base04
+            })
+            .border_type(BorderType::Rounded)
+            .borders(Borders::ALL)
+            .title(title)
+    }
+
+    fn render_library(&self, state: &mut Scripts, area: Rect, buf: &mut ratatui::prelude::Buffer) {
+        let colors = config::get().colors;
+
+        let block = Self::block("Library".into(), state.focus == Focus::Library);
+        let table_area = block.inner(area);
+        block.render(area, buf);
+
+        let header = Row::new(["Kind", "Name"].map(|h| Cell::from(h.bold())))
+            .bottom_margin(1)
+            .fg(colors.base04)
+            .bg(colors.base02);
+
+        let files = state.files.iter().map(|path| {
+            (
+                Cell::from(" LUA ".bg(colors.base0b).fg(colors.base00)),
+                Cell::from(
+                    path.file_name()
+                        .map(|name| name.to_string_lossy().into_owned())
+                        .unwrap_or_default(),
+                ),
+            )
+        });
+
+        let functions = state.functions.iter().map(|function| {
+            let kind = if function.is_read_only() {
+                " FN RO "
+            } else {
+                " FN "
+            };
+            (
+                Cell::from(kind.bg(colors.base0e).fg(colors.base00)),
+                Cell::from(format!("{}.{}", function.library, function.name)),
+            )
+        });
+
+        let rows = files
+            .chain(functions)
+            .enumerate()
+            .map(|(idx, (kind, name))| {
+                Row::new([kind, name])
+                    .fg(colors.base04)
+                    .bg(if idx % 2 == 0 {
+                        colors.base00
+                    } else {
+                        colors.base01
+                    })
+            });
+
+        let table = Table::new(rows, [Constraint::Length(7), Constraint::Fill(1)])
+            .header(header)
+            .highlight_symbol(HIGHLIGHT_SYMBOL)
+            .highlight_style(colors.base05)
+            .highlight_spacing(HighlightSpacing::Always);
+
+        StatefulWidget::render(table, table_area, buf, &mut state.table);
+    }
+}
+
+impl StatefulWidget for ScriptsWidget {
+    type State = Scripts;
+
+    fn render(self, area: Rect, buf: &mut ratatui::prelude::Buffer, state: &mut Self::State) {
+        let colors = config::get().colors;
+
+        let [library_area, work_area] =
+            Layout::horizontal([Constraint::Percentage(30), Constraint::Fill(1)]).areas(area);
+
+        let [editor_area, inputs_area, result_area] = Layout::vertical([
+            Constraint::Fill(2),
+            Constraint::Length(3),
+            Constraint::Fill(1),
+        ])
+        .areas(work_area);
+
+        let [keys_area, argv_area] =
+            Layout::horizontal([Constraint::Percentage(50), Constraint::Percentage(50)])
+                .areas(inputs_area);
+
+        self.render_library(state, library_area, buf);
+
+        let title = match state.target {
+            Target::Script(Some(ref name)) => format!("Script: {name} (<Ctrl-r> runs)"),
+            Target::Script(None) => "Script (<Ctrl-r> runs)".into(),
+            Target::Function(ref name) => format!("Function: {name} (<Ctrl-r> calls)"),
+        };
+
+        let focus = state.focus;
+        state
+            .editor
+            .set_block(Self::block(title, focus == Focus::Editor));
+        state.editor.set_cursor_style(if focus == Focus::Editor {
+            ratatui::style::Style::default().reversed()
+        } else {
+            ratatui::style::Style::default()
+        });
+        state.editor.render(editor_area, buf);
+
+        state
+            .keys
+            .set_block(Self::block("KEYS".into(), focus == Focus::Keys));
+        state.keys.render(keys_area, buf);
+
+        state
+            .argv
+            .set_block(Self::block("ARGV".into(), focus == Focus::Argv));
+        state.argv.render(argv_area, buf);
+
+        let block = Self::block("Result".into(), false);
+        let inner = block.inner(result_area);
+        block.render(result_area, buf);
+
+        if let Some(ref result) = state.result {
+            let mut lines = vec![Line::from(format!("> {}", result.command))
+                .fg(colors.base0d)
+                .bold()];
+            lines.extend(result.reply.lines());
+            Paragraph::new(Text::from(lines))
+                .bg(Color::Reset)
+                .render(inner, buf);
+        }
+    }
+}
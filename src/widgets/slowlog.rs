@@ -0,0 +1,352 @@
+use std::time::{Duration, Instant};
+
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    style::Stylize,
+    widgets::{
+        Block, BorderType, Borders, HighlightSpacing, Paragraph, Row, StatefulWidget, Table,
+        TableState, Widget, Wrap,
+    },
+};
+
+use crate::{
+    config,
+    redis_client::slowlog::{self, CommandStats, SlowlogEntry},
+};
+
+const HIGHLIGHT_SYMBOL: &str = " >> ";
+/// Rows of the aggregate panel, the slowest commands in total.
+const STATS_ROWS: usize = 8;
+/// How long ticks wait before fetching again after a failed fetch.
+pub const RETRY_DELAY: Duration = Duration::from_secs(10);
+
+/// `1970-01-01 00:00:00`, in UTC.
+pub fn timestamp(unix: u64) -> String {
+    let days = (unix / 86400) as i64;
+    let secs = unix % 86400;
+
+    // Days to a civil date, after Howard Hinnant's `civil_from_days`
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{year:04}-{month:02}-{day:02} {:02}:{:02}:{:02}",
+        secs / 3600,
+        secs % 3600 / 60,
+        secs % 60
+    )
+}
+
+fn duration(us: u64) -> String {
+    match us {
+        0..=999 => format!("{us}µs"),
+        1000..=999_999 => format!("{:.2}ms", us as f64 / 1000.0),
+        _ => format!("{:.2}s", us as f64 / 1_000_000.0),
+    }
+}
+
+/// The arguments as typed in the console, quoting the ones with spaces.
+fn command_line(args: &[String]) -> String {
+    args.iter()
+        .map(|arg| {
+            if arg.is_empty() || arg.contains(char::is_whitespace) {
+                format!("{arg:?}")
+            } else {
+                arg.clone()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// The entries of `SLOWLOG GET`, newest first, with the time spent per command.
+pub struct Slowlog {
+    entries: Vec<SlowlogEntry>,
+    filter: String,
+    /// Indexes of the entries whose command matches the filter.
+    visible: Vec<usize>,
+    stats: Vec<CommandStats>,
+    table: TableState,
+    /// Whether a fetch is on its way, so ticks do not queue up more of them.
+    pending: bool,
+    /// Until when ticks do not fetch, after a failed fetch.
+    retry_at: Option<Instant>,
+}
+
+impl Slowlog {
+    pub fn new() -> Self {
+        Self {
+            entries: Vec::new(),
+            filter: String::new(),
+            visible: Vec::new(),
+            stats: Vec::new(),
+            table: TableState::default(),
+            pending: false,
+            retry_at: None,
+        }
+    }
+
+    /// Marks a fetch as sent, `false` when one is still on its way or a failed one is too
+    /// recent.
+    pub fn request(&mut self) -> bool {
+        if self.retry_at.is_some_and(|at| Instant::now() < at) {
+            return false;
+        }
+        !std::mem::replace(&mut self.pending, true)
+    }
+
+    /// Clears the fetch on its way, ticks try again after [`RETRY_DELAY`].
+    pub fn fail(&mut self) {
+        self.pending = false;
+        self.retry_at = Some(Instant::now() + RETRY_DELAY);
+    }
+
+    pub fn set_entries(&mut self, entries: Vec<SlowlogEntry>) {
+        let selected = self.selected().map(|entry| entry.id);
+        self.pending = false;
+        self.retry_at = None;
+        self.entries = entries;
+        self.apply(selected);
+    }
+
+    pub fn filter(&self) -> &str {
+        &self.filter
+    }
+
+    pub fn set_filter(&mut self, filter: String) {
+        let selected = self.selected().map(|entry| entry.id);
+        self.filter = filter;
+        self.apply(selected);
+    }
+
+    fn apply(&mut self, selected: Option<u64>) {
+        let filter = self.filter.to_uppercase();
+        let entries = &self.entries;
+        self.visible = (0..entries.len())
+            .filter(|idx| entries[*idx].command().contains(&filter))
+            .collect();
+        self.stats = slowlog::aggregate(self.visible.iter().map(|idx| &entries[*idx]));
+
+        let index = selected
+            .and_then(|id| self.visible.iter().position(|idx| entries[*idx].id == id))
+            .or_else(|| (!self.visible.is_empty()).then_some(0));
+        self.table.select(index);
+    }
+
+    pub fn selected(&self) -> Option<&SlowlogEntry> {
+        self.table
+            .selected()
+            .and_then(|i| self.visible.get(i))
+            .and_then(|idx| self.entries.get(*idx))
+    }
+
+    pub fn scroll_next(&mut self) {
+        if self.visible.is_empty() {
+            return;
+        }
+        let next = self
+            .table
+            .selected()
+            .map_or(0, |i| (i + 1) % self.visible.len());
+        self.table.select(Some(next));
+    }
+
+    pub fn scroll_previous(&mut self) {
+        if self.visible.is_empty() {
+            return;
+        }
+        let last = self.visible.len() - 1;
+        let previous = self
+            .table
+            .selected()
+            .map_or(last, |i| (i + last) % self.visible.len());
+        self.table.select(Some(previous));
+    }
+}
+
+pub struct SlowlogWidget;
+
+impl StatefulWidget for SlowlogWidget {
+    type State = Slowlog;
+
+    fn render(self, area: Rect, buf: &mut ratatui::prelude::Buffer, state: &mut Self::State) {
+        let colors = config::get().colors;
+
+        let mut title = format!(
+            "Slowlog ({} of {})",
+            state.visible.len(),
+            state.entries.len()
+        );
+        if !state.filter.is_empty() {
+            title.push_str(&format!(" for commands matching {:?}", state.filter));
+        }
+        title.push_str(" <f> filter, <R> reset, <r> refresh");
+
+        let block = Block::new()
+            .bg(colors.base00)
+            .fg(colors.base04)
+            .border_type(BorderType::Rounded)
+            .borders(Borders::ALL)
+            .title(title);
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        let stats_height = state.stats.len().min(STATS_ROWS) as u16 + 3;
+        let [table_area, args_area, stats_area] = Layout::vertical([
+            Constraint::Fill(1),
+            Constraint::Length(4),
+            Constraint::Length(stats_height),
+        ])
+        .areas(inner);
+
+        let header = Row::new(["Id", "Time (UTC)", "Duration", "Client", "Command"])
+            .bold()
+            .bottom_margin(1)
+            .fg(colors.base04)
+            .bg(colors.base02);
+
+        let rows = state.visible.iter().enumerate().map(|(i, idx)| {
+            let entry = &state.entries[*idx];
+            let client = if entry.client_name.is_empty() {
+                entry.client_addr.clone()
+            } else {
+                format!("{} ({})", entry.client_addr, entry.client_name)
+            };
+
+            Row::new([
+                entry.id.to_string(),
+                timestamp(entry.timestamp),
+                duration(entry.duration_us),
+                client,
+                command_line(&entry.args),
+            ])
+            .fg(colors.base04)
+            .bg(if i % 2 == 0 {
+                colors.base00
+            } else {
+                colors.base01
+            })
+        });
+
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(8),
+                Constraint::Length(20),
+                Constraint::Length(10),
+                Constraint::Length(28),
+                Constraint::Fill(1),
+            ],
+        )
+        .header(header)
+        .highlight_symbol(HIGHLIGHT_SYMBOL)
+        .highlight_style(colors.base05)
+        .highlight_spacing(HighlightSpacing::Always);
+        StatefulWidget::render(table, table_area, buf, &mut state.table);
+
+        let args = state
+            .selected()
+            .map(|entry| command_line(&entry.args))
+            .unwrap_or_default();
+        Paragraph::new(args)
+            .wrap(Wrap { trim: false })
+            .fg(colors.base05)
+            .block(
+                Block::new()
+                    .borders(Borders::TOP)
+                    .border_style(colors.base03)
+                    .title("Arguments"),
+            )
+            .render(args_area, buf);
+
+        let header = Row::new(["Command", "Count", "Total", "Average", "Max"])
+            .bold()
+            .fg(colors.base04)
+            .bg(colors.base02);
+        let rows = state.stats.iter().take(STATS_ROWS).map(|stat| {
+            Row::new([
+                stat.command.clone(),
+                stat.count.to_string(),
+                duration(stat.total_us),
+                duration(stat.average_us()),
+                duration(stat.max_us),
+            ])
+            .fg(colors.base04)
+        });
+        let stats = Table::new(rows, [Constraint::Fill(1); 5])
+            .header(header)
+            .block(
+                Block::new()
+                    .borders(Borders::TOP)
+                    .border_style(colors.base03)
+                    .title("By command"),
+            );
+        Widget::render(stats, stats_area, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn entry(id: u64, args: &[&str]) -> SlowlogEntry {
+        SlowlogEntry {
+            id,
+            timestamp: 0,
+            duration_us: 10,
+            args: args.iter().map(|arg| arg.to_string()).collect(),
+            client_addr: String::new(),
+            client_name: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_timestamp() {
+        assert_eq!(timestamp(0), "1970-01-01 00:00:00");
+        assert_eq!(timestamp(1_709_210_096), "2024-02-29 12:34:56");
+    }
+
+    #[test]
+    fn test_failed_fetch() {
+        let mut slowlog = Slowlog::new();
+        assert!(slowlog.request());
+        slowlog.fail();
+        assert!(!slowlog.request());
+
+        slowlog.retry_at = Some(Instant::now());
+        assert!(slowlog.request());
+        slowlog.set_entries(Vec::new());
+        assert_eq!(slowlog.retry_at, None);
+    }
+
+    #[test]
+    fn test_filter_by_command() {
+        let mut slowlog = Slowlog::new();
+        assert!(slowlog.request());
+        assert!(!slowlog.request());
+
+        slowlog.set_entries(vec![
+            entry(3, &["KEYS", "*"]),
+            entry(2, &["hgetall", "user:1"]),
+            entry(1, &["keys", "user key"]),
+        ]);
+        assert!(slowlog.request());
+        slowlog.scroll_next();
+        slowlog.scroll_next();
+
+        slowlog.set_filter("key".into());
+        assert_eq!(slowlog.visible, [0, 2]);
+        assert_eq!(slowlog.selected().map(|entry| entry.id), Some(1));
+        assert_eq!(slowlog.stats.len(), 1);
+        assert_eq!(command_line(&slowlog.entries[2].args), "keys \"user key\"");
+    }
+}
@@ -0,0 +1,222 @@
+use ratatui::{
+    layout::{Constraint, Rect},
+    style::Stylize,
+    text::{Line, Text},
+    widgets::{
+        Block, BorderType, Borders, Cell, HighlightSpacing, Paragraph, Row, StatefulWidget, Table,
+        TableState, Widget,
+    },
+};
+
+use crate::{
+    config,
+    redis_client::{
+        client::{command_from_args, display_command},
+        types::TransactionOutcome,
+    },
+};
+
+const HIGHLIGHT_SYMBOL: &str = " >> ";
+
+/// Commands queued while a transaction is open, submitted together with `MULTI`/`EXEC`.
+pub struct Transaction {
+    active: bool,
+    watch: bool,
+    table: TableState,
+    queued: Vec<Vec<String>>,
+    outcome: Option<TransactionOutcome>,
+}
+
+impl Transaction {
+    pub fn new() -> Self {
+        Self {
+            active: false,
+            watch: false,
+            table: TableState::default(),
+            queued: Vec::new(),
+            outcome: None,
+        }
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.active
+    }
+
+    pub fn is_watching(&self) -> bool {
+        self.watch
+    }
+
+    /// The panel stays around after `EXEC` to show the results.
+    pub fn is_visible(&self) -> bool {
+        self.active || self.outcome.is_some()
+    }
+
+    pub fn queued(&self) -> &[Vec<String>] {
+        &self.queued
+    }
+
+    pub fn begin(&mut self) {
+        self.active = true;
+        self.outcome = None;
+    }
+
+    pub fn queue(&mut self, args: Vec<String>) {
+        self.queued.push(args);
+        self.table.select(Some(self.queued.len() - 1));
+    }
+
+    pub fn remove_selected(&mut self) {
+        let Some(index) = self.table.selected().filter(|&i| i < self.queued.len()) else {
+            return;
+        };
+
+        self.queued.remove(index);
+        self.table.select(match self.queued.len() {
+            0 => None,
+            len => Some(index.min(len - 1)),
+        });
+    }
+
+    /// Flips the `WATCH` option and returns whether it is now enabled.
+    pub fn toggle_watch(&mut self) -> bool {
+        self.watch = !self.watch;
+        self.watch
+    }
+
+    /// Closes the transaction and hands over the queued commands for `EXEC`.
+    pub fn take_queued(&mut self) -> Vec<Vec<String>> {
+        self.active = false;
+        self.watch = false;
+        self.table.select(None);
+        std::mem::take(&mut self.queued)
+    }
+
+    pub fn discard(&mut self) {
+        self.take_queued();
+        self.outcome = None;
+    }
+
+    pub fn set_outcome(&mut self, outcome: TransactionOutcome) {
+        self.outcome = Some(outcome);
+    }
+
+    pub fn scroll_next(&mut self) {
+        if self.queued.is_empty() {
+            return;
+        }
+        let next = self
+            .table
+            .selected()
+            .map_or(0, |i| (i + 1) % self.queued.len());
+        self.table.select(Some(next));
+    }
+
+    pub fn scroll_previous(&mut self) {
+        if self.queued.is_empty() {
+            return;
+        }
+        let last = self.queued.len() - 1;
+        let previous = self
+            .table
+            .selected()
+            .map_or(last, |i| (i + last) % self.queued.len());
+        self.table.select(Some(previous));
+    }
+}
+
+fn outcome_lines(outcome: &TransactionOutcome) -> Vec<Line<'static>> {
+    let colors = config::get().colors;
+
+    match outcome {
+        TransactionOutcome::Executed(entries) => entries
+            .iter()
+            .enumerate()
+            .flat_map(|(idx, entry)| {
+                let header = Line::from(format!("{}) {}", idx + 1, entry.command))
+                    .fg(colors.base0d)
+                    .bold();
+                std::iter::once(header).chain(entry.reply.lines())
+            })
+            .collect(),
+        TransactionOutcome::Aborted => {
+            vec![
+                Line::from("EXEC aborted: a watched key was changed, nothing was applied")
+                    .fg(colors.base08),
+            ]
+        }
+        TransactionOutcome::Failed(err) => {
+            vec![Line::from(format!("Transaction failed: {err}")).fg(colors.base08)]
+        }
+    }
+}
+
+pub struct TransactionWidget {
+    focused: bool,
+}
+
+impl TransactionWidget {
+    pub fn new(focused: bool) -> Self {
+        Self { focused }
+    }
+}
+
+impl StatefulWidget for TransactionWidget {
+    type State = Transaction;
+
+    fn render(self, area: Rect, buf: &mut ratatui::prelude::Buffer, state: &mut Self::State) {
+        let colors = config::get().colors;
+
+        let title = if state.active {
+            format!(
+                "MULTI: {} queued{} (<Enter> exec, <x> remove, <w> watch, <D> discard)",
+                state.queued.len(),
+                if state.watch { ", WATCH" } else { "" },
+            )
+        } else {
+            "EXEC results".into()
+        };
+
+        let block = Block::new()
+            .bg(colors.base00)
+            .fg(colors.base04)
+            .border_style(if self.focused {
+                colors.base0d
+            } else {
+                colors.base09
+            })
+            .border_type(BorderType::Rounded)
+            .borders(Borders::ALL)
+            .title(title);
+
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        if !state.active {
+            if let Some(ref outcome) = state.outcome {
+                Paragraph::new(Text::from(outcome_lines(outcome))).render(inner, buf);
+            }
+            return;
+        }
+
+        let rows = state.queued.iter().enumerate().map(|(idx, args)| {
+            let command = command_from_args(args)
+                .map(|cmd| display_command(&cmd))
+                .unwrap_or_default();
+
+            Row::new([Cell::from(format!("{})", idx + 1)), Cell::from(command)])
+                .fg(colors.base04)
+                .bg(if idx % 2 == 0 {
+                    colors.base00
+                } else {
+                    colors.base01
+                })
+        });
+
+        let table = Table::new(rows, [Constraint::Length(4), Constraint::Fill(1)])
+            .highlight_symbol(HIGHLIGHT_SYMBOL)
+            .highlight_style(colors.base05)
+            .highlight_spacing(HighlightSpacing::Always);
+
+        StatefulWidget::render(table, inner, buf, &mut state.table);
+    }
+}
@@ -0,0 +1,478 @@
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+
+use byte_unit::{Byte, UnitType};
+use ratatui::{
+    layout::{Constraint, Rect},
+    style::Stylize,
+    text::{Line, Span},
+    widgets::{Cell, HighlightSpacing, Row, StatefulWidget, Table, TableState},
+};
+
+use crate::{config, redis_client::types::KeyMeta, widgets::filter::glob_escape};
+
+const HIGHLIGHT_SYMBOL: &str = " >> ";
+
+#[derive(Debug, Default)]
+struct Folder {
+    folders: BTreeMap<String, Folder>,
+    keys: BTreeMap<String, KeyMeta>,
+    /// Keys anywhere below this folder.
+    count: usize,
+    /// Sum of `MEMORY USAGE` of those keys.
+    memory: u128,
+}
+
+impl Folder {
+    fn insert(&mut self, path: &[&str], leaf: &str, meta: KeyMeta) {
+        self.count += 1;
+        self.memory += meta.size;
+
+        match path.split_first() {
+            Some((name, rest)) => self
+                .folders
+                .entry((*name).to_owned())
+                .or_default()
+                .insert(rest, leaf, meta),
+            None => {
+                self.keys.insert(leaf.to_owned(), meta);
+            }
+        }
+    }
+
+    fn remove(&mut self, path: &[&str], leaf: &str) -> Option<KeyMeta> {
+        let removed = match path.split_first() {
+            Some((name, rest)) => {
+                let folder = self.folders.get_mut(*name)?;
+                let removed = folder.remove(rest, leaf);
+                if folder.count == 0 {
+                    self.folders.remove(*name);
+                }
+                removed
+            }
+            None => self.keys.remove(leaf),
+        }?;
+
+        self.count -= 1;
+        self.memory -= removed.size;
+        Some(removed)
+    }
+
+    fn get(&self, path: &[&str], leaf: &str) -> Option<&KeyMeta> {
+        match path.split_first() {
+            Some((name, rest)) => self.folders.get(*name)?.get(rest, leaf),
+            None => self.keys.get(leaf),
+        }
+    }
+}
+
+/// Loaded keys grouped into folders on a namespace delimiter, e.g. `app:user:1` lives in
+/// `app:` > `user:`. Keys are added page by page as SCAN results come in.
+#[derive(Debug)]
+pub struct KeyTree {
+    delimiter: String,
+    root: Folder,
+}
+
+impl KeyTree {
+    pub fn new(delimiter: impl Into<String>) -> Self {
+        Self {
+            delimiter: delimiter.into(),
+            root: Folder::default(),
+        }
+    }
+
+    fn split<'a>(&self, key: &'a str) -> (Vec<&'a str>, &'a str) {
+        if self.delimiter.is_empty() {
+            return (Vec::new(), key);
+        }
+
+        let mut segments: Vec<&str> = key.split(self.delimiter.as_str()).collect();
+        let leaf = segments.pop().unwrap_or_default();
+        (segments, leaf)
+    }
+
+    /// Adds a key, replacing it when the same key was already loaded by another page.
+    pub fn insert(&mut self, meta: KeyMeta) {
+        let key = meta.key.clone();
+        let (path, leaf) = self.split(&key);
+
+        self.root.remove(&path, leaf);
+        self.root.insert(&path, leaf, meta);
+    }
+
+    pub fn get(&self, key: &str) -> Option<&KeyMeta> {
+        let (path, leaf) = self.split(key);
+        self.root.get(&path, leaf)
+    }
+
+    pub fn remove(&mut self, key: &str) -> Option<KeyMeta> {
+        let (path, leaf) = self.split(key);
+        self.root.remove(&path, leaf)
+    }
+
+    pub fn len(&self) -> usize {
+        self.root.count
+    }
+
+    pub fn clear(&mut self) {
+        self.root = Folder::default();
+    }
+
+    /// Rows of the expanded part of the tree, folders before keys at every level.
+    pub fn rows(&self, expanded: &HashSet<String>) -> Vec<TreeRow> {
+        let mut rows = Vec::new();
+        self.push_rows(&self.root, "", 0, expanded, &mut rows);
+        rows
+    }
+
+    fn push_rows(
+        &self,
+        folder: &Folder,
+        prefix: &str,
+        depth: usize,
+        expanded: &HashSet<String>,
+        rows: &mut Vec<TreeRow>,
+    ) {
+        for (name, child) in &folder.folders {
+            let path = format!("{prefix}{name}{}", self.delimiter);
+            let is_expanded = expanded.contains(&path);
+
+            rows.push(TreeRow {
+                depth,
+                name: format!("{name}{}", self.delimiter),
+                path: path.clone(),
+                kind: TreeRowKind::Folder {
+                    expanded: is_expanded,
+                    count: child.count,
+                    memory: child.memory,
+                },
+            });
+
+            if is_expanded {
+                self.push_rows(child, &path, depth + 1, expanded, rows);
+            }
+        }
+
+        for (name, meta) in &folder.keys {
+            rows.push(TreeRow {
+                depth,
+                name: name.clone(),
+                path: meta.key.clone(),
+                kind: TreeRowKind::Key,
+            });
+        }
+    }
+
+    /// The folder containing `prefix`, `None` for top level folders.
+    pub fn parent(&self, prefix: &str) -> Option<String> {
+        let trimmed = prefix.strip_suffix(self.delimiter.as_str())?;
+        let end = trimmed.rfind(self.delimiter.as_str())?;
+        Some(trimmed[..end + self.delimiter.len()].to_owned())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TreeRowKind {
+    Folder {
+        expanded: bool,
+        count: usize,
+        memory: u128,
+    },
+    Key,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TreeRow {
+    pub depth: usize,
+    pub name: String,
+    /// The full key, or the prefix shared by the keys of a folder.
+    pub path: String,
+    pub kind: TreeRowKind,
+}
+
+/// Tree view state of the keyspace: the loaded keys, the expanded folders and the
+/// rows currently visible.
+pub struct KeyTreeView {
+    tree: KeyTree,
+    expanded: HashSet<String>,
+    rows: Vec<TreeRow>,
+    table: TableState,
+}
+
+impl KeyTreeView {
+    pub fn new(delimiter: impl Into<String>) -> Self {
+        Self {
+            tree: KeyTree::new(delimiter),
+            expanded: HashSet::new(),
+            rows: Vec::new(),
+            table: TableState::default(),
+        }
+    }
+
+    pub fn insert_keys(&mut self, keys: impl IntoIterator<Item = KeyMeta>) {
+        for meta in keys {
+            self.tree.insert(meta);
+        }
+        self.rebuild();
+    }
+
+    pub fn remove_key(&mut self, key: &str) {
+        if self.tree.remove(key).is_some() {
+            self.rebuild();
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.tree.clear();
+        self.rebuild();
+    }
+
+    pub fn len(&self) -> usize {
+        self.tree.len()
+    }
+
+    fn rebuild(&mut self) {
+        let selected = self.selected_row().map(|row| row.path.clone());
+        self.rows = self.tree.rows(&self.expanded);
+
+        let index = selected
+            .and_then(|path| self.rows.iter().position(|row| row.path == path))
+            .or_else(|| (!self.rows.is_empty()).then_some(0));
+        self.table.select(index);
+    }
+
+    fn selected_row(&self) -> Option<&TreeRow> {
+        self.table.selected().and_then(|i| self.rows.get(i))
+    }
+
+    pub fn selected_key(&self) -> Option<&KeyMeta> {
+        match self.selected_row()? {
+            TreeRow {
+                kind: TreeRowKind::Key,
+                path,
+                ..
+            } => self.tree.get(path),
+            _ => None,
+        }
+    }
+
+    /// Expands or collapses the selected folder and returns the `SCAN` pattern it narrows
+    /// the keyspace to, `Some(None)` meaning the whole keyspace. `None` when a key is selected.
+    pub fn toggle_selected(&mut self) -> Option<Option<String>> {
+        let row = self.selected_row()?.clone();
+        let TreeRowKind::Folder { expanded, .. } = row.kind else {
+            return None;
+        };
+
+        let prefix = if expanded {
+            self.expanded
+                .retain(|path| !path.starts_with(row.path.as_str()));
+            self.tree.parent(&row.path)
+        } else {
+            self.expanded.insert(row.path.clone());
+            Some(row.path)
+        };
+        self.rebuild();
+
+        Some(prefix.map(|prefix| format!("{}*", glob_escape(&prefix))))
+    }
+
+    /// Selects the `row`th visible row, returns whether it was selected already.
+    pub fn click_row(&mut self, row: usize) -> bool {
+        let index = self.table.offset() + row;
+        if index >= self.rows.len() {
+            return false;
+        }
+
+        let again = self.table.selected() == Some(index);
+        self.table.select(Some(index));
+        again
+    }
+
+    pub fn scroll_next(&mut self) {
+        if self.rows.is_empty() {
+            return;
+        }
+        let next = self
+            .table
+            .selected()
+            .map_or(0, |i| (i + 1) % self.rows.len());
+        self.table.select(Some(next));
+    }
+
+    pub fn scroll_previous(&mut self) {
+        if self.rows.is_empty() {
+            return;
+        }
+        let last = self.rows.len() - 1;
+        let previous = self
+            .table
+            .selected()
+            .map_or(last, |i| (i + last) % self.rows.len());
+        self.table.select(Some(previous));
+    }
+}
+
+fn human_size(size: u128) -> String {
+    format!(
+        "{:.2}",
+        unsafe { Byte::from_u128_unsafe(size) }.get_appropriate_unit(UnitType::Binary)
+    )
+}
+
+pub struct KeyTreeWidget<'a> {
+    selection: &'a BTreeSet<String>,
+}
+
+impl<'a> KeyTreeWidget<'a> {
+    pub fn new(selection: &'a BTreeSet<String>) -> Self {
+        Self { selection }
+    }
+}
+
+impl StatefulWidget for KeyTreeWidget<'_> {
+    type State = KeyTreeView;
+
+    fn render(self, area: Rect, buf: &mut ratatui::prelude::Buffer, state: &mut Self::State) {
+        let colors = config::get().colors;
+
+        let header = Row::new(["Name", "Keys", "Memory"].map(|h| Cell::from(h.bold())))
+            .top_margin(1)
+            .bottom_margin(1)
+            .fg(colors.base04)
+            .bg(colors.base02);
+
+        let rows = state.rows.iter().enumerate().map(|(idx, row)| {
+            let indent = "  ".repeat(row.depth);
+            let selected = row.kind == TreeRowKind::Key && self.selection.contains(&row.path);
+
+            let cells = match row.kind {
+                TreeRowKind::Folder {
+                    expanded,
+                    count,
+                    memory,
+                } => [
+                    Cell::from(Line::from(vec![
+                        Span::raw(indent),
+                        Span::raw(if expanded { "▾ " } else { "▸ " }),
+                        Span::raw(row.name.clone()).bold(),
+                    ])),
+                    Cell::from(count.to_string()),
+                    Cell::from(human_size(memory)),
+                ],
+                TreeRowKind::Key => {
+                    let meta = state.tree.get(&row.path);
+                    let color = meta
+                        .map(|meta| meta.r_type.color())
+                        .unwrap_or(colors.base04);
+
+                    [
+                        Cell::from(Line::from(vec![
+                            Span::raw(indent),
+                            Span::raw(if selected { "✓ " } else { "• " }).fg(color),
+                            Span::raw(row.name.clone()),
+                        ])),
+                        Cell::from(""),
+                        Cell::from(meta.map(|meta| human_size(meta.size)).unwrap_or_default()),
+                    ]
+                }
+            };
+
+            Row::new(cells).fg(colors.base04).bg(if selected {
+                colors.base02
+            } else if idx % 2 == 0 {
+                colors.base00
+            } else {
+                colors.base01
+            })
+        });
+
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Fill(1),
+                Constraint::Length(8),
+                Constraint::Length(12),
+            ],
+        )
+        .header(header)
+        .highlight_symbol(HIGHLIGHT_SYMBOL)
+        .highlight_style(colors.base05)
+        .highlight_spacing(HighlightSpacing::Always);
+
+        StatefulWidget::render(table, area, buf, &mut state.table);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::redis_client::types::{KeyValue, RedisType};
+
+    fn meta(key: &str, size: u128) -> KeyMeta {
+        KeyMeta {
+            key: key.into(),
+            r_type: RedisType::String,
+            size,
+            ttl: -1,
+            value: KeyValue::Unknown,
+            encoding: None,
+            idle: None,
+            freq: None,
+        }
+    }
+
+    #[test]
+    fn test_tree_aggregates() {
+        let mut tree = KeyTree::new(":");
+        tree.insert(meta("app:user:1", 10));
+        tree.insert(meta("app:user:2", 20));
+        tree.insert(meta("app:user:2", 5));
+        tree.insert(meta("app:session", 1));
+        tree.insert(meta("plain", 3));
+
+        assert_eq!(tree.len(), 4);
+
+        let rows = tree.rows(&HashSet::from(["app:".to_owned()]));
+        let summary: Vec<(usize, &str, TreeRowKind)> = rows
+            .iter()
+            .map(|row| (row.depth, row.path.as_str(), row.kind.clone()))
+            .collect();
+
+        assert_eq!(
+            summary,
+            [
+                (
+                    0,
+                    "app:",
+                    TreeRowKind::Folder {
+                        expanded: true,
+                        count: 3,
+                        memory: 16,
+                    }
+                ),
+                (
+                    1,
+                    "app:user:",
+                    TreeRowKind::Folder {
+                        expanded: false,
+                        count: 2,
+                        memory: 15,
+                    }
+                ),
+                (1, "app:session", TreeRowKind::Key),
+                (0, "plain", TreeRowKind::Key),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parent_and_escape() {
+        let tree = KeyTree::new("::");
+        assert_eq!(tree.parent("a::b::"), Some("a::".to_owned()));
+        assert_eq!(tree.parent("a::"), None);
+        assert_eq!(glob_escape("cache[1]:*"), "cache\\[1\\]:\\*");
+    }
+}
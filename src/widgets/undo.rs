@@ -0,0 +1,148 @@
+use std::time::SystemTime;
+
+use ratatui::{
+    layout::{Constraint, Flex, Layout, Rect},
+    style::Stylize,
+    widgets::{
+        Block, BorderType, Borders, Cell, Clear, HighlightSpacing, Row, StatefulWidget, Table,
+        TableState, Widget,
+    },
+};
+
+use crate::{config, redis_client::undo::UndoEntry};
+
+const HIGHLIGHT_SYMBOL: &str = " >> ";
+
+pub struct UndoHistory {
+    table: TableState,
+    entries: Vec<UndoEntry>,
+}
+
+impl UndoHistory {
+    pub fn new() -> Self {
+        Self {
+            table: TableState::default(),
+            entries: Vec::new(),
+        }
+    }
+
+    pub fn set_entries(&mut self, entries: Vec<UndoEntry>) {
+        self.entries = entries;
+        let selected = match self.table.selected() {
+            _ if self.entries.is_empty() => None,
+            Some(i) => Some(i.min(self.entries.len() - 1)),
+            None => Some(0),
+        };
+        self.table.select(selected);
+    }
+
+    /// Number of changes to revert to get back to the state before the selected one.
+    pub fn selected_depth(&self) -> Option<usize> {
+        self.table.selected().map(|i| i + 1)
+    }
+
+    pub fn scroll_next(&mut self) {
+        if self.entries.is_empty() {
+            return;
+        }
+        let next = self
+            .table
+            .selected()
+            .map_or(0, |i| (i + 1) % self.entries.len());
+        self.table.select(Some(next));
+    }
+
+    pub fn scroll_previous(&mut self) {
+        if self.entries.is_empty() {
+            return;
+        }
+        let last = self.entries.len() - 1;
+        let previous = self
+            .table
+            .selected()
+            .map_or(last, |i| (i + last) % self.entries.len());
+        self.table.select(Some(previous));
+    }
+}
+
+fn elapsed(at: SystemTime) -> String {
+    let secs = at.elapsed().map(|d| d.as_secs()).unwrap_or_default();
+    match secs {
+        0..=59 => format!("{secs}s ago"),
+        60..=3599 => format!("{}m ago", secs / 60),
+        _ => format!("{}h ago", secs / 3600),
+    }
+}
+
+pub struct UndoHistoryWidget;
+
+impl StatefulWidget for UndoHistoryWidget {
+    type State = UndoHistory;
+
+    fn render(self, area: Rect, buf: &mut ratatui::prelude::Buffer, state: &mut Self::State) {
+        let colors = config::get().colors;
+
+        let [popup_area] = Layout::horizontal([Constraint::Percentage(70)])
+            .flex(Flex::Center)
+            .areas(area);
+        let [popup_area] = Layout::vertical([Constraint::Percentage(60)])
+            .flex(Flex::Center)
+            .areas(popup_area);
+
+        let block = Block::new()
+            .bg(colors.base00)
+            .fg(colors.base04)
+            .border_type(BorderType::Rounded)
+            .borders(Borders::ALL)
+            .title("Undo history (<Enter> reverts up to the selected change)");
+
+        let table_area = block.inner(popup_area);
+        Clear.render(popup_area, buf);
+        block.render(popup_area, buf);
+
+        let widths = [
+            Constraint::Length(10),
+            Constraint::Fill(2),
+            Constraint::Fill(1),
+        ];
+        let header = Row::new(["When", "Command", "Keys"].map(|h| Cell::from(h.bold())))
+            .bottom_margin(1)
+            .fg(colors.base04)
+            .bg(colors.base02);
+
+        let rows = state.entries.iter().enumerate().map(|(idx, entry)| {
+            let keys = entry
+                .keys
+                .iter()
+                .map(|snapshot| {
+                    if snapshot.dump.is_some() {
+                        snapshot.key.clone()
+                    } else {
+                        format!("{} (new)", snapshot.key)
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join(", ");
+
+            Row::new([
+                Cell::from(elapsed(entry.at)),
+                Cell::from(entry.command.clone()),
+                Cell::from(keys),
+            ])
+            .fg(colors.base04)
+            .bg(if idx % 2 == 0 {
+                colors.base00
+            } else {
+                colors.base01
+            })
+        });
+
+        let table = Table::new(rows, widths)
+            .header(header)
+            .highlight_symbol(HIGHLIGHT_SYMBOL)
+            .highlight_style(colors.base05)
+            .highlight_spacing(HighlightSpacing::Always);
+
+        StatefulWidget::render(table, table_area, buf, &mut state.table);
+    }
+}